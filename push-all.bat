@@ -1,3 +1,3 @@
 @echo off
 echo Pushing to all configured repositories...
-cargo run -- %*
\ No newline at end of file
+cargo run -- push %*
\ No newline at end of file
@@ -0,0 +1,233 @@
+use anyhow::{anyhow, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use crate::core::operation_log::LOGGED_OPERATIONS;
+use crate::core::repository::AuthType;
+use std::collections::HashMap;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+pub struct Args {
+    /// Run in GUI mode
+    #[clap(long, action)]
+    pub gui: bool,
+    
+    /// Keep running and commit and push local changes every `--interval` seconds
+    #[clap(long, action, conflicts_with = "gui")]
+    pub daemon: bool,
+    
+    /// Seconds between daemon cycles
+    #[clap(long, default_value_t = 300, value_parser = clap::value_parser!(u64).range(1..), requires = "daemon")]
+    pub interval: u64,
+    
+    /// Keep running and commit and push once files in the working directory stop changing
+    #[clap(long, action, conflicts_with_all = ["gui", "daemon"])]
+    pub watch: bool,
+    
+    /// Seconds without file changes before watch mode pushes
+    #[clap(long, default_value_t = 5, value_parser = clap::value_parser!(u64).range(1..))]
+    pub quiet_period: u64,
+    
+    /// Print the results as JSON instead of one line per repository
+    #[clap(long, global = true, action)]
+    pub json: bool,
+    
+    /// Print the refspec, auth type and remote URL of each repository
+    #[clap(short, long, global = true, action, conflicts_with = "quiet")]
+    pub verbose: bool,
+    
+    /// Only print errors and the final summary
+    #[clap(short, long, global = true, action)]
+    pub quiet: bool,
+    
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// How much the CLI prints while it runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+}
+
+impl Args {
+    pub fn verbosity(&self) -> Verbosity {
+        if self.quiet {
+            Verbosity::Quiet
+        } else if self.verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Commit all changes and push them to every repository
+    Push {
+        /// Commit message
+        #[clap(short, long, default_value = "Auto commit")]
+        message: String,
+        
+        /// Branch name
+        #[clap(short, long, default_value = "main")]
+        branch: String,
+        
+        /// Only use the repositories in this group
+        #[clap(short, long)]
+        group: Option<String>,
+        
+        /// Branch per repository, e.g. `web=main,api=develop`. Other repositories use `--branch`.
+        #[clap(long, value_parser = parse_branch_overrides, conflicts_with = "group")]
+        branches: Option<HashMap<String, String>>,
+    },
+    
+    /// Pull a branch from every repository
+    Pull {
+        /// Branch name
+        #[clap(short, long, default_value = "main")]
+        branch: String,
+        
+        /// Only use the repositories in this group
+        #[clap(short, long)]
+        group: Option<String>,
+    },
+    
+    /// Fetch a branch from every repository
+    Fetch {
+        /// Branch name
+        #[clap(short, long, default_value = "main")]
+        branch: String,
+        
+        /// Only use the repositories in this group
+        #[clap(short, long)]
+        group: Option<String>,
+    },
+    
+    /// Clone every repository into a directory
+    Clone {
+        /// Directory the repositories are cloned into
+        #[clap(short, long, default_value = ".")]
+        path: String,
+        
+        /// Also try to clone repositories that already have a checkout in the directory
+        #[clap(long)]
+        include_existing: bool,
+    },
+    
+    /// Create a tag on HEAD and push it to every repository
+    Tag {
+        /// Tag name
+        name: String,
+        
+        /// Tag message, defaults to "Release <name>"
+        #[clap(short, long)]
+        message: Option<String>,
+    },
+    
+    /// Print commit, file and contributor statistics of the locally cloned repositories
+    Stats,
+    
+    /// Print the configured repositories and groups
+    List,
+    
+    /// Add a repository to repos.json
+    AddRepo {
+        /// Repository name, also used as the remote name
+        #[clap(long)]
+        name: String,
+        
+        /// Remote URL
+        #[clap(long)]
+        url: String,
+        
+        /// How to authenticate with the remote
+        #[clap(long, value_enum, default_value = "default")]
+        auth: AuthArg,
+        
+        /// Access token, required with `--auth token`
+        #[clap(long, conflicts_with = "ssh_key")]
+        token: Option<String>,
+        
+        /// Private key file, required with `--auth ssh`
+        #[clap(long)]
+        ssh_key: Option<String>,
+    },
+    
+    /// Remove a repository from repos.json and from its groups
+    RemoveRepo {
+        /// Name of the repository to remove
+        #[clap(long)]
+        name: String,
+    },
+    
+    /// Check repos.json for problems and exit with a non-zero code when any are found
+    Validate {
+        /// Also check that the credentials of each repository are accepted
+        #[clap(long)]
+        check_auth: bool,
+    },
+    
+    /// Print the most recent pushes, pulls, fetches and clones from ~/.dola-maru/history.jsonl
+    Log {
+        /// Only entries of repositories whose name contains this, ignoring case
+        #[clap(long)]
+        repo: Option<String>,
+        
+        /// Only entries of this operation
+        #[clap(long, value_parser = clap::builder::PossibleValuesParser::new(LOGGED_OPERATIONS))]
+        operation: Option<String>,
+        
+        /// Number of entries to print, newest first
+        #[clap(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+}
+
+/// Parse `repo1=main,repo2=develop` into a map from repository name to branch.
+/// Spaces around names and branches are ignored, an empty string gives no overrides.
+pub fn parse_branch_overrides(s: &str) -> Result<HashMap<String, String>> {
+    let mut overrides = HashMap::new();
+    if s.trim().is_empty() {
+        return Ok(overrides);
+    }
+    
+    for entry in s.split(',') {
+        let (name, branch) = entry
+            .split_once('=')
+            .ok_or_else(|| anyhow!("expected REPOSITORY=BRANCH, got '{}'", entry.trim()))?;
+        let (name, branch) = (name.trim(), branch.trim());
+        if name.is_empty() || branch.is_empty() {
+            return Err(anyhow!("expected REPOSITORY=BRANCH, got '{}'", entry.trim()));
+        }
+        if overrides.insert(name.to_string(), branch.to_string()).is_some() {
+            return Err(anyhow!("repository '{}' is listed more than once", name));
+        }
+    }
+    
+    Ok(overrides)
+}
+
+/// Authentication methods accepted by `add-repo --auth`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum AuthArg {
+    Default,
+    Token,
+    Ssh,
+    SshAgent,
+    CredentialHelper,
+}
+
+impl From<AuthArg> for AuthType {
+    fn from(auth: AuthArg) -> Self {
+        match auth {
+            AuthArg::Default => AuthType::Default,
+            AuthArg::Token => AuthType::Token,
+            AuthArg::Ssh => AuthType::SSH,
+            AuthArg::SshAgent => AuthType::SshAgent,
+            AuthArg::CredentialHelper => AuthType::CredentialHelper,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use crate::cli::args::{Command, Verbosity};
+use crate::cli::runner::{print_results, record_history, run_command};
+use crate::core::git_operations::{get_working_changes, FileStatus};
+use crate::core::operation_log::OperationLog;
+use crate::core::repository::RepoConfig;
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// What a daemon cycle does after looking at the working tree
+#[derive(Debug, PartialEq)]
+pub enum CycleAction {
+    /// Nothing to commit, wait for the next cycle
+    Skip,
+    /// Commit and push this many changed files
+    Push(usize),
+}
+
+/// Only commit and push when the working tree has changes, so idle cycles don't create empty commits
+pub fn plan_cycle(changes: &[FileStatus]) -> CycleAction {
+    if changes.is_empty() {
+        CycleAction::Skip
+    } else {
+        CycleAction::Push(changes.len())
+    }
+}
+
+/// The push that daemon and watch mode repeat: the `push` subcommand when one is given, otherwise push with its defaults
+pub fn daemon_command(command: Option<Command>) -> Result<Command> {
+    match command {
+        Some(command @ Command::Push { .. }) => Ok(command),
+        Some(_) => Err(anyhow!("--daemon and --watch can only be combined with the push subcommand")),
+        None => Ok(Command::Push {
+            message: "Auto commit".to_string(),
+            branch: "main".to_string(),
+            group: None,
+            branches: None,
+        }),
+    }
+}
+
+/// Commit and push the local changes every `interval` until Ctrl+C is pressed.
+/// A cycle that is running when Ctrl+C arrives is finished before the daemon stops.
+pub async fn run_daemon(config: Arc<Mutex<RepoConfig>>, command: Command, interval: Duration, verbosity: Verbosity) -> Result<()> {
+    if verbosity >= Verbosity::Normal {
+        println!("Multi-Repo Pusher daemon");
+        println!("========================");
+        println!("Pushing changes every {} seconds, press Ctrl+C to stop", interval.as_secs());
+    }
+    info!("Daemon started with an interval of {} seconds", interval.as_secs());
+    
+    // Listen for Ctrl+C from the start, not only while sleeping
+    let mut stop_receiver = listen_for_ctrl_c();
+    
+    let mut cycle = 1;
+    loop {
+        run_cycle(&config.lock().unwrap(), &command, cycle, verbosity);
+        
+        tokio::select! {
+            _ = &mut stop_receiver => break,
+            _ = tokio::time::sleep(interval) => {},
+        }
+        cycle += 1;
+    }
+    
+    info!("Daemon stopped after {} cycles", cycle);
+    if verbosity >= Verbosity::Normal {
+        println!("\nStopped after {} cycles", cycle);
+    }
+    Ok(())
+}
+
+/// Resolves once Ctrl+C is pressed. The handler is installed right away, so a Ctrl+C
+/// during a push no longer kills the process but is picked up when the push is done.
+pub fn listen_for_ctrl_c() -> tokio::sync::oneshot::Receiver<()> {
+    let (stop_sender, stop_receiver) = tokio::sync::oneshot::channel();
+    tokio::spawn(async move {
+        match tokio::signal::ctrl_c().await {
+            Ok(()) => {
+                let _ = stop_sender.send(());
+            },
+            Err(e) => {
+                // Keep the sender alive so this isn't mistaken for Ctrl+C
+                warn!("Cannot listen for Ctrl+C, stop the process by killing it: {}", e);
+                std::future::pending::<()>().await;
+            },
+        }
+    });
+    stop_receiver
+}
+
+/// Look at the working tree of the local repository and push if anything changed
+pub fn run_cycle(config: &RepoConfig, command: &Command, cycle: u64, verbosity: Verbosity) {
+    let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+    
+    let changes = git2::Repository::open(".")
+        .map_err(anyhow::Error::from)
+        .and_then(|repo| get_working_changes(&repo));
+    let changes = match changes {
+        Ok(changes) => changes,
+        Err(e) => {
+            warn!("Daemon cycle {} could not read the working tree: {}", cycle, e);
+            println!("[{}] Cycle {}: could not read the working tree: {}", timestamp, cycle, e);
+            return;
+        }
+    };
+    
+    match plan_cycle(&changes) {
+        CycleAction::Skip => {
+            info!("Daemon cycle {}: no changes", cycle);
+            if verbosity >= Verbosity::Normal {
+                println!("[{}] Cycle {}: no changes, skipping", timestamp, cycle);
+            }
+        },
+        CycleAction::Push(changed_files) => {
+            info!("Daemon cycle {}: {} changed files", cycle, changed_files);
+            if verbosity >= Verbosity::Normal {
+                println!("[{}] Cycle {}: {} changed files", timestamp, cycle, changed_files);
+            }
+            let results = run_command(config, command, verbosity);
+            record_history(&OperationLog::default_location(), command, &results);
+            print_results(&results, verbosity);
+        },
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use crate::cli::args::{Args, Command};
+    use crate::cli::daemon::{daemon_command, plan_cycle, CycleAction};
+    use crate::core::git_operations::{FileStatus, WorkingChangeStatus};
+    use clap::Parser;
+
+    #[test]
+    fn test_cycle_skips_without_changes() {
+        assert_eq!(plan_cycle(&[]), CycleAction::Skip);
+    }
+
+    #[test]
+    fn test_cycle_pushes_changed_files() {
+        let changes = vec![
+            FileStatus { path: "src/main.rs".to_string(), status: WorkingChangeStatus::Modified },
+            FileStatus { path: "notes.txt".to_string(), status: WorkingChangeStatus::Untracked },
+        ];
+        assert_eq!(plan_cycle(&changes), CycleAction::Push(2));
+    }
+
+    #[test]
+    fn test_daemon_command_defaults_to_push() {
+        assert_eq!(daemon_command(None).unwrap(), Command::Push {
+            message: "Auto commit".to_string(),
+            branch: "main".to_string(),
+            group: None,
+            branches: None,
+        });
+        
+        let args = Args::try_parse_from(["maru", "--daemon", "push", "-b", "dev", "-g", "web"]).unwrap();
+        assert_eq!(daemon_command(args.command).unwrap(), Command::Push {
+            message: "Auto commit".to_string(),
+            branch: "dev".to_string(),
+            group: Some("web".to_string()),
+            branches: None,
+        });
+        
+        assert!(daemon_command(Some(Command::Pull { branch: "main".to_string(), group: None })).is_err());
+    }
+
+    #[test]
+    fn test_parse_daemon_interval() {
+        let args = Args::try_parse_from(["maru", "--daemon", "--interval", "60"]).unwrap();
+        assert!(args.daemon);
+        assert_eq!(args.interval, 60);
+        
+        let args = Args::try_parse_from(["maru", "--daemon"]).unwrap();
+        assert_eq!(args.interval, 300);
+        
+        assert!(Args::try_parse_from(["maru", "--daemon", "--interval", "0"]).is_err());
+        // The interval means nothing outside daemon mode
+        assert!(Args::try_parse_from(["maru", "--interval", "60", "list"]).is_err());
+        assert!(Args::try_parse_from(["maru", "--daemon", "--gui"]).is_err());
+    }
+}
@@ -1,4 +1,11 @@
+pub mod args;
+pub mod daemon;
 pub mod runner;
+pub mod watch;
 
 #[cfg(test)]
-mod runner_tests;
\ No newline at end of file
+mod daemon_tests;
+#[cfg(test)]
+mod runner_tests;
+#[cfg(test)]
+mod watch_tests;
@@ -1,32 +1,509 @@
-use crate::core::repository::RepoConfig;
-use crate::core::git_operations::{add_all_changes, commit_changes, push_to_remote};
-use anyhow::Result;
-use git2::Repository;
+use crate::cli::args::{Command, Verbosity};
+use crate::core::repository::{AuthType, DuplicateRepository, RepoConfig, RepositoryInfo};
+use crate::core::git_operations::{
+    push_to_all_repositories_with_options,
+    pull_from_all_repositories,
+    fetch_from_all_repositories,
+    clone_all_repositories,
+    tag_all_repositories,
+    verify_authentication,
+    CommitOptions
+};
+use crate::core::error_handler::ResultStatus;
+use crate::core::repository_stats::{collect_overall_stats, stats_to_json, format_relative_time, OverallStats};
+use crate::core::notifications::send_push_notifications;
+use crate::core::operation_log::{LogFilter, OperationLog};
+use crate::core::batch_operations::{
+    push_to_group_repositories,
+    pull_from_group_repositories,
+    fetch_from_group_repositories
+};
+use anyhow::{anyhow, Result};
+use log::warn;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
-pub fn run_cli(config: Arc<Mutex<RepoConfig>>, message: &str, branch: &str) -> Result<()> {
-    println!("Multi-Repo Pusher");
-    println!("=================");
+pub fn run_cli(
+    config: Arc<Mutex<RepoConfig>>,
+    command: Command,
+    json: bool,
+    verbosity: Verbosity,
+    save_config: fn(&RepoConfig) -> Result<()>,
+) -> Result<()> {
+    // Config edits are saved right away, nothing is pushed or fetched
+    {
+        let mut config_guard = config.lock().unwrap();
+        if let Some(message) = edit_config(&mut config_guard, &command) {
+            let message = message?;
+            save_config(&config_guard)?;
+            println!("{}", message);
+            return Ok(());
+        }
+    }
+    
+    // Validation only reports problems, the exit code tells scripts whether there were any
+    if let Command::Validate { check_auth } = command {
+        let problems = validate_config(&config.lock().unwrap(), check_auth);
+        if json {
+            println!("{}", serde_json::to_string_pretty(&problems)?);
+        } else if problems.is_empty() {
+            println!("No problems found");
+        } else {
+            for problem in &problems {
+                println!("✗ {}", problem);
+            }
+        }
+        return check_problems(&problems);
+    }
+    
+    // Reports print what is configured, cloned or recorded instead of running an operation
+    if let Some(report) = run_report(&config.lock().unwrap(), &command, json) {
+        println!("{}", report?);
+        return Ok(());
+    }
+    
+    // Keep stdout machine-readable in JSON mode
+    let verbosity = if json { Verbosity::Quiet } else { verbosity };
+    
+    if verbosity >= Verbosity::Normal {
+        println!("Multi-Repo Pusher");
+        println!("=================");
+    }
     
     let config_guard = config.lock().unwrap();
+    let results = run_command(&config_guard, &command, verbosity);
+    record_history(&OperationLog::default_location(), &command, &results);
     
-    // Get the current repository
-    let repo = Repository::open(".")?;
+    if json {
+        println!("{}", results_to_json(&results)?);
+    } else {
+        print_results(&results, verbosity);
+    }
     
-    // Add all changes
-    add_all_changes(&repo)?;
+    // A failed repository makes the process exit with a non-zero code
+    check_results(&results)
+}
+
+/// Turn partial failures into an error so pipelines can detect them
+pub fn check_results(results: &[(String, ResultStatus, String)]) -> Result<()> {
+    let failed_count = results.iter().filter(|(_, status, _)| matches!(status, ResultStatus::Failed | ResultStatus::Conflict)).count();
+    if failed_count > 0 {
+        return Err(anyhow!("{} of {} repositories failed", failed_count, results.len()));
+    }
+    
+    Ok(())
+}
+
+/// Fail when validation found problems, so a CI step or pre-commit hook stops
+pub fn check_problems(problems: &[String]) -> Result<()> {
+    if !problems.is_empty() {
+        return Err(anyhow!("{} problems found in the configuration", problems.len()));
+    }
     
-    // Commit changes
-    commit_changes(&repo, message)?;
+    Ok(())
+}
+
+/// Every problem `RepoConfig::validate` finds, and with `check_auth` the repositories
+/// whose credentials are not accepted
+pub fn validate_config(config: &RepoConfig, check_auth: bool) -> Vec<String> {
+    let mut problems: Vec<String> = config.validate().iter().map(|problem| problem.to_string()).collect();
     
-    // Push to all configured repositories
-    for repo_info in &config_guard.repositories {
-        println!("\nPushing to {} ({})...", repo_info.name, repo_info.url);
-        match push_to_remote(&repo, repo_info, branch) {
-            Ok(_) => println!("✓ Successfully pushed to {}", repo_info.name),
-            Err(e) => println!("✗ Failed to push to {}: {}", repo_info.name, e),
+    if check_auth {
+        for repo_info in &config.repositories {
+            match verify_authentication(repo_info) {
+                Ok(true) => {},
+                Ok(false) => problems.push(format!("{}: authentication failed", repo_info.name)),
+                Err(e) => problems.push(format!("{}: could not check authentication ({})", repo_info.name, e)),
+            }
         }
     }
     
-    Ok(())
-}
\ No newline at end of file
+    problems
+}
+
+/// Run a subcommand against all repositories, or only those of `--group` when it is given
+pub fn run_command(config: &RepoConfig, command: &Command, verbosity: Verbosity) -> Vec<(String, ResultStatus, String)> {
+    let progress = |line: String| if verbosity >= Verbosity::Normal { println!("\n{}", line) };
+    
+    if verbosity == Verbosity::Verbose {
+        print_repository_details(config, command);
+    }
+    
+    let results = match command {
+        Command::Push { message, branch, group: Some(group), .. } => {
+            progress(format!("Pushing to group {} on {}...", group, branch));
+            push_to_group_repositories(config, group, message, branch)
+        },
+        Command::Push { message, branch, group: None, branches } => {
+            progress(format!("Pushing to all repositories on {}...", branch));
+            let mut commit_options = CommitOptions::from_config(config);
+            commit_options.branch_overrides = branches.clone().unwrap_or_default();
+            push_to_all_repositories_with_options(config, message, branch, &commit_options)
+        },
+        Command::Pull { branch, group: Some(group) } => {
+            progress(format!("Pulling {} from group {}...", branch, group));
+            pull_from_group_repositories(config, group, branch)
+        },
+        Command::Pull { branch, group: None } => {
+            progress(format!("Pulling {} from all repositories...", branch));
+            pull_from_all_repositories(config, branch)
+        },
+        Command::Fetch { branch, group: Some(group) } => {
+            progress(format!("Fetching {} from group {}...", branch, group));
+            fetch_from_group_repositories(config, group, branch)
+        },
+        Command::Fetch { branch, group: None } => {
+            progress(format!("Fetching {} from all repositories...", branch));
+            fetch_from_all_repositories(config, branch)
+        },
+        Command::Clone { path, include_existing } => {
+            progress(format!("Cloning all repositories into {}...", path));
+            clone_all_repositories(config, path, !include_existing)
+        },
+        Command::Tag { name, message } => {
+            progress(format!("Creating tag {} in all repositories...", name));
+            let message = message.clone().unwrap_or_else(|| format!("Release {}", name));
+            tag_all_repositories(config, name, &message)
+        },
+        // Reports are printed by run_cli and have no per-repository results
+        Command::Stats | Command::List | Command::AddRepo { .. } | Command::RemoveRepo { .. } | Command::Validate { .. } | Command::Log { .. } => Vec::new(),
+    };
+    
+    // Tell the team about finished pushes
+    if matches!(command, Command::Push { .. }) {
+        send_push_notifications(config, &results);
+    }
+    
+    results
+}
+
+// Print where each targeted repository will be pushed to or fetched from
+fn print_repository_details(config: &RepoConfig, command: &Command) {
+    let (repositories, refspec): (Vec<&RepositoryInfo>, Option<String>) = match command {
+        Command::Push { branch, group, .. } | Command::Pull { branch, group } | Command::Fetch { branch, group } => {
+            let repositories = match group {
+                Some(group) => config.get_repositories_in_group(group),
+                None => config.repositories.iter().collect(),
+            };
+            (repositories, Some(format!("refs/heads/{}:refs/heads/{}", branch, branch)))
+        },
+        Command::Tag { name, .. } => {
+            (config.repositories.iter().collect(), Some(format!("refs/tags/{}:refs/tags/{}", name, name)))
+        },
+        Command::Clone { .. } => (config.repositories.iter().collect(), None),
+        Command::Stats | Command::List | Command::AddRepo { .. } | Command::RemoveRepo { .. } | Command::Validate { .. } | Command::Log { .. } => {
+            (Vec::new(), None)
+        },
+    };
+    
+    let branch_overrides = match command {
+        Command::Push { branches: Some(branches), .. } => branches.clone(),
+        _ => HashMap::new(),
+    };
+    
+    // Remotes already configured in the local repository win over the configured URL
+    let local_repo = git2::Repository::open(".").ok();
+    
+    for repo_info in repositories {
+        let remote_url = local_repo
+            .as_ref()
+            .and_then(|repo| repo.find_remote(&repo_info.name).ok())
+            .and_then(|remote| remote.url().map(|url| url.to_string()))
+            .unwrap_or_else(|| repo_info.url.clone());
+        
+        println!("  {}:", repo_info.name);
+        println!("    remote:  {}", remote_url);
+        println!("    auth:    {:?}", repo_info.auth_type);
+        match (&refspec, branch_overrides.get(&repo_info.name)) {
+            (Some(_), Some(branch)) => println!("    refspec: refs/heads/{}:refs/heads/{}", branch, branch),
+            (Some(refspec), None) => println!("    refspec: {}", refspec),
+            (None, _) => {},
+        }
+    }
+}
+
+/// Name under which the results of a command are kept in the operation history, `None` if they aren't
+pub fn history_operation(command: &Command) -> Option<&'static str> {
+    match command {
+        Command::Push { .. } => Some("push"),
+        Command::Pull { .. } => Some("pull"),
+        Command::Fetch { .. } => Some("fetch"),
+        Command::Clone { .. } => Some("clone"),
+        _ => None,
+    }
+}
+
+/// Add the results of a push, pull, fetch or clone to the history.
+/// Failing to write it only logs a warning, the operation itself has already run.
+pub fn record_history(log: &OperationLog, command: &Command, results: &[(String, ResultStatus, String)]) {
+    let Some(operation) = history_operation(command) else {
+        return;
+    };
+    if let Err(e) = log.record(operation, results) {
+        warn!("Failed to write the operation history to {}: {}", log.path().display(), e);
+    }
+}
+
+/// Print the repository results. Quiet mode only prints failures and a summary line.
+pub fn print_results(results: &[(String, ResultStatus, String)], verbosity: Verbosity) {
+    for (repo_name, status, message) in results {
+        if *status == ResultStatus::Success {
+            if verbosity >= Verbosity::Normal {
+                // Keep notes like "(after 2 attempts)"
+                match message.strip_prefix("Success").map(str::trim) {
+                    Some(note) if !note.is_empty() => println!("✓ {} {}", repo_name, note),
+                    _ => println!("✓ {}", repo_name),
+                }
+            }
+        } else {
+            println!("✗ {}: {}", repo_name, message);
+        }
+    }
+    
+    if verbosity == Verbosity::Quiet {
+        let succeeded = results.iter().filter(|(_, status, _)| *status == ResultStatus::Success).count();
+        println!("{} of {} repositories succeeded", succeeded, results.len());
+    }
+}
+
+#[derive(Serialize)]
+struct RepositoryResult<'a> {
+    repository: &'a str,
+    status: ResultStatus,
+    message: &'a str,
+}
+
+/// Serialize results as a JSON array of `{ "repository": ..., "status": ..., "message": ... }` objects
+pub fn results_to_json(results: &[(String, ResultStatus, String)]) -> Result<String> {
+    let entries: Vec<RepositoryResult> = results
+        .iter()
+        .map(|(repository, status, message)| RepositoryResult { repository, status: *status, message })
+        .collect();
+    Ok(serde_json::to_string_pretty(&entries)?)
+}
+
+/// Apply the subcommands that change the configuration, `None` for every other subcommand.
+/// The configuration is left untouched when the edit is rejected.
+pub fn edit_config(config: &mut RepoConfig, command: &Command) -> Option<Result<String>> {
+    match command {
+        Command::AddRepo { name, url, auth, token, ssh_key } => {
+            Some(add_repository(config, name, url, (*auth).into(), token.as_deref(), ssh_key.as_deref()))
+        },
+        Command::RemoveRepo { name } => Some(remove_repository(config, name)),
+        _ => None,
+    }
+}
+
+// Check and add a repository the way the Add form of the GUI does, without the duplicate name override
+fn add_repository(
+    config: &mut RepoConfig,
+    name: &str,
+    url: &str,
+    auth_type: AuthType,
+    token: Option<&str>,
+    ssh_key: Option<&str>,
+) -> Result<String> {
+    if name.trim().is_empty() {
+        return Err(anyhow!("Repository name must not be empty"));
+    }
+    if !RepoConfig::validate_repository_url(url) {
+        return Err(anyhow!("Invalid repository URL: {}", url));
+    }
+    
+    let mut repo_info = RepositoryInfo::with_auth(name.to_string(), url.to_string(), auth_type.clone());
+    match auth_type {
+        AuthType::Token => {
+            repo_info.auth_token = token.filter(|token| !token.is_empty())
+                .ok_or_else(|| anyhow!("--auth token needs --token"))?
+                .to_string();
+        },
+        AuthType::SSH => {
+            repo_info.ssh_key_path = ssh_key.filter(|path| !path.is_empty())
+                .ok_or_else(|| anyhow!("--auth ssh needs --ssh-key"))?
+                .to_string();
+        },
+        _ => {}
+    }
+    
+    match config.find_duplicate(&repo_info) {
+        Some(DuplicateRepository::Url(existing)) => {
+            return Err(anyhow!("This repository is already configured as '{}'", existing));
+        },
+        Some(DuplicateRepository::Name) => {
+            return Err(anyhow!("A repository named '{}' already exists", name));
+        },
+        None => {}
+    }
+    
+    config.add_repository(repo_info);
+    Ok(format!("Added repository {}", name))
+}
+
+// Remove a repository by name, groups drop it as a member too
+fn remove_repository(config: &mut RepoConfig, name: &str) -> Result<String> {
+    let index = config.repositories
+        .iter()
+        .position(|repo| repo.name == name)
+        .ok_or_else(|| anyhow!("No repository named '{}' is configured", name))?;
+    config.remove_repository(index);
+    Ok(format!("Removed repository {}", name))
+}
+
+/// Output of the subcommands that only read the configuration or the history, `None` for repository operations
+pub fn run_report(config: &RepoConfig, command: &Command, json: bool) -> Option<Result<String>> {
+    match command {
+        Command::Stats => Some(stats_report(config, json)),
+        Command::List => Some(list_report(config, json)),
+        Command::Log { repo, operation, limit } => {
+            let filter = LogFilter {
+                repo: repo.clone().unwrap_or_default(),
+                operation: operation.clone().unwrap_or_default(),
+            };
+            Some(log_report(&OperationLog::default_location(), &filter, *limit, json))
+        },
+        _ => None,
+    }
+}
+
+/// Collect statistics over each repository's `local_path` and format them as a table, or as JSON
+pub fn stats_report(config: &RepoConfig, json: bool) -> Result<String> {
+    let stats = collect_overall_stats(config)?;
+    if json {
+        stats_to_json(&stats)
+    } else {
+        Ok(format_stats_table(&stats))
+    }
+}
+
+/// Totals followed by one row per repository
+pub fn format_stats_table(stats: &OverallStats) -> String {
+    let mut table = format!(
+        "Repositories: {}\nGroups:       {}\nCommits:      {}\nContributors: {}\n",
+        stats.total_repositories, stats.total_groups, stats.total_commits, stats.total_contributors
+    );
+    if stats.repository_stats.is_empty() {
+        return table;
+    }
+    
+    let name_width = stats
+        .repository_stats
+        .iter()
+        .map(|repo_stats| repo_stats.name.len())
+        .chain(std::iter::once("Repository".len()))
+        .max()
+        .unwrap_or_default();
+    
+    table.push_str(&format!(
+        "\n{:<name_width$}  {:>7}  {:>7}  {:>12}  Last commit\n",
+        "Repository", "Commits", "Files", "Contributors"
+    ));
+    for repo_stats in &stats.repository_stats {
+        if !repo_stats.available {
+            table.push_str(&format!("{:<name_width$}  not cloned locally\n", repo_stats.name));
+            continue;
+        }
+        let last_commit = repo_stats.last_commit_date.map(format_relative_time).unwrap_or_else(|| "-".to_string());
+        table.push_str(&format!(
+            "{:<name_width$}  {:>7}  {:>7}  {:>12}  {}\n",
+            repo_stats.name,
+            repo_stats.total_commits,
+            repo_stats.total_files,
+            repo_stats.contributors.len(),
+            last_commit
+        ));
+    }
+    table
+}
+
+// Credentials are left out so the listing can be pasted into issues
+#[derive(Serialize)]
+struct ListedRepository<'a> {
+    name: &'a str,
+    url: &'a str,
+    auth_type: &'a AuthType,
+    groups: Vec<&'a str>,
+}
+
+#[derive(Serialize)]
+struct ListedGroup<'a> {
+    name: &'a str,
+    description: &'a str,
+    members: &'a [String],
+}
+
+#[derive(Serialize)]
+struct ConfigListing<'a> {
+    repositories: Vec<ListedRepository<'a>>,
+    groups: Vec<ListedGroup<'a>>,
+}
+
+/// List each repository (name, url, auth type, groups) and each group with its members
+pub fn list_report(config: &RepoConfig, json: bool) -> Result<String> {
+    let repositories: Vec<ListedRepository> = config
+        .repositories
+        .iter()
+        .map(|repo| {
+            // Membership comes from the groups, plus the group set on the repository itself
+            let mut groups: Vec<&str> = config
+                .groups
+                .iter()
+                .filter(|group| group.repository_names.contains(&repo.name))
+                .map(|group| group.name.as_str())
+                .collect();
+            if !repo.group.is_empty() && !groups.contains(&repo.group.as_str()) {
+                groups.push(&repo.group);
+            }
+            ListedRepository { name: &repo.name, url: &repo.url, auth_type: &repo.auth_type, groups }
+        })
+        .collect();
+    let groups: Vec<ListedGroup> = config
+        .groups
+        .iter()
+        .map(|group| ListedGroup { name: &group.name, description: &group.description, members: &group.repository_names })
+        .collect();
+    
+    if json {
+        return Ok(serde_json::to_string_pretty(&ConfigListing { repositories, groups })?);
+    }
+    
+    let mut listing = format!("Repositories ({}):\n", repositories.len());
+    for repo in &repositories {
+        listing.push_str(&format!("  {}\n", repo.name));
+        listing.push_str(&format!("    url:    {}\n", repo.url));
+        listing.push_str(&format!("    auth:   {:?}\n", repo.auth_type));
+        if !repo.groups.is_empty() {
+            listing.push_str(&format!("    groups: {}\n", repo.groups.join(", ")));
+        }
+    }
+    
+    listing.push_str(&format!("\nGroups ({}):\n", groups.len()));
+    for group in &groups {
+        if group.members.is_empty() {
+            listing.push_str(&format!("  {}: (no repositories)\n", group.name));
+        } else {
+            listing.push_str(&format!("  {}: {}\n", group.name, group.members.join(", ")));
+        }
+    }
+    Ok(listing)
+}
+
+/// The newest `limit` history entries matching `filter`, newest first, one per line or as a JSON array
+pub fn log_report(log: &OperationLog, filter: &LogFilter, limit: usize, json: bool) -> Result<String> {
+    let entries = log.recent(filter, limit)?;
+    if json {
+        return Ok(serde_json::to_string_pretty(&entries)?);
+    }
+    if entries.is_empty() {
+        return Ok("No operations recorded".to_string());
+    }
+    
+    let repo_width = entries.iter().map(|entry| entry.repo.len()).max().unwrap_or(0);
+    let lines: Vec<String> = entries
+        .iter()
+        .map(|entry| {
+            format!("{}  {:<5}  {:<width$}  {}", entry.formatted_time(), entry.operation, entry.repo, entry.message, width = repo_width)
+        })
+        .collect();
+    Ok(lines.join("\n"))
+}
@@ -1,7 +1,13 @@
 #[cfg(test)]
 mod tests {
-    use crate::core::repository::{RepoConfig, RepositoryInfo};
-    use crate::cli::runner::run_cli;
+    use crate::core::repository::{AuthType, RepoConfig, RepositoryGroup, RepositoryInfo};
+    use crate::cli::args::{Args, AuthArg, Command, Verbosity, parse_branch_overrides};
+    use crate::cli::runner::{run_cli, run_command, results_to_json, check_results, stats_report, list_report, edit_config,
+        validate_config, check_problems, record_history, log_report};
+    use crate::core::error_handler::ResultStatus;
+    use crate::core::operation_log::{LogFilter, OperationLog};
+    use crate::core::test_support::{init_temp_repo, commit_file};
+    use clap::Parser;
     use std::sync::{Arc, Mutex};
 
     #[test]
@@ -10,11 +16,41 @@ mod tests {
         // and that the run_cli function exists with the correct signature
         let _run_cli_fn = run_cli as fn(
             Arc<Mutex<RepoConfig>>, 
-            &str, 
-            &str
+            Command,
+            bool,
+            Verbosity,
+            fn(&RepoConfig) -> anyhow::Result<()>
         ) -> anyhow::Result<()>;
     }
 
+    #[test]
+    fn test_parse_pull_subcommand() {
+        let args = Args::try_parse_from(["maru", "pull", "--branch", "dev"]).unwrap();
+        assert!(!args.gui);
+        assert_eq!(args.command, Some(Command::Pull { branch: "dev".to_string(), group: None }));
+    }
+
+    #[test]
+    fn test_parse_push_subcommand_defaults() {
+        let args = Args::try_parse_from(["maru", "push"]).unwrap();
+        assert_eq!(args.command, Some(Command::Push {
+            message: "Auto commit".to_string(),
+            branch: "main".to_string(),
+            group: None,
+            branches: None,
+        }));
+        
+        let args = Args::try_parse_from(["maru", "tag", "v1.0.0"]).unwrap();
+        assert_eq!(args.command, Some(Command::Tag { name: "v1.0.0".to_string(), message: None }));
+    }
+
+    #[test]
+    fn test_parse_gui_without_subcommand() {
+        let args = Args::try_parse_from(["maru", "--gui"]).unwrap();
+        assert!(args.gui);
+        assert_eq!(args.command, None);
+    }
+
     #[test]
     fn test_repo_config_structure() {
         let config = RepoConfig::new();
@@ -32,4 +68,347 @@ mod tests {
         assert_eq!(repo.name, "test-repo");
         assert_eq!(repo.url, "https://github.com/user/repo.git");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_group_option() {
+        let args = Args::try_parse_from(["maru", "push", "--group", "frontend", "--message", "x"]).unwrap();
+        assert_eq!(args.command, Some(Command::Push {
+            message: "x".to_string(),
+            branch: "main".to_string(),
+            group: Some("frontend".to_string()),
+            branches: None,
+        }));
+    }
+
+    #[test]
+    fn test_group_option_selects_group_operations() {
+        let mut config = RepoConfig::new();
+        config.add_repository(RepositoryInfo::new(
+            "backend".to_string(),
+            "https://github.com/user/backend.git".to_string()
+        ));
+        
+        // Only the group functions report an empty group
+        let command = Command::Fetch { branch: "main".to_string(), group: Some("frontend".to_string()) };
+        let results = run_command(&config, &command, Verbosity::Quiet);
+        assert_eq!(results, vec![("frontend".to_string(), ResultStatus::Failed, "No repositories found in group".to_string())]);
+        
+        let command = Command::Pull { branch: "main".to_string(), group: Some("frontend".to_string()) };
+        let results = run_command(&config, &command, Verbosity::Quiet);
+        assert_eq!(results, vec![("frontend".to_string(), ResultStatus::Failed, "No repositories found in group".to_string())]);
+    }
+
+    #[test]
+    fn test_parse_json_flag_after_subcommand() {
+        let args = Args::try_parse_from(["maru", "fetch", "--json"]).unwrap();
+        assert!(args.json);
+    }
+
+    #[test]
+    fn test_results_to_json_is_parseable() {
+        let results = vec![
+            ("origin".to_string(), ResultStatus::Success, "Success".to_string()),
+            ("mirror".to_string(), ResultStatus::Failed, "Failed to push: \"auth\" error".to_string()),
+        ];
+        
+        let json = results_to_json(&results).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        
+        let entries = parsed.as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["repository"], "origin");
+        assert_eq!(entries[0]["status"], "success");
+        assert_eq!(entries[0]["message"], "Success");
+        assert_eq!(entries[1]["repository"], "mirror");
+        assert_eq!(entries[1]["status"], "failed");
+        assert_eq!(entries[1]["message"], "Failed to push: \"auth\" error");
+    }
+
+    #[test]
+    fn test_check_results_fails_on_any_failure() {
+        let all_ok = vec![
+            ("origin".to_string(), ResultStatus::Success, "Success".to_string()),
+            ("mirror".to_string(), ResultStatus::Skipped, "Skipped (disabled)".to_string()),
+        ];
+        assert!(check_results(&all_ok).is_ok());
+        assert!(check_results(&[]).is_ok());
+        
+        let mixed = vec![
+            ("origin".to_string(), ResultStatus::Success, "Success".to_string()),
+            ("mirror".to_string(), ResultStatus::Failed, "Network error: connection refused".to_string()),
+            ("backup".to_string(), ResultStatus::Conflict, "Merge conflicts detected in: a.txt".to_string()),
+        ];
+        let err = check_results(&mixed).unwrap_err();
+        assert_eq!(err.to_string(), "2 of 3 repositories failed");
+    }
+
+    #[test]
+    fn test_parse_verbosity_flags() {
+        let args = Args::try_parse_from(["maru", "push"]).unwrap();
+        assert_eq!(args.verbosity(), Verbosity::Normal);
+        
+        let args = Args::try_parse_from(["maru", "push", "--verbose"]).unwrap();
+        assert_eq!(args.verbosity(), Verbosity::Verbose);
+        
+        let args = Args::try_parse_from(["maru", "-q", "pull"]).unwrap();
+        assert_eq!(args.verbosity(), Verbosity::Quiet);
+        
+        // The two flags are mutually exclusive
+        assert!(Args::try_parse_from(["maru", "push", "--verbose", "--quiet"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_stats_subcommand() {
+        let args = Args::try_parse_from(["maru", "stats"]).unwrap();
+        assert_eq!(args.command, Some(Command::Stats));
+        assert!(!args.json);
+        
+        let args = Args::try_parse_from(["maru", "stats", "--json"]).unwrap();
+        assert_eq!(args.command, Some(Command::Stats));
+        assert!(args.json);
+    }
+
+    #[test]
+    fn test_stats_report_collects_local_repositories() {
+        let (dir, repo) = init_temp_repo();
+        commit_file(&repo, "README.md", "hello\n", "Initial commit");
+        commit_file(&repo, "src/main.rs", "fn main() {}\n", "Add main");
+        
+        let mut config = RepoConfig::new();
+        let mut cloned = RepositoryInfo::new("cloned".to_string(), "https://github.com/user/cloned.git".to_string());
+        cloned.local_path = dir.path().to_str().unwrap().to_string();
+        config.add_repository(cloned);
+        config.add_repository(RepositoryInfo::new("remote-only".to_string(), "https://github.com/user/remote.git".to_string()));
+        
+        let table = stats_report(&config, false).unwrap();
+        assert!(table.starts_with("Repositories: 2\nGroups:       0\nCommits:      2\nContributors: 1\n"));
+        assert!(table.lines().any(|line| line.starts_with("cloned ") && line.contains("  2  ")));
+        assert!(table.contains("remote-only  not cloned locally"));
+        
+        let json: serde_json::Value = serde_json::from_str(&stats_report(&config, true).unwrap()).unwrap();
+        assert_eq!(json["total_repositories"], 2);
+        assert_eq!(json["total_commits"], 2);
+        assert_eq!(json["repository_stats"][0]["name"], "cloned");
+        assert_eq!(json["repository_stats"][1]["available"], false);
+    }
+
+    #[test]
+    fn test_list_report_shows_repositories_and_groups() {
+        let mut config = RepoConfig::new();
+        let mut repo = RepositoryInfo::with_auth(
+            "web".to_string(),
+            "https://github.com/user/web.git".to_string(),
+            AuthType::Token,
+        );
+        repo.auth_token = "secret-token".to_string();
+        config.add_repository(repo);
+        let mut group = RepositoryGroup::new("frontend".to_string(), "UI code".to_string());
+        group.add_repository("web".to_string());
+        config.add_group(group);
+        
+        let listing = list_report(&config, false).unwrap();
+        assert_eq!(listing, concat!(
+            "Repositories (1):\n",
+            "  web\n",
+            "    url:    https://github.com/user/web.git\n",
+            "    auth:   Token\n",
+            "    groups: frontend\n",
+            "\n",
+            "Groups (1):\n",
+            "  frontend: web\n",
+        ));
+        
+        let json: serde_json::Value = serde_json::from_str(&list_report(&config, true).unwrap()).unwrap();
+        assert_eq!(json["repositories"][0]["name"], "web");
+        assert_eq!(json["repositories"][0]["auth_type"], "token");
+        assert_eq!(json["repositories"][0]["groups"][0], "frontend");
+        assert_eq!(json["groups"][0]["members"][0], "web");
+        // Credentials never end up in the listing
+        assert!(!list_report(&config, true).unwrap().contains("secret-token"));
+        
+        let args = Args::try_parse_from(["maru", "list", "--json"]).unwrap();
+        assert_eq!(args.command, Some(Command::List));
+        assert!(args.json);
+    }
+
+    #[test]
+    fn test_add_repo_then_list() {
+        let args = Args::try_parse_from([
+            "maru", "add-repo", "--name", "web", "--url", "https://github.com/user/web.git",
+            "--auth", "token", "--token", "abc123",
+        ]).unwrap();
+        let command = args.command.unwrap();
+        assert_eq!(command, Command::AddRepo {
+            name: "web".to_string(),
+            url: "https://github.com/user/web.git".to_string(),
+            auth: AuthArg::Token,
+            token: Some("abc123".to_string()),
+            ssh_key: None,
+        });
+        
+        let mut config = RepoConfig::new();
+        assert_eq!(edit_config(&mut config, &command).unwrap().unwrap(), "Added repository web");
+        assert_eq!(config.repositories[0].auth_type, AuthType::Token);
+        assert_eq!(config.repositories[0].auth_token, "abc123");
+        assert!(list_report(&config, false).unwrap().contains("  web\n    url:    https://github.com/user/web.git\n    auth:   Token\n"));
+        
+        // The same URL or name again is rejected and leaves the config alone
+        assert!(edit_config(&mut config, &command).unwrap().is_err());
+        let same_url = Command::AddRepo {
+            name: "web-copy".to_string(),
+            url: "git@github.com:user/web.git".to_string(),
+            auth: AuthArg::Default,
+            token: None,
+            ssh_key: None,
+        };
+        let err = edit_config(&mut config, &same_url).unwrap().unwrap_err();
+        assert_eq!(err.to_string(), "This repository is already configured as 'web'");
+        assert_eq!(config.repositories.len(), 1);
+    }
+
+    #[test]
+    fn test_add_repo_rejects_invalid_input() {
+        let mut config = RepoConfig::new();
+        let add = |url: &str, auth: AuthArg| Command::AddRepo {
+            name: "web".to_string(),
+            url: url.to_string(),
+            auth,
+            token: None,
+            ssh_key: None,
+        };
+        
+        assert!(edit_config(&mut config, &add("not a url", AuthArg::Default)).unwrap().is_err());
+        assert!(edit_config(&mut config, &add("https://github.com/user/web.git", AuthArg::Token)).unwrap().is_err());
+        assert!(edit_config(&mut config, &add("https://github.com/user/web.git", AuthArg::Ssh)).unwrap().is_err());
+        assert!(config.repositories.is_empty());
+        
+        // Operations and reports are not config edits
+        assert!(edit_config(&mut config, &Command::List).is_none());
+    }
+
+    #[test]
+    fn test_remove_repo_then_list() {
+        let mut config = RepoConfig::new();
+        config.add_repository(RepositoryInfo::new("web".to_string(), "https://github.com/user/web.git".to_string()));
+        config.add_repository(RepositoryInfo::new("api".to_string(), "https://github.com/user/api.git".to_string()));
+        let mut group = RepositoryGroup::new("all".to_string(), String::new());
+        group.add_repository("web".to_string());
+        group.add_repository("api".to_string());
+        config.add_group(group);
+        
+        let args = Args::try_parse_from(["maru", "remove-repo", "--name", "web"]).unwrap();
+        let command = args.command.unwrap();
+        assert_eq!(edit_config(&mut config, &command).unwrap().unwrap(), "Removed repository web");
+        
+        let listing = list_report(&config, false).unwrap();
+        assert!(!listing.contains("web"));
+        assert!(listing.contains("Repositories (1):\n  api\n"));
+        assert!(listing.contains("  all: api\n"));
+        
+        // Removing it again reports that it is gone
+        let err = edit_config(&mut config, &command).unwrap().unwrap_err();
+        assert_eq!(err.to_string(), "No repository named 'web' is configured");
+    }
+
+    #[test]
+    fn test_validate_reports_invalid_url_and_fails() {
+        let args = Args::try_parse_from(["maru", "validate"]).unwrap();
+        assert_eq!(args.command, Some(Command::Validate { check_auth: false }));
+        let args = Args::try_parse_from(["maru", "validate", "--check-auth"]).unwrap();
+        assert_eq!(args.command, Some(Command::Validate { check_auth: true }));
+        
+        let mut config = RepoConfig::new();
+        config.add_repository(RepositoryInfo::new("good".to_string(), "https://github.com/user/good.git".to_string()));
+        assert!(validate_config(&config, false).is_empty());
+        assert!(check_problems(&[]).is_ok());
+        
+        config.add_repository(RepositoryInfo::new("broken".to_string(), "not a url".to_string()));
+        let problems = validate_config(&config, false);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("broken: invalid URL"));
+        
+        // The whole command exits with an error so CI fails
+        let no_save = |_: &RepoConfig| -> anyhow::Result<()> { panic!("validate must not save") };
+        let result = run_cli(
+            Arc::new(Mutex::new(config)),
+            Command::Validate { check_auth: false },
+            false,
+            Verbosity::Quiet,
+            no_save,
+        );
+        assert_eq!(result.unwrap_err().to_string(), "1 problems found in the configuration");
+    }
+
+    #[test]
+    fn test_parse_branch_overrides() {
+        let overrides = parse_branch_overrides("web=main, api = develop").unwrap();
+        assert_eq!(overrides.len(), 2);
+        assert_eq!(overrides["web"], "main");
+        assert_eq!(overrides["api"], "develop");
+        assert!(parse_branch_overrides("").unwrap().is_empty());
+        // Branch names may contain slashes
+        assert_eq!(parse_branch_overrides("web=feature/login").unwrap()["web"], "feature/login");
+        
+        assert!(parse_branch_overrides("web").is_err());
+        assert!(parse_branch_overrides("web=").is_err());
+        assert!(parse_branch_overrides("=main").is_err());
+        assert!(parse_branch_overrides("web=main,,api=develop").is_err());
+        let err = parse_branch_overrides("web=main,web=develop").unwrap_err();
+        assert_eq!(err.to_string(), "repository 'web' is listed more than once");
+    }
+
+    #[test]
+    fn test_parse_push_branches_option() {
+        let args = Args::try_parse_from(["maru", "push", "--branches", "web=main,api=develop"]).unwrap();
+        let Some(Command::Push { branch, branches: Some(branches), .. }) = args.command else {
+            panic!("expected a push with branch overrides");
+        };
+        assert_eq!(branch, "main");
+        assert_eq!(branches["api"], "develop");
+        
+        assert!(Args::try_parse_from(["maru", "push", "--branches", "web"]).is_err());
+        // Groups push one branch to all of their repositories
+        assert!(Args::try_parse_from(["maru", "push", "--branches", "web=main", "--group", "frontend"]).is_err());
+    }
+
+    #[test]
+    fn test_record_history_and_log_report() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let log = OperationLog::new(dir.path().join("history.jsonl"));
+        let results = vec![
+            ("web".to_string(), ResultStatus::Success, "Success".to_string()),
+            ("api".to_string(), ResultStatus::Failed, "Authentication failed for repository 'api'".to_string()),
+        ];
+        
+        let fetch = Command::Fetch { branch: "main".to_string(), group: None };
+        record_history(&log, &fetch, &results);
+        // Reports and config edits are not operations on the repositories
+        record_history(&log, &Command::List, &results);
+        
+        let report = log_report(&log, &LogFilter::default(), 20, false).unwrap();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("fetch  api  Authentication failed for repository 'api'"));
+        assert!(lines[1].contains("fetch  web  Success"));
+        
+        let filter = LogFilter { repo: "web".to_string(), ..Default::default() };
+        let json: serde_json::Value = serde_json::from_str(&log_report(&log, &filter, 20, true).unwrap()).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 1);
+        assert_eq!(json[0]["repo"], "web");
+        assert_eq!(json[0]["operation"], "fetch");
+        
+        let empty = OperationLog::new(dir.path().join("missing.jsonl"));
+        assert_eq!(log_report(&empty, &LogFilter::default(), 20, false).unwrap(), "No operations recorded");
+    }
+
+    #[test]
+    fn test_parse_log_command() {
+        let args = Args::try_parse_from(["maru", "log", "--repo", "web", "--operation", "push", "-n", "5"]).unwrap();
+        let Some(Command::Log { repo, operation, limit }) = args.command else {
+            panic!("expected a log command");
+        };
+        assert_eq!((repo.as_deref(), operation.as_deref(), limit), (Some("web"), Some("push"), 5));
+        
+        assert!(Args::try_parse_from(["maru", "log", "--operation", "tag"]).is_err());
+    }
+}
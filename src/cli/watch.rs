@@ -0,0 +1,124 @@
+use crate::cli::args::{Command, Verbosity};
+use crate::cli::daemon::{listen_for_ctrl_c, run_cycle};
+use crate::core::repository::RepoConfig;
+use anyhow::{anyhow, Result};
+use git2::Repository;
+use log::{info, warn};
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::ffi::OsStr;
+use std::path::{Component, Path};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Batches bursts of file changes: a push is due once no change has come in for the quiet period
+#[derive(Debug)]
+pub struct Debouncer {
+    quiet_period: Duration,
+    last_change: Option<Instant>,
+}
+
+impl Debouncer {
+    pub fn new(quiet_period: Duration) -> Self {
+        Self {
+            quiet_period,
+            last_change: None,
+        }
+    }
+    
+    /// Note a change, which restarts the quiet period
+    pub fn record_change(&mut self, now: Instant) {
+        self.last_change = Some(now);
+    }
+    
+    /// How long until the pending changes are due, None when nothing is pending
+    pub fn time_until_due(&self, now: Instant) -> Option<Duration> {
+        self.last_change
+            .map(|last_change| (last_change + self.quiet_period).saturating_duration_since(now))
+    }
+    
+    /// True once the quiet period has passed, which also clears the pending changes
+    pub fn take_due(&mut self, now: Instant) -> bool {
+        if self.time_until_due(now) == Some(Duration::ZERO) {
+            self.last_change = None;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Whether a path relative to the working directory is inside a `.git` directory
+pub fn is_git_internal(relative: &Path) -> bool {
+    relative.components().any(|component| component == Component::Normal(OsStr::new(".git")))
+}
+
+/// Whether a change to `path` should not trigger a push: git's own files, gitignored paths
+/// and anything outside the working directory
+pub fn is_ignored_change(repo: &Repository, workdir: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(workdir) else {
+        return true;
+    };
+    is_git_internal(relative) || repo.is_path_ignored(relative).unwrap_or(false)
+}
+
+/// Whether an event changed files, reads and other access don't
+pub fn is_change_event(kind: &EventKind) -> bool {
+    matches!(kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_))
+}
+
+/// Watch the working directory of the local repository and commit and push once
+/// changes have settled for `quiet_period`, until Ctrl+C is pressed
+pub async fn run_watch(config: Arc<Mutex<RepoConfig>>, command: Command, quiet_period: Duration, verbosity: Verbosity) -> Result<()> {
+    let repo = Repository::open(".")?;
+    // notify reports canonical paths, so compare against the canonical working directory
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| anyhow!("Cannot watch a bare repository"))?
+        .canonicalize()?;
+    
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let _ = event_sender.send(event);
+    })?;
+    watcher.watch(&workdir, RecursiveMode::Recursive)?;
+    
+    if verbosity >= Verbosity::Normal {
+        println!("Multi-Repo Pusher watch");
+        println!("=======================");
+        println!("Watching {}, pushing {} seconds after the last change, press Ctrl+C to stop", workdir.display(), quiet_period.as_secs());
+    }
+    info!("Watching {} with a quiet period of {} seconds", workdir.display(), quiet_period.as_secs());
+    
+    let mut stop_receiver = listen_for_ctrl_c();
+    let mut debouncer = Debouncer::new(quiet_period);
+    let mut cycle = 1;
+    
+    loop {
+        let until_due = debouncer.time_until_due(Instant::now());
+        
+        tokio::select! {
+            _ = &mut stop_receiver => break,
+            event = event_receiver.recv() => match event {
+                Some(Ok(event)) => {
+                    if is_change_event(&event.kind) && event.paths.iter().any(|path| !is_ignored_change(&repo, &workdir, path)) {
+                        debouncer.record_change(Instant::now());
+                    }
+                },
+                Some(Err(e)) => warn!("File watch error: {}", e),
+                None => break,
+            },
+            _ = tokio::time::sleep(until_due.unwrap_or_default()), if until_due.is_some() => {
+                if debouncer.take_due(Instant::now()) {
+                    run_cycle(&config.lock().unwrap(), &command, cycle, verbosity);
+                    cycle += 1;
+                }
+            },
+        }
+    }
+    
+    info!("Stopped watching {}", workdir.display());
+    if verbosity >= Verbosity::Normal {
+        println!("\nStopped watching after {} cycles", cycle - 1);
+    }
+    Ok(())
+}
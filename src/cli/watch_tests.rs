@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use crate::cli::args::Args;
+    use crate::cli::watch::{is_change_event, is_git_internal, is_ignored_change, Debouncer};
+    use notify::event::{AccessKind, CreateKind, DataChange, ModifyKind, RemoveKind};
+    use notify::EventKind;
+    use crate::core::test_support::{init_temp_repo, write_file};
+    use clap::Parser;
+    use std::path::Path;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_debouncer_waits_for_the_quiet_period() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(Duration::from_secs(5));
+        assert_eq!(debouncer.time_until_due(start), None);
+        assert!(!debouncer.take_due(start));
+        
+        debouncer.record_change(start);
+        assert_eq!(debouncer.time_until_due(start + Duration::from_secs(2)), Some(Duration::from_secs(3)));
+        assert!(!debouncer.take_due(start + Duration::from_secs(4)));
+        
+        assert!(debouncer.take_due(start + Duration::from_secs(5)));
+        // The batch has been handed out
+        assert_eq!(debouncer.time_until_due(start + Duration::from_secs(6)), None);
+        assert!(!debouncer.take_due(start + Duration::from_secs(6)));
+    }
+
+    #[test]
+    fn test_debouncer_batches_rapid_changes() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(Duration::from_secs(5));
+        
+        // Every save restarts the quiet period
+        for second in 0..4 {
+            debouncer.record_change(start + Duration::from_secs(second * 2));
+        }
+        assert!(!debouncer.take_due(start + Duration::from_secs(10)));
+        assert!(debouncer.take_due(start + Duration::from_secs(11)));
+    }
+
+    #[test]
+    fn test_git_internal_paths() {
+        assert!(is_git_internal(Path::new(".git/index")));
+        assert!(is_git_internal(Path::new("vendor/lib/.git/HEAD")));
+        assert!(!is_git_internal(Path::new("src/main.rs")));
+        assert!(!is_git_internal(Path::new(".gitignore")));
+    }
+
+    #[test]
+    fn test_ignored_changes() {
+        let (_dir, repo) = init_temp_repo();
+        write_file(&repo, ".gitignore", "target/\n*.log\n");
+        let workdir = repo.workdir().unwrap().to_path_buf();
+        
+        assert!(!is_ignored_change(&repo, &workdir, &workdir.join("src/main.rs")));
+        assert!(!is_ignored_change(&repo, &workdir, &workdir.join(".gitignore")));
+        assert!(is_ignored_change(&repo, &workdir, &workdir.join(".git/index")));
+        assert!(is_ignored_change(&repo, &workdir, &workdir.join("target/debug/maru")));
+        assert!(is_ignored_change(&repo, &workdir, &workdir.join("build.log")));
+        assert!(is_ignored_change(&repo, &workdir, Path::new("/somewhere/else.txt")));
+    }
+
+    #[test]
+    fn test_parse_watch_quiet_period() {
+        let args = Args::try_parse_from(["maru", "--watch"]).unwrap();
+        assert!(args.watch);
+        assert_eq!(args.quiet_period, 5);
+        
+        let args = Args::try_parse_from(["maru", "--watch", "--quiet-period", "30", "push", "-m", "Mirror"]).unwrap();
+        assert_eq!(args.quiet_period, 30);
+        
+        assert!(Args::try_parse_from(["maru", "--watch", "--daemon"]).is_err());
+    }
+
+    #[test]
+    fn test_only_changes_count_as_change_events() {
+        assert!(is_change_event(&EventKind::Create(CreateKind::File)));
+        assert!(is_change_event(&EventKind::Modify(ModifyKind::Data(DataChange::Content))));
+        assert!(is_change_event(&EventKind::Remove(RemoveKind::File)));
+        assert!(!is_change_event(&EventKind::Access(AccessKind::Read)));
+        assert!(!is_change_event(&EventKind::Any));
+        assert!(!is_change_event(&EventKind::Other));
+    }
+}
@@ -12,6 +12,7 @@ mod tests {
         pull_from_all_repositories,
         fetch_from_all_repositories
     };
+    use crate::core::error_handler::ResultStatus;
 
     #[test]
     fn test_validate_repository_url_comprehensive() {
@@ -151,9 +152,9 @@ mod tests {
         let _config = RepoConfig::new();
         
         // Verify function signatures
-        let _push_all_fn = push_to_all_repositories as fn(&RepoConfig, &str, &str) -> Vec<(String, String)>;
-        let _pull_all_fn = pull_from_all_repositories as fn(&RepoConfig, &str) -> Vec<(String, String)>;
-        let _fetch_all_fn = fetch_from_all_repositories as fn(&RepoConfig, &str) -> Vec<(String, String)>;
+        let _push_all_fn = push_to_all_repositories as fn(&RepoConfig, &str, &str) -> Vec<(String, ResultStatus, String)>;
+        let _pull_all_fn = pull_from_all_repositories as fn(&RepoConfig, &str) -> Vec<(String, ResultStatus, String)>;
+        let _fetch_all_fn = fetch_from_all_repositories as fn(&RepoConfig, &str) -> Vec<(String, ResultStatus, String)>;
     }
 
     #[test]
@@ -161,6 +162,8 @@ mod tests {
         // Test that all authentication types are properly defined
         assert_eq!(format!("{:?}", AuthType::Default), "Default");
         assert_eq!(format!("{:?}", AuthType::SSH), "SSH");
+        assert_eq!(format!("{:?}", AuthType::SshAgent), "SshAgent");
+        assert_eq!(format!("{:?}", AuthType::CredentialHelper), "CredentialHelper");
         assert_eq!(format!("{:?}", AuthType::Token), "Token");
     }
 }
\ No newline at end of file
@@ -1,13 +1,31 @@
-use crate::core::repository::RepoConfig;
+use crate::core::repository::{RepoConfig, RepositoryInfo};
+use crate::core::git_backend::{GitBackend, LocalBackend};
 use crate::core::git_operations::{
-    push_to_remote, 
-    pull_from_remote, 
-    fetch_from_remote,
-    add_all_changes,
-    commit_changes
+    validate_commit_message,
+    log_result,
+    skip_if_disabled,
+    with_lfs_warning,
+    CommitOptions,
+    NetworkOptions,
+    NothingToCommit,
+    TransferProgress,
+    LFS_PUSH_WARNING
 };
-use crate::core::error_handler::format_error_result;
-use git2::Repository;
+use crate::core::error_handler::{format_retry_result, retry_operation, ResultStatus, RetryPolicy, NO_CHANGES_TO_COMMIT};
+use crate::core::parallel::for_each_bounded;
+use log::{error, info, warn};
+
+// Open the repository in the current directory, reporting why when there is none
+fn open_current_repository(report: &mut dyn FnMut((String, ResultStatus, String))) -> Option<LocalBackend> {
+    match LocalBackend::open(".") {
+        Ok(backend) => Some(backend),
+        Err(e) => {
+            error!("Failed to open the local repository: {}", e);
+            report(("Repository".to_string(), ResultStatus::Failed, format!("Failed to open repository: {}", e)));
+            None
+        }
+    }
+}
 
 /// Perform push operation on all repositories in a group
 pub fn push_to_group_repositories(
@@ -15,49 +33,119 @@ pub fn push_to_group_repositories(
     group_name: &str, 
     commit_message: &str, 
     branch: &str
-) -> Vec<(String, String)> {
+) -> Vec<(String, ResultStatus, String)> {
     let mut results = Vec::new();
-    
+    push_to_group_repositories_reporting(config, group_name, commit_message, branch, &mut |result| results.push(result), &|_, _| {});
+    results
+}
+
+/// Commit once in the current directory's repository, then push to the repositories of a group
+/// `config.max_parallel_operations` at a time. Results are handed to `report` in group order
+/// and the transfer progress of each repository to `progress`.
+pub fn push_to_group_repositories_reporting(
+    config: &RepoConfig, 
+    group_name: &str, 
+    commit_message: &str, 
+    branch: &str,
+    report: &mut dyn FnMut((String, ResultStatus, String)),
+    progress: &(dyn Fn(&str, TransferProgress) + Sync),
+) {
+    if let Some(backend) = open_current_repository(report) {
+        push_to_group_repositories_with_backend(&backend, config, group_name, commit_message, branch, report, progress);
+    }
+}
+
+/// Like `push_to_group_repositories_reporting`, doing the git work through `backend`
+pub fn push_to_group_repositories_with_backend(
+    backend: &dyn GitBackend,
+    config: &RepoConfig, 
+    group_name: &str, 
+    commit_message: &str, 
+    branch: &str,
+    report: &mut dyn FnMut((String, ResultStatus, String)),
+    progress: &(dyn Fn(&str, TransferProgress) + Sync),
+) {
     // Get repositories in the group
     let repositories = config.get_repositories_in_group(group_name);
+    info!("Group {} has {} repositories", group_name, repositories.len());
     
     if repositories.is_empty() {
-        results.push((group_name.to_string(), "No repositories found in group".to_string()));
-        return results;
+        warn!("No repositories found in group {}", group_name);
+        report((group_name.to_string(), ResultStatus::Failed, "No repositories found in group".to_string()));
+        return;
     }
     
-    // Try to open the current repository
-    match Repository::open(".") {
-        Ok(repo) => {
-            // Add all changes
-            if let Err(e) = add_all_changes(&repo) {
-                results.push(("Repository".to_string(), format!("Failed to add changes: {}", e)));
-            }
-            
-            // Commit changes
-            if let Err(e) = commit_changes(&repo, commit_message) {
-                results.push(("Repository".to_string(), format!("Failed to commit changes: {}", e)));
-            }
-            
-            // Push to each repository in the group
-            for repo_info in repositories {
-                let result = push_to_remote(&repo, repo_info, branch);
-                results.push(format_error_result("pushing to", repo_info, result));
-            }
+    // Reject a bad commit message before staging anything
+    let commit_options = CommitOptions::from_config(config);
+    if let Err(e) = validate_commit_message(commit_message, &commit_options) {
+        warn!("Rejected commit message: {}", e);
+        report((group_name.to_string(), ResultStatus::Failed, e.to_string()));
+        return;
+    }
+    
+    let network_options = NetworkOptions::from_config(config);
+    
+    // Add all changes
+    if let Err(e) = backend.add_all() {
+        error!("Failed to add changes: {}", e);
+        report(("Repository".to_string(), ResultStatus::Failed, format!("Failed to add changes: {}", e)));
+    }
+    
+    // Commit changes
+    match backend.commit(commit_message, &commit_options) {
+        Ok(_) => {},
+        Err(e) if e.is::<NothingToCommit>() => {
+            report(("Repository".to_string(), ResultStatus::Skipped, NO_CHANGES_TO_COMMIT.to_string()));
         },
-        Err(_) => {
-            // In test environments or when no repo is available, we still want to test the functionality
-            // So we'll just add a note and simulate the operations
-            results.push(("Repository".to_string(), "Note: No local repository found, simulating remote operations only".to_string()));
-            
-            // Simulate push operations for testing
-            for repo_info in repositories {
-                results.push((repo_info.name.clone(), "Simulated push result for testing environment".to_string()));
-            }
+        Err(e) => {
+            error!("Failed to commit changes: {}", e);
+            report(("Repository".to_string(), ResultStatus::Failed, format!("Failed to commit changes: {}", e)));
         }
     }
     
-    results
+    // Push to each repository in the group
+    let targets: Vec<RepositoryInfo> = repositories.iter().filter(|repo_info| repo_info.enabled).flat_map(|repo_info| repo_info.push_targets()).collect();
+    prepare_remotes(backend, &targets);
+    for_each_bounded(&repositories, config.max_parallel_operations, |&repo_info| {
+        if let Some(skipped) = skip_if_disabled(repo_info) {
+            return vec![skipped];
+        }
+        push_to_all_remotes_of(backend, repo_info, branch, &config.retry, &network_options, progress)
+    }, &mut |_, results| {
+        for result in results {
+            report(result);
+        }
+    });
+}
+
+// Add the missing remotes one at a time before the parallel work starts, adding them concurrently races on `.git/config`.
+// A remote that can't be added is left for the operation itself to report.
+fn prepare_remotes<'a>(backend: &dyn GitBackend, targets: impl IntoIterator<Item = &'a RepositoryInfo>) {
+    for target in targets {
+        if let Err(e) = backend.prepare_remote(target) {
+            warn!("Failed to add the remote {}: {}", target.name, e);
+        }
+    }
+}
+
+/// Push `branch` to a repository and then to each of its extra remotes, one result per remote
+pub fn push_to_all_remotes_of(
+    backend: &dyn GitBackend,
+    repo_info: &RepositoryInfo,
+    branch: &str,
+    retry: &RetryPolicy,
+    network_options: &NetworkOptions,
+    progress: &dyn Fn(&str, TransferProgress),
+) -> Vec<(String, ResultStatus, String)> {
+    let uses_lfs = backend.uses_lfs();
+    repo_info.push_targets().iter().map(|target| {
+        let (result, attempts) = retry_operation(retry, "pushing to", target, || {
+            backend.push(target, branch, network_options, &|transfer| progress(&target.name, transfer))
+        });
+        log_result("Push to", target, &result);
+        let result = format_retry_result("pushing to", target, result, attempts);
+        if uses_lfs { with_lfs_warning(result, LFS_PUSH_WARNING) } else { result }
+    }).collect()
 }
 
 /// Perform pull operation on all repositories in a group
@@ -65,35 +153,79 @@ pub fn pull_from_group_repositories(
     config: &RepoConfig, 
     group_name: &str, 
     branch: &str
-) -> Vec<(String, String)> {
+) -> Vec<(String, ResultStatus, String)> {
     let mut results = Vec::new();
-    
+    pull_from_group_repositories_reporting(config, group_name, branch, false, &mut |result| results.push(result));
+    results
+}
+
+/// Pull from the repositories of a group into the current directory's repository, reporting each result as it completes.
+/// With `auto_stash`, local changes are stashed while the pulls run.
+/// Unlike push and fetch the pulls run one at a time since each one merges into the same working tree.
+pub fn pull_from_group_repositories_reporting(
+    config: &RepoConfig, 
+    group_name: &str, 
+    branch: &str,
+    auto_stash: bool,
+    report: &mut dyn FnMut((String, ResultStatus, String)),
+) {
+    if let Some(backend) = open_current_repository(report) {
+        pull_from_group_repositories_with_backend(&backend, config, group_name, branch, auto_stash, report);
+    }
+}
+
+/// Like `pull_from_group_repositories_reporting`, doing the git work through `backend`
+pub fn pull_from_group_repositories_with_backend(
+    backend: &dyn GitBackend,
+    config: &RepoConfig, 
+    group_name: &str, 
+    branch: &str,
+    auto_stash: bool,
+    report: &mut dyn FnMut((String, ResultStatus, String)),
+) {
     // Get repositories in the group
     let repositories = config.get_repositories_in_group(group_name);
+    info!("Group {} has {} repositories", group_name, repositories.len());
     
     if repositories.is_empty() {
-        results.push((group_name.to_string(), "No repositories found in group".to_string()));
-        return results;
+        warn!("No repositories found in group {}", group_name);
+        report((group_name.to_string(), ResultStatus::Failed, "No repositories found in group".to_string()));
+        return;
     }
     
-    // Try to open the current repository
-    match Repository::open(".") {
-        Ok(repo) => {
-            // Pull from each repository in the group
-            for repo_info in repositories {
-                let result = pull_from_remote(&repo, repo_info, branch);
-                results.push(format_error_result("pulling from", repo_info, result));
-            }
-        },
-        Err(_) => {
-            // In test environments, simulate results
-            for repo_info in repositories {
-                results.push((repo_info.name.clone(), "Simulated pull result for testing environment".to_string()));
+    let network_options = NetworkOptions::from_config(config);
+    
+    let stashed = if auto_stash {
+        match backend.stash() {
+            Ok(stashed) => stashed,
+            Err(e) => {
+                error!("Failed to stash local changes: {}", e);
+                report(("Repository".to_string(), ResultStatus::Failed, format!("Failed to stash local changes: {}", e)));
+                return;
             }
         }
+    } else {
+        false
+    };
+    
+    // Pull from each repository in the group
+    for repo_info in repositories {
+        if let Some(skipped) = skip_if_disabled(repo_info) {
+            report(skipped);
+            continue;
+        }
+        let (result, attempts) = retry_operation(&config.retry, "pulling from", repo_info, || {
+            backend.pull(repo_info, branch, &network_options)
+        });
+        log_result("Pull from", repo_info, &result);
+        report(format_retry_result("pulling from", repo_info, result, attempts));
     }
     
-    results
+    if stashed && let Err(e) = backend.unstash() {
+        // The changes are still in the stash, nothing is lost
+        error!("Failed to restore stashed changes: {}", e);
+        report(("Repository".to_string(), ResultStatus::Failed, format!("Failed to restore stashed changes, they are kept in the stash: {}", e)));
+    }
 }
 
 /// Perform fetch operation on all repositories in a group
@@ -101,33 +233,57 @@ pub fn fetch_from_group_repositories(
     config: &RepoConfig, 
     group_name: &str, 
     branch: &str
-) -> Vec<(String, String)> {
+) -> Vec<(String, ResultStatus, String)> {
     let mut results = Vec::new();
-    
+    fetch_from_group_repositories_reporting(config, group_name, branch, &mut |result| results.push(result), &|_, _| {});
+    results
+}
+
+/// Fetch from the repositories of a group into the current directory's repository `config.max_parallel_operations`
+/// at a time, reporting the results in group order and the transfer progress while they run
+pub fn fetch_from_group_repositories_reporting(
+    config: &RepoConfig, 
+    group_name: &str, 
+    branch: &str,
+    report: &mut dyn FnMut((String, ResultStatus, String)),
+    progress: &(dyn Fn(&str, TransferProgress) + Sync),
+) {
+    if let Some(backend) = open_current_repository(report) {
+        fetch_from_group_repositories_with_backend(&backend, config, group_name, branch, report, progress);
+    }
+}
+
+/// Like `fetch_from_group_repositories_reporting`, doing the git work through `backend`
+pub fn fetch_from_group_repositories_with_backend(
+    backend: &dyn GitBackend,
+    config: &RepoConfig, 
+    group_name: &str, 
+    branch: &str,
+    report: &mut dyn FnMut((String, ResultStatus, String)),
+    progress: &(dyn Fn(&str, TransferProgress) + Sync),
+) {
     // Get repositories in the group
     let repositories = config.get_repositories_in_group(group_name);
+    info!("Group {} has {} repositories", group_name, repositories.len());
     
     if repositories.is_empty() {
-        results.push((group_name.to_string(), "No repositories found in group".to_string()));
-        return results;
+        warn!("No repositories found in group {}", group_name);
+        report((group_name.to_string(), ResultStatus::Failed, "No repositories found in group".to_string()));
+        return;
     }
     
-    // Try to open the current repository
-    match Repository::open(".") {
-        Ok(repo) => {
-            // Fetch from each repository in the group
-            for repo_info in repositories {
-                let result = fetch_from_remote(&repo, repo_info, branch);
-                results.push(format_error_result("fetching from", repo_info, result));
-            }
-        },
-        Err(_) => {
-            // In test environments, simulate results
-            for repo_info in repositories {
-                results.push((repo_info.name.clone(), "Simulated fetch result for testing environment".to_string()));
-            }
-        }
-    }
+    let network_options = NetworkOptions::from_config(config);
     
-    results
+    // Fetch from each repository in the group
+    prepare_remotes(backend, repositories.iter().copied().filter(|repo_info| repo_info.enabled));
+    for_each_bounded(&repositories, config.max_parallel_operations, |&repo_info| {
+        if let Some(skipped) = skip_if_disabled(repo_info) {
+            return skipped;
+        }
+        let (result, attempts) = retry_operation(&config.retry, "fetching from", repo_info, || {
+            backend.fetch(repo_info, branch, &network_options, &|transfer| progress(&repo_info.name, transfer))
+        });
+        log_result("Fetch from", repo_info, &result);
+        format_retry_result("fetching from", repo_info, result, attempts)
+    }, &mut |_, result| report(result));
 }
\ No newline at end of file
@@ -0,0 +1,96 @@
+use anyhow::Result;
+use git2::Repository;
+use std::path::Path;
+use crate::core::git_operations::is_conventional_commit;
+
+// Headings of the changelog, in the order they are written
+const FEATURES: &str = "Features";
+const FIXES: &str = "Fixes";
+const OTHER: &str = "Other";
+
+// Turn a commit header into its changelog heading and entry text.
+// "feat(ui): add dark mode" is a feature "**ui:** add dark mode", anything
+// that isn't a conventional commit goes under Other as it is.
+fn changelog_entry(header: &str) -> (&'static str, String) {
+    if !is_conventional_commit(header) {
+        return (OTHER, header.to_string());
+    }
+    let Some((prefix, description)) = header.split_once(": ") else {
+        return (OTHER, header.to_string());
+    };
+    
+    let (commit_type, scope) = match prefix.split_once('(') {
+        Some((commit_type, scope)) => (commit_type, scope.strip_suffix(')')),
+        None => (prefix, None),
+    };
+    let heading = match commit_type {
+        "feat" => FEATURES,
+        "fix" => FIXES,
+        _ => OTHER,
+    };
+    let text = match scope {
+        Some(scope) => format!("**{}:** {}", scope, description),
+        None => description.to_string(),
+    };
+    (heading, text)
+}
+
+/// Markdown changelog of the commits in `from_tag..to_ref`, grouped into Features, Fixes and Other
+/// by their conventional-commit type. An empty `from_tag` covers the whole history of `to_ref`.
+/// Merge commits are left out, empty sections aren't written.
+pub fn generate_changelog(repo_path: &str, from_tag: &str, to_ref: &str) -> Result<String> {
+    let repo = Repository::open(repo_path)?;
+    let mut revwalk = repo.revwalk()?;
+    if from_tag.is_empty() {
+        revwalk.push(repo.revparse_single(to_ref)?.peel_to_commit()?.id())?;
+    } else {
+        revwalk.push_range(&format!("{}..{}", from_tag, to_ref))?;
+    }
+    
+    let mut sections: Vec<(&str, Vec<String>)> = vec![(FEATURES, Vec::new()), (FIXES, Vec::new()), (OTHER, Vec::new())];
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        
+        let (heading, text) = changelog_entry(commit.summary().unwrap_or_default());
+        let short_id = &commit.id().to_string()[..7];
+        if let Some((_, entries)) = sections.iter_mut().find(|(name, _)| *name == heading) {
+            entries.push(format!("- {} ({})", text, short_id));
+        }
+    }
+    
+    let title = if from_tag.is_empty() {
+        format!("## {}", to_ref)
+    } else {
+        format!("## {}..{}", from_tag, to_ref)
+    };
+    let mut changelog = title;
+    for (heading, entries) in sections {
+        if entries.is_empty() {
+            continue;
+        }
+        changelog.push_str(&format!("\n\n### {}\n\n{}", heading, entries.join("\n")));
+    }
+    changelog.push('\n');
+    
+    Ok(changelog)
+}
+
+/// Put `changelog` at the top of the file at `path`, keeping the older entries below it
+pub fn save_changelog(path: &Path, changelog: &str) -> Result<()> {
+    let existing = match std::fs::read_to_string(path) {
+        Ok(existing) => existing,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    
+    let content = if existing.is_empty() {
+        changelog.to_string()
+    } else {
+        format!("{}\n{}", changelog, existing)
+    };
+    std::fs::write(path, content)?;
+    Ok(())
+}
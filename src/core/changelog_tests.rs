@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::changelog::{generate_changelog, save_changelog};
+    use crate::core::test_support::{commit_file, init_temp_repo};
+
+    #[test]
+    fn test_changelog_groups_conventional_commits() {
+        let (dir, repo) = init_temp_repo();
+        let released = commit_file(&repo, "a.txt", "1\n", "feat: already released");
+        repo.tag_lightweight("v1.0.0", &repo.find_object(released, None).unwrap(), false).unwrap();
+        
+        commit_file(&repo, "b.txt", "1\n", "feat(ui): add dark mode");
+        commit_file(&repo, "c.txt", "1\n", "fix: crash on empty config");
+        commit_file(&repo, "d.txt", "1\n", "docs: explain groups");
+        commit_file(&repo, "e.txt", "1\n", "tweak things");
+        let latest = commit_file(&repo, "f.txt", "1\n", "feat: push to selected repositories");
+        
+        let changelog = generate_changelog(dir.path().to_str().unwrap(), "v1.0.0", "HEAD").unwrap();
+        
+        let features = changelog.find("### Features").unwrap();
+        let fixes = changelog.find("### Fixes").unwrap();
+        let other = changelog.find("### Other").unwrap();
+        assert!(changelog.starts_with("## v1.0.0..HEAD"));
+        assert!(features < fixes && fixes < other);
+        
+        let position = |text: &str| changelog.find(text).unwrap_or_else(|| panic!("{} missing from:\n{}", text, changelog));
+        assert!((features..fixes).contains(&position("- **ui:** add dark mode")));
+        assert!((features..fixes).contains(&position(&format!("- push to selected repositories ({})", &latest.to_string()[..7]))));
+        assert!((fixes..other).contains(&position("- crash on empty config")));
+        assert!(position("- explain groups") > other);
+        assert!(position("- tweak things") > other);
+        // Commits before the tag aren't part of the range
+        assert!(!changelog.contains("already released"));
+    }
+
+    #[test]
+    fn test_changelog_leaves_out_empty_sections() {
+        let (dir, repo) = init_temp_repo();
+        commit_file(&repo, "a.txt", "1\n", "fix: only a fix");
+        
+        let changelog = generate_changelog(dir.path().to_str().unwrap(), "", "HEAD").unwrap();
+        
+        assert!(changelog.starts_with("## HEAD"));
+        assert!(changelog.contains("### Fixes"));
+        assert!(!changelog.contains("### Features"));
+        assert!(!changelog.contains("### Other"));
+    }
+
+    #[test]
+    fn test_save_changelog_prepends() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("CHANGELOG.md");
+        
+        save_changelog(&path, "## v1.0.0\n").unwrap();
+        save_changelog(&path, "## v1.1.0\n").unwrap();
+        
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "## v1.1.0\n\n## v1.0.0\n");
+    }
+}
@@ -1,6 +1,7 @@
 use git2::{Repository, Oid, Commit, DiffOptions, DiffDelta};
 use serde::{Deserialize, Serialize};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use crate::core::repository_comparison::{push_patch_line, DiffSettings};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct CommitInfo {
@@ -36,6 +37,53 @@ pub struct CommitDiff {
     pub diff_content: String,
 }
 
+/// A line of a file at HEAD together with the commit that last changed it
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BlameLine {
+    pub line_no: usize,
+    pub commit_id: String,
+    pub author: String,
+    pub content: String,
+}
+
+/// Client-side filter over commits that were already loaded
+#[derive(Debug, Clone, Default)]
+pub struct CommitFilter {
+    pub text: String,       // Case-insensitive match on message, author or short id
+    pub author: String,     // Case-insensitive match on author name or email
+    pub since: Option<i64>, // Inclusive lower bound on the author date
+    pub until: Option<i64>, // Inclusive upper bound on the author date
+}
+
+impl CommitFilter {
+    pub fn matches(&self, commit: &CommitInfo) -> bool {
+        let text = self.text.trim().to_lowercase();
+        if !text.is_empty()
+            && !commit.message.to_lowercase().contains(&text)
+            && !commit.author.to_lowercase().contains(&text)
+            && !commit.short_id.to_lowercase().contains(&text)
+        {
+            return false;
+        }
+        
+        let author = self.author.trim().to_lowercase();
+        if !author.is_empty()
+            && !commit.author.to_lowercase().contains(&author)
+            && !commit.author_email.to_lowercase().contains(&author)
+        {
+            return false;
+        }
+        
+        self.since.is_none_or(|since| commit.date >= since)
+            && self.until.is_none_or(|until| commit.date <= until)
+    }
+}
+
+/// Keep the commits matching the filter, in their original order
+pub fn filter_commits<'a>(commits: &'a [CommitInfo], filter: &CommitFilter) -> Vec<&'a CommitInfo> {
+    commits.iter().filter(|commit| filter.matches(commit)).collect()
+}
+
 impl CommitInfo {
     pub fn from_git_commit(commit: &Commit) -> Self {
         let id = commit.id().to_string();
@@ -94,6 +142,54 @@ pub fn get_commit_history(repo_path: &str, limit: usize) -> Result<Vec<CommitInf
     Ok(commits)
 }
 
+/// List the commits (newest first) that changed `file_path`, up to `limit` of them.
+/// Like `git log -- <path>`, a merge only counts if the file differs from every parent.
+pub fn get_file_history(repo_path: &str, file_path: &str, limit: usize) -> Result<Vec<CommitInfo>> {
+    let repo = Repository::open(repo_path)?;
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_head()?;
+    
+    let mut commits = Vec::new();
+    
+    for oid in revwalk {
+        if commits.len() >= limit {
+            break;
+        }
+        
+        let commit = repo.find_commit(oid?)?;
+        if commit_touches_path(&repo, &commit, file_path)? {
+            commits.push(CommitInfo::from_git_commit(&commit));
+        }
+    }
+    
+    Ok(commits)
+}
+
+// Whether the commit changed the path compared to each of its parents (or added it, for a root commit)
+fn commit_touches_path(repo: &Repository, commit: &Commit, file_path: &str) -> Result<bool> {
+    let tree = commit.tree()?;
+    
+    let changed_against = |parent_tree: Option<&git2::Tree>| -> Result<bool> {
+        let mut diff_options = DiffOptions::new();
+        diff_options.pathspec(file_path);
+        diff_options.disable_pathspec_match(true);
+        let diff = repo.diff_tree_to_tree(parent_tree, Some(&tree), Some(&mut diff_options))?;
+        Ok(diff.deltas().len() > 0)
+    };
+    
+    if commit.parent_count() == 0 {
+        return changed_against(None);
+    }
+    
+    for parent in commit.parents() {
+        if !changed_against(Some(&parent.tree()?))? {
+            return Ok(false);
+        }
+    }
+    
+    Ok(true)
+}
+
 pub fn get_commit_diff(repo_path: &str, commit_id: &str) -> Result<CommitDiff> {
     let repo = Repository::open(repo_path)?;
     let oid = Oid::from_str(commit_id)?;
@@ -103,12 +199,13 @@ pub fn get_commit_diff(repo_path: &str, commit_id: &str) -> Result<CommitDiff> {
     
     // Get the diff for this commit
     let mut diff_options = DiffOptions::new();
-    let diff = if commit.parent_count() > 0 {
+    let mut diff = if commit.parent_count() > 0 {
         let parent = commit.parent(0)?;
         repo.diff_tree_to_tree(Some(&parent.tree()?), Some(&commit.tree()?), Some(&mut diff_options))?
     } else {
         repo.diff_tree_to_tree(None, Some(&commit.tree()?), Some(&mut diff_options))?
     };
+    DiffSettings::default().find_renames(&mut diff)?;
     
     let mut file_changes = Vec::new();
     let mut diff_content = String::new();
@@ -121,8 +218,8 @@ pub fn get_commit_diff(repo_path: &str, commit_id: &str) -> Result<CommitDiff> {
     }, None, None, None)?;
     
     // Format the diff content
-    diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
-        diff_content.push_str(&format!("{}", String::from_utf8_lossy(line.content())));
+    diff.print(git2::DiffFormat::Patch, |delta, _hunk, line| {
+        push_patch_line(&mut diff_content, &delta, &line);
         true
     })?;
     
@@ -135,4 +232,48 @@ pub fn get_commit_diff(repo_path: &str, commit_id: &str) -> Result<CommitDiff> {
 
 pub fn get_repository_commits(repo_path: &str, limit: usize) -> Result<Vec<CommitInfo>> {
     get_commit_history(repo_path, limit)
-}
\ No newline at end of file
+}
+
+/// Blame every line of `file_path` as it is at HEAD.
+/// Binary files and paths that don't exist at HEAD are reported as errors.
+pub fn blame_file(repo_path: &str, file_path: &str) -> Result<Vec<BlameLine>> {
+    let repo = Repository::open(repo_path)?;
+    let head_tree = repo.head()?.peel_to_tree()?;
+    
+    let entry = head_tree
+        .get_path(std::path::Path::new(file_path))
+        .map_err(|_| anyhow!("'{}' does not exist at HEAD", file_path))?;
+    let blob = entry
+        .to_object(&repo)?
+        .into_blob()
+        .map_err(|_| anyhow!("'{}' is not a file", file_path))?;
+    
+    if blob.is_binary() {
+        return Err(anyhow!("'{}' is a binary file and can't be blamed", file_path));
+    }
+    
+    let blame = repo.blame_file(std::path::Path::new(file_path), None)?;
+    let content = String::from_utf8_lossy(blob.content());
+    
+    let mut lines = Vec::new();
+    
+    for (index, line) in content.lines().enumerate() {
+        let line_no = index + 1;
+        let (commit_id, author) = match blame.get_line(line_no) {
+            Some(hunk) => (
+                hunk.final_commit_id().to_string(),
+                hunk.final_signature().name().unwrap_or("Unknown").to_string(),
+            ),
+            None => (String::new(), String::new()),
+        };
+        
+        lines.push(BlameLine {
+            line_no,
+            commit_id,
+            author,
+            content: line.to_string(),
+        });
+    }
+    
+    Ok(lines)
+}
@@ -7,8 +7,13 @@ mod tests {
         CommitDiff,
         get_commit_history,
         get_commit_diff,
-        get_repository_commits
+        get_repository_commits,
+        get_file_history,
+        blame_file,
+        filter_commits,
+        CommitFilter
     };
+    use crate::core::test_support::{init_temp_repo, commit_file};
     use git2::Commit;
 
     // Since we can't easily create real git commits for testing, we'll test the data structures
@@ -158,4 +163,174 @@ mod tests {
         let _status_deleted = FileChangeStatus::Deleted;
         let _status_renamed = FileChangeStatus::Renamed;
     }
-}
\ No newline at end of file
+
+    fn sample_commit(short_id: &str, message: &str, author: &str, date: i64) -> CommitInfo {
+        CommitInfo {
+            id: format!("{}000000000", short_id),
+            short_id: short_id.to_string(),
+            message: message.to_string(),
+            author: author.to_string(),
+            author_email: format!("{}@example.com", author.to_lowercase()),
+            date,
+            parents: Vec::new(),
+        }
+    }
+
+    fn sample_commits() -> Vec<CommitInfo> {
+        vec![
+            sample_commit("aaa1111", "Fix login bug", "Alice", 1_700_000_000),
+            sample_commit("bbb2222", "Add search box", "Bob", 1_700_100_000),
+            sample_commit("ccc3333", "Refactor login form", "Bob", 1_700_200_000),
+        ]
+    }
+
+    #[test]
+    fn test_filter_commits_empty_filter_keeps_all() {
+        let commits = sample_commits();
+        assert_eq!(filter_commits(&commits, &CommitFilter::default()).len(), 3);
+    }
+
+    #[test]
+    fn test_filter_commits_by_text() {
+        let commits = sample_commits();
+        
+        let filter = CommitFilter { text: "LOGIN".to_string(), ..Default::default() };
+        let ids: Vec<&str> = filter_commits(&commits, &filter).iter().map(|c| c.short_id.as_str()).collect();
+        assert_eq!(ids, vec!["aaa1111", "ccc3333"]);
+        
+        // Short ids and authors match too
+        let filter = CommitFilter { text: "bbb2".to_string(), ..Default::default() };
+        assert_eq!(filter_commits(&commits, &filter).len(), 1);
+        let filter = CommitFilter { text: "alice".to_string(), ..Default::default() };
+        assert_eq!(filter_commits(&commits, &filter).len(), 1);
+    }
+
+    #[test]
+    fn test_filter_commits_by_author_and_date() {
+        let commits = sample_commits();
+        
+        let filter = CommitFilter { author: "bob@".to_string(), ..Default::default() };
+        assert_eq!(filter_commits(&commits, &filter).len(), 2);
+        
+        let filter = CommitFilter {
+            author: "bob".to_string(),
+            since: Some(1_700_150_000),
+            ..Default::default()
+        };
+        let ids: Vec<&str> = filter_commits(&commits, &filter).iter().map(|c| c.short_id.as_str()).collect();
+        assert_eq!(ids, vec!["ccc3333"]);
+        
+        let filter = CommitFilter { until: Some(1_700_100_000), ..Default::default() };
+        assert_eq!(filter_commits(&commits, &filter).len(), 2);
+    }
+
+    #[test]
+    fn test_get_commit_history_respects_limit() {
+        let (dir, repo) = init_temp_repo();
+        for i in 0..8 {
+            commit_file(&repo, "counter.txt", &format!("{}\n", i), &format!("Commit {}", i));
+        }
+        let repo_path = dir.path().to_str().unwrap();
+        
+        let commits = get_commit_history(repo_path, 5).unwrap();
+        assert_eq!(commits.len(), 5);
+        // Newest first
+        assert_eq!(commits[0].message, "Commit 7");
+        
+        assert_eq!(get_commit_history(repo_path, 8).unwrap().len(), 8);
+        assert_eq!(get_commit_history(repo_path, usize::MAX).unwrap().len(), 8);
+        assert!(get_commit_history(repo_path, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_file_history_only_includes_commits_touching_the_file() {
+        let (dir, repo) = init_temp_repo();
+        commit_file(&repo, "tracked.txt", "first\n", "Add tracked file");
+        commit_file(&repo, "other.txt", "unrelated\n", "Add other file");
+        commit_file(&repo, "tracked.txt", "second\n", "Update tracked file");
+        let repo_path = dir.path().to_str().unwrap();
+        
+        let history = get_file_history(repo_path, "tracked.txt", 50).unwrap();
+        let messages: Vec<&str> = history.iter().map(|commit| commit.message.as_str()).collect();
+        assert_eq!(messages, vec!["Update tracked file", "Add tracked file"]);
+        
+        assert_eq!(get_file_history(repo_path, "tracked.txt", 1).unwrap().len(), 1);
+        assert!(get_file_history(repo_path, "missing.txt", 50).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_blame_file_attributes_lines_to_commits() {
+        let (dir, repo) = init_temp_repo();
+        let first = commit_file(&repo, "notes.txt", "one\ntwo\n", "Add notes");
+        let second = commit_file(&repo, "notes.txt", "one\ntwo\nthree\n", "Append to notes");
+        let repo_path = dir.path().to_str().unwrap();
+        
+        let lines = blame_file(repo_path, "notes.txt").unwrap();
+        assert_eq!(lines.len(), 3);
+        
+        assert_eq!(lines[0].line_no, 1);
+        assert_eq!(lines[0].content, "one");
+        assert_eq!(lines[0].commit_id, first.to_string());
+        assert_eq!(lines[1].commit_id, first.to_string());
+        assert_eq!(lines[2].content, "three");
+        assert_eq!(lines[2].commit_id, second.to_string());
+        assert_eq!(lines[2].author, "Test User");
+    }
+
+    #[test]
+    fn test_blame_file_missing_and_binary() {
+        let (dir, repo) = init_temp_repo();
+        commit_file(&repo, "notes.txt", "one\n", "Add notes");
+        let repo_path = dir.path().to_str().unwrap();
+        
+        let err = blame_file(repo_path, "missing.txt").unwrap_err();
+        assert!(err.to_string().contains("does not exist"));
+        
+        commit_file(&repo, "image.bin", "\0\x01\x02binary", "Add binary");
+        let err = blame_file(repo_path, "image.bin").unwrap_err();
+        assert!(err.to_string().contains("binary"));
+    }
+
+    #[test]
+    fn test_commit_diff_shows_placeholder_for_binary_files() {
+        let (dir, repo) = init_temp_repo();
+        commit_file(&repo, "image.bin", "\0\x01\x02first", "Add binary");
+        commit_file(&repo, "notes.txt", "one\n", "Add notes");
+        commit_file(&repo, "notes.txt", "two\n", "Change notes");
+        std::fs::write(dir.path().join("image.bin"), b"\0\xff\xfe\x80second").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("image.bin")).unwrap();
+        index.write().unwrap();
+        commit_file(&repo, "notes.txt", "three\n", "Change notes and binary");
+        let head = repo.head().unwrap().target().unwrap();
+        
+        let diff = get_commit_diff(dir.path().to_str().unwrap(), &head.to_string()).unwrap();
+        assert!(diff.diff_content.contains("diff --git a/image.bin b/image.bin"));
+        assert!(diff.diff_content.contains("Binary file changed"));
+        assert!(!diff.diff_content.contains("second"));
+        assert!(!diff.diff_content.contains('\u{FFFD}'));
+        // Text files next to it still show their lines
+        assert!(diff.diff_content.contains("-two"));
+        assert!(diff.diff_content.contains("+three"));
+    }
+
+    #[test]
+    fn test_commit_diff_detects_renames() {
+        let (dir, repo) = init_temp_repo();
+        commit_file(&repo, "docs/guide.md", "# Guide\n\nStep one\nStep two\n", "Add guide");
+        
+        std::fs::remove_file(dir.path().join("docs/guide.md")).unwrap();
+        let mut index = repo.index().unwrap();
+        index.remove_path(std::path::Path::new("docs/guide.md")).unwrap();
+        index.write().unwrap();
+        let rename = commit_file(&repo, "docs/user-guide.md", "# Guide\n\nStep one\nStep two\n", "Rename guide");
+        
+        let diff = get_commit_diff(dir.path().to_str().unwrap(), &rename.to_string()).unwrap();
+        assert_eq!(diff.file_changes, vec![FileChange {
+            path: "docs/user-guide.md".to_string(),
+            status: FileChangeStatus::Renamed,
+            additions: 0,
+            deletions: 0,
+        }]);
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::path::Path;
+use crate::core::config_migration::migrate;
+use crate::core::repository::RepoConfig;
+
+/// File formats the configuration can be exported to. JSON stays the format of `repos.json`,
+/// the others are for editing the configuration by hand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// Pick the format from the file extension
+    pub fn from_path(path: &str) -> Result<Self> {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_lowercase());
+        match extension.as_deref() {
+            Some("json") => Ok(ConfigFormat::Json),
+            Some("yaml") | Some("yml") => Ok(ConfigFormat::Yaml),
+            Some("toml") => Ok(ConfigFormat::Toml),
+            _ => Err(anyhow!("Unknown configuration format for {}, expected .json, .yaml, .yml or .toml", path)),
+        }
+    }
+    
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ConfigFormat::Json => "json",
+            ConfigFormat::Yaml => "yaml",
+            ConfigFormat::Toml => "toml",
+        }
+    }
+}
+
+/// Serialize the configuration in the given format
+pub fn config_to_string(config: &RepoConfig, format: ConfigFormat) -> Result<String> {
+    Ok(match format {
+        ConfigFormat::Json => serde_json::to_string_pretty(config)?,
+        ConfigFormat::Yaml => serde_yaml::to_string(config)?,
+        ConfigFormat::Toml => toml::to_string_pretty(config)?,
+    })
+}
+
+/// Read a configuration written in the given format, migrating older versions
+pub fn config_from_str(contents: &str, format: ConfigFormat) -> Result<RepoConfig> {
+    // Go through JSON values so every format gets the same migration
+    let config: Value = match format {
+        ConfigFormat::Json => serde_json::from_str(contents)?,
+        ConfigFormat::Yaml => serde_yaml::from_str(contents)?,
+        ConfigFormat::Toml => toml::from_str(contents)?,
+    };
+    migrate(config)
+}
+
+pub fn export_config(config: &RepoConfig, path: &str, format: ConfigFormat) -> Result<()> {
+    std::fs::write(path, config_to_string(config, format)?)
+        .map_err(|e| anyhow!("Failed to write {}: {}", path, e))
+}
+
+/// Read a configuration exported by `export_config`, with the format taken from the extension
+pub fn import_config(path: &str) -> Result<RepoConfig> {
+    let format = ConfigFormat::from_path(path)?;
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read {}: {}", path, e))?;
+    config_from_str(&contents, format)
+}
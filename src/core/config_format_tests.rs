@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::config_format::{config_from_str, config_to_string, export_config, import_config, ConfigFormat};
+    use crate::core::repository::{AuthType, RepoConfig, RepositoryGroup, RepositoryInfo};
+
+    fn sample_config() -> RepoConfig {
+        let mut config = RepoConfig::with_name("work".to_string());
+        config.add_repository(RepositoryInfo::new("repo1".to_string(), "https://github.com/user/repo1.git".to_string()));
+        let mut repo2 = RepositoryInfo::with_auth("repo2".to_string(), "git@github.com:user/repo2.git".to_string(), AuthType::SshAgent)
+            .with_group("backend".to_string())
+            .with_default_branch("develop".to_string());
+        repo2.enabled = false;
+        config.add_repository(repo2);
+        let mut group = RepositoryGroup::new("backend".to_string(), "Services".to_string());
+        group.add_repository("repo2".to_string());
+        config.add_group(group);
+        config.signing_key = Some("ABCD1234".to_string());
+        config.timeout_secs = 90;
+        config
+    }
+
+    #[test]
+    fn test_config_round_trips_through_each_format() {
+        let config = sample_config();
+        let expected = serde_json::to_value(&config).unwrap();
+        
+        for format in [ConfigFormat::Json, ConfigFormat::Yaml, ConfigFormat::Toml] {
+            let contents = config_to_string(&config, format).unwrap();
+            let restored = config_from_str(&contents, format).unwrap();
+            assert_eq!(serde_json::to_value(&restored).unwrap(), expected, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(ConfigFormat::from_path("repos.json").unwrap(), ConfigFormat::Json);
+        assert_eq!(ConfigFormat::from_path("repos.yml").unwrap(), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path("dir/repos.YAML").unwrap(), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path("repos.toml").unwrap(), ConfigFormat::Toml);
+        assert!(ConfigFormat::from_path("repos.txt").is_err());
+        assert!(ConfigFormat::from_path("repos").is_err());
+    }
+
+    #[test]
+    fn test_export_and_import_detect_format() {
+        let config = sample_config();
+        let dir = tempfile::TempDir::new().unwrap();
+        
+        for format in [ConfigFormat::Json, ConfigFormat::Yaml, ConfigFormat::Toml] {
+            let path = dir.path().join(format!("repos.{}", format.extension())).to_string_lossy().to_string();
+            export_config(&config, &path, format).unwrap();
+            let imported = import_config(&path).unwrap();
+            assert_eq!(imported.repositories.len(), 2);
+            assert_eq!(imported.repositories[1].default_branch.as_deref(), Some("develop"));
+            assert!(!imported.repositories[1].enabled);
+        }
+    }
+
+    #[test]
+    fn test_export_leaves_out_ssh_passphrases() {
+        let mut config = sample_config();
+        config.repositories[0].ssh_passphrase = Some("correct horse".to_string());
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("repos.yaml").to_string_lossy().to_string();
+        
+        export_config(&config, &path, ConfigFormat::Yaml).unwrap();
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("correct horse"));
+        assert_eq!(import_config(&path).unwrap().repositories[0].ssh_passphrase, None);
+    }
+}
@@ -0,0 +1,77 @@
+use anyhow::{anyhow, Result};
+use log::info;
+use serde_json::{Map, Value};
+use crate::core::repository::RepoConfig;
+
+/// Version of the config file written by this build. Bump it and add a step to
+/// `migrate` whenever a change can't be expressed with `#[serde(default)]`.
+pub const CURRENT_CONFIG_VERSION: u32 = 1;
+
+/// Upgrade a config file of any known version to the current shape, then deserialize it.
+/// Files without a `version` field predate versioning and are treated as version 0.
+pub fn migrate(mut config: Value) -> Result<RepoConfig> {
+    let root = config
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("The configuration must be a JSON object"))?;
+    
+    let version = match root.get("version") {
+        None => 0,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| anyhow!("Invalid configuration version: {}", version))?,
+    };
+    if version > CURRENT_CONFIG_VERSION {
+        return Err(anyhow!(
+            "The configuration has version {}, this build only understands up to version {}",
+            version,
+            CURRENT_CONFIG_VERSION
+        ));
+    }
+    
+    if version < 1 {
+        migrate_v0_to_v1(root);
+    }
+    
+    if version < CURRENT_CONFIG_VERSION {
+        info!("Migrated the configuration from version {} to {}", version, CURRENT_CONFIG_VERSION);
+    }
+    root.insert("version".to_string(), Value::from(CURRENT_CONFIG_VERSION));
+    
+    Ok(serde_json::from_value(config)?)
+}
+
+// Version 0 only had the `group` name on each repository. Build the group list from those
+// names so grouped repositories keep working with the group operations.
+fn migrate_v0_to_v1(root: &mut Map<String, Value>) {
+    if root.contains_key("groups") {
+        return;
+    }
+    
+    let mut groups: Vec<(String, Vec<Value>)> = Vec::new();
+    let repositories = root.get("repositories").and_then(Value::as_array).into_iter().flatten();
+    for repo in repositories {
+        let name = repo.get("name").and_then(Value::as_str);
+        let group = repo.get("group").and_then(Value::as_str).filter(|group| !group.is_empty());
+        let (Some(name), Some(group)) = (name, group) else {
+            continue;
+        };
+        
+        match groups.iter_mut().find(|(existing, _)| existing == group) {
+            Some((_, members)) => members.push(Value::from(name)),
+            None => groups.push((group.to_string(), vec![Value::from(name)])),
+        }
+    }
+    
+    let groups = groups
+        .into_iter()
+        .map(|(name, members)| {
+            serde_json::json!({
+                "name": name,
+                "description": "",
+                "repository_names": members,
+            })
+        })
+        .collect();
+    root.insert("groups".to_string(), Value::Array(groups));
+}
@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::config_migration::{migrate, CURRENT_CONFIG_VERSION};
+    use crate::core::repository::{AuthType, RepoConfig};
+    use serde_json::json;
+
+    #[test]
+    fn test_migrate_v0_config_without_groups() {
+        let v0 = json!({
+            "repositories": [
+                { "name": "github", "url": "https://github.com/user/repo.git" },
+                { "name": "gitlab", "url": "https://gitlab.com/user/repo.git", "group": "mirrors" },
+                { "name": "codeberg", "url": "https://codeberg.org/user/repo.git", "group": "mirrors" }
+            ]
+        });
+        
+        let config = migrate(v0).unwrap();
+        assert_eq!(config.version, CURRENT_CONFIG_VERSION);
+        assert_eq!(config.repositories.len(), 3);
+        assert_eq!(config.repositories[0].auth_type, AuthType::Default);
+        
+        // Groups are rebuilt from the group name on each repository
+        assert_eq!(config.groups.len(), 1);
+        assert_eq!(config.groups[0].name, "mirrors");
+        assert_eq!(config.groups[0].repository_names, vec!["gitlab".to_string(), "codeberg".to_string()]);
+        assert_eq!(config.get_repositories_in_group("mirrors").len(), 2);
+    }
+
+    #[test]
+    fn test_migrate_keeps_current_config() {
+        let mut config = RepoConfig::new();
+        config.timeout_secs = 10;
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(value["version"], json!(CURRENT_CONFIG_VERSION));
+        
+        let migrated = migrate(value).unwrap();
+        assert_eq!(migrated.version, CURRENT_CONFIG_VERSION);
+        assert_eq!(migrated.timeout_secs, 10);
+        assert!(migrated.groups.is_empty());
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_and_invalid_configs() {
+        let newer = json!({ "version": CURRENT_CONFIG_VERSION + 1, "repositories": [] });
+        assert!(migrate(newer).unwrap_err().to_string().contains("only understands up to version"));
+        
+        assert!(migrate(json!({ "version": "one", "repositories": [] })).is_err());
+        assert!(migrate(json!([])).is_err());
+    }
+}
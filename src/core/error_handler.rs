@@ -1,5 +1,8 @@
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use crate::core::repository::{RepositoryInfo};
+use crate::core::git_operations::MergeConflicts;
 
 #[derive(Debug, Clone)]
 pub struct GitOperationError {
@@ -49,11 +52,43 @@ impl GitOperationError {
     }
 }
 
+impl std::fmt::Display for GitOperationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.format_user_message())
+    }
+}
+
+impl std::error::Error for GitOperationError {}
+
 pub fn handle_git_error(operation: &str, repo_info: &RepositoryInfo, error: anyhow::Error) -> GitOperationError {
+    // Already classified, e.g. by `check_remote_reachable`
+    if let Some(error) = error.downcast_ref::<GitOperationError>() {
+        return error.clone();
+    }
+    
+    GitOperationError::new(operation, &repo_info.name, &error.to_string(), classify_error(&error))
+}
+
+/// What kind of failure `error` is. A `GitOperationError` keeps its type and a git2 error is classified
+/// by its code and class, the message is only searched for keywords when neither says.
+pub fn classify_error(error: &anyhow::Error) -> ErrorType {
+    if let Some(error) = error.downcast_ref::<GitOperationError>() {
+        return error.error_type.clone();
+    }
+    
     let error_message = error.to_string();
     
+    // libgit2 knows when it failed on the network or on credentials, even when its message doesn't say so
+    let git_error_type = error.downcast_ref::<git2::Error>().and_then(|e| match (e.code(), e.class()) {
+        (git2::ErrorCode::Auth, _) => Some(ErrorType::Authentication),
+        (_, git2::ErrorClass::Net) => Some(ErrorType::Network),
+        _ => None,
+    });
+    
     // Classify error based on error message content
-    let error_type = if error_message.contains("authentication") || 
+    if let Some(error_type) = git_error_type {
+        error_type
+    } else if error_message.contains("authentication") || 
                      error_message.contains("Authentication") || 
                      error_message.contains("401") || 
                      error_message.contains("403") ||
@@ -77,17 +112,167 @@ pub fn handle_git_error(operation: &str, repo_info: &RepositoryInfo, error: anyh
         ErrorType::Repository
     } else {
         ErrorType::Unknown
-    };
-    
-    GitOperationError::new(operation, &repo_info.name, &error_message, error_type)
+    }
 }
 
-pub fn format_error_result(operation: &str, repo_info: &RepositoryInfo, result: Result<()>) -> (String, String) {
+/// The result of an operation on a repository as (name, status, message). A `MergeConflicts` error is a conflict, any other error a failure.
+pub fn format_error_result(operation: &str, repo_info: &RepositoryInfo, result: Result<()>) -> (String, ResultStatus, String) {
     match result {
-        Ok(_) => (repo_info.name.clone(), "Success".to_string()),
+        Ok(_) => (repo_info.name.clone(), ResultStatus::Success, "Success".to_string()),
         Err(e) => {
+            let status = if e.is::<MergeConflicts>() { ResultStatus::Conflict } else { ResultStatus::Failed };
             let error = handle_git_error(operation, repo_info, e);
-            (repo_info.name.clone(), error.format_user_message())
+            (repo_info.name.clone(), status, error.format_user_message())
         }
     }
-}
\ No newline at end of file
+}
+
+/// How an operation on one repository ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultStatus {
+    Success,
+    Conflict, // Merge conflicts were found or left behind
+    Skipped, // Deliberately not touched, counts as neither a success nor a failure
+    Failed,
+}
+
+/// Result of checking the local repository for merge conflicts
+pub fn conflict_check_status(has_conflicts: bool) -> (ResultStatus, String) {
+    if has_conflicts {
+        (ResultStatus::Conflict, CONFLICTS_DETECTED.to_string())
+    } else {
+        (ResultStatus::Success, NO_CONFLICTS.to_string())
+    }
+}
+
+/// How often a git operation is retried after a network error
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay_ms: u64, // Doubled after every failed attempt
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay_ms: 500,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before the given retry (1 for the first retry)
+    pub fn delay_before_retry(&self, retry: u32) -> Duration {
+        let factor = 1u64.checked_shl(retry.saturating_sub(1)).unwrap_or(u64::MAX);
+        Duration::from_millis(self.base_delay_ms.saturating_mul(factor))
+    }
+}
+
+/// Run an operation, retrying it with exponential backoff while it fails with a network error.
+/// Authentication, permission and other errors are returned right away.
+/// Returns the final result together with the number of attempts made.
+pub fn retry_operation<T>(
+    policy: &RetryPolicy,
+    operation: &str,
+    repo_info: &RepositoryInfo,
+    mut run: impl FnMut() -> Result<T>,
+) -> (Result<T>, u32) {
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+    
+    loop {
+        let result = run();
+        
+        let retryable = match &result {
+            Ok(_) => false,
+            Err(e) => matches!(classify_error(e), ErrorType::Network),
+        };
+        
+        if !retryable || attempt >= max_attempts {
+            return (result, attempt);
+        }
+        
+        log::warn!("Network error while {} {}, retrying (attempt {} of {})", operation, repo_info.name, attempt + 1, max_attempts);
+        std::thread::sleep(policy.delay_before_retry(attempt));
+        attempt += 1;
+    }
+}
+
+/// Status of a repository left out of a batch operation because it is disabled
+pub const SKIPPED_DISABLED: &str = "Skipped (disabled)";
+
+/// Status of a repository that clone-all left alone because it is already cloned
+pub const ALREADY_CLONED: &str = "Already cloned";
+
+/// Status reported by a push when staging found nothing to commit
+pub const NO_CHANGES_TO_COMMIT: &str = "No changes to commit";
+
+/// Status reported by the conflict check when the index has conflicts
+pub const CONFLICTS_DETECTED: &str = "Conflicts detected";
+
+/// Status reported by the conflict check when the index is clean
+pub const NO_CONFLICTS: &str = "No conflicts";
+
+/// Start of the error of a pull that stopped on merge conflicts
+pub const MERGE_CONFLICTS_DETECTED: &str = "Merge conflicts detected";
+
+/// How many results of an operation ended which way
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ResultSummary {
+    pub succeeded: usize,
+    pub conflicts: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+impl ResultSummary {
+    pub fn total(&self) -> usize {
+        self.succeeded + self.conflicts + self.skipped + self.failed
+    }
+}
+
+impl std::fmt::Display for ResultSummary {
+    /// Like "✅ 4 succeeded · ⚠ 1 conflict · ❌ 2 failed", leaving out the counts that are zero
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if self.succeeded > 0 {
+            parts.push(format!("✅ {} succeeded", self.succeeded));
+        }
+        if self.conflicts > 0 {
+            parts.push(format!("⚠ {} conflict{}", self.conflicts, if self.conflicts == 1 { "" } else { "s" }));
+        }
+        if self.skipped > 0 {
+            parts.push(format!("⏭ {} skipped", self.skipped));
+        }
+        if self.failed > 0 {
+            parts.push(format!("❌ {} failed", self.failed));
+        }
+        write!(f, "{}", parts.join(" · "))
+    }
+}
+
+/// Count the results of an operation by their status
+pub fn summarize_results(results: &[(String, ResultStatus, String)]) -> ResultSummary {
+    let mut summary = ResultSummary::default();
+    for (_, status, _) in results {
+        match status {
+            ResultStatus::Success => summary.succeeded += 1,
+            ResultStatus::Conflict => summary.conflicts += 1,
+            ResultStatus::Skipped => summary.skipped += 1,
+            ResultStatus::Failed => summary.failed += 1,
+        }
+    }
+    summary
+}
+
+/// Like `format_error_result`, noting the attempts when the operation had to be retried
+pub fn format_retry_result(operation: &str, repo_info: &RepositoryInfo, result: Result<()>, attempts: u32) -> (String, ResultStatus, String) {
+    let (name, status, message) = format_error_result(operation, repo_info, result);
+    if attempts > 1 {
+        (name, status, format!("{} (after {} attempts)", message, attempts))
+    } else {
+        (name, status, message)
+    }
+}
@@ -1,8 +1,15 @@
 #[cfg(test)]
 mod tests {
-    use crate::core::error_handler::{GitOperationError, ErrorType, handle_git_error, format_error_result};
+    use crate::core::error_handler::{
+        GitOperationError, ErrorType, RetryPolicy,
+        handle_git_error, classify_error, format_error_result, format_retry_result, retry_operation,
+        summarize_results, conflict_check_status, ResultSummary, ResultStatus,
+        CONFLICTS_DETECTED, NO_CONFLICTS
+    };
+    use crate::core::git_operations::MergeConflicts;
     use crate::core::repository::RepositoryInfo;
     use anyhow::anyhow;
+    use std::time::Duration;
 
     #[test]
     fn test_git_operation_error_creation() {
@@ -117,20 +124,201 @@ mod tests {
         assert!(matches!(unknown_error.error_type, ErrorType::Unknown));
     }
 
+    #[test]
+    fn test_handle_git_error_uses_libgit2_error_class() {
+        let repo_info = RepositoryInfo::new("test-repo".to_string(), "https://github.com/user/repo.git".to_string());
+
+        // The message alone doesn't mention the network
+        let unresolved = git2::Error::new(git2::ErrorCode::GenericError, git2::ErrorClass::Net, "failed to resolve address for host.invalid");
+        let error = handle_git_error("fetching from", &repo_info, anyhow!(unresolved));
+        assert!(matches!(error.error_type, ErrorType::Network));
+
+        let rejected = git2::Error::new(git2::ErrorCode::Auth, git2::ErrorClass::Http, "too many redirects or authentication replays");
+        let error = handle_git_error("pushing to", &repo_info, anyhow!(rejected));
+        assert!(matches!(error.error_type, ErrorType::Authentication));
+
+        // An already classified error keeps its type
+        let classified = GitOperationError::new("checking", "test-repo", "refused", ErrorType::Network);
+        let error = handle_git_error("pushing to", &repo_info, anyhow::Error::new(classified));
+        assert!(matches!(error.error_type, ErrorType::Network));
+        assert_eq!(error.operation, "checking");
+    }
+
     #[test]
     fn test_format_error_result() {
         let repo_info = RepositoryInfo::new("test-repo".to_string(), "https://github.com/user/repo.git".to_string());
 
         // Test successful result
         let success_result = Ok(());
-        let (name, status) = format_error_result("pushing to", &repo_info, success_result);
+        let (name, status, message) = format_error_result("pushing to", &repo_info, success_result);
         assert_eq!(name, "test-repo");
-        assert_eq!(status, "Success");
+        assert_eq!(status, ResultStatus::Success);
+        assert_eq!(message, "Success");
 
         // Test error result
         let error_result = Err(anyhow!("Authentication failed"));
-        let (name, status) = format_error_result("pushing to", &repo_info, error_result);
+        let (name, status, message) = format_error_result("pushing to", &repo_info, error_result);
         assert_eq!(name, "test-repo");
-        assert_eq!(status, "Authentication failed for repository 'test-repo'. Please check your credentials.");
+        assert_eq!(status, ResultStatus::Failed);
+        assert_eq!(message, "Authentication failed for repository 'test-repo'. Please check your credentials.");
+    }
+
+    fn quick_retries(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy { max_attempts, base_delay_ms: 1 }
+    }
+
+    #[test]
+    fn test_retry_recovers_from_transient_network_errors() {
+        let repo_info = RepositoryInfo::new("test-repo".to_string(), "https://github.com/user/repo.git".to_string());
+        let mut calls = 0;
+        
+        let (result, attempts) = retry_operation(&quick_retries(3), "pushing to", &repo_info, || {
+            calls += 1;
+            if calls < 3 {
+                Err(anyhow!("Connection reset by peer"))
+            } else {
+                Ok(())
+            }
+        });
+        
+        assert!(result.is_ok());
+        assert_eq!(attempts, 3);
+        assert_eq!(calls, 3);
+        
+        let (_, status, message) = format_retry_result("pushing to", &repo_info, result, attempts);
+        assert_eq!(status, ResultStatus::Success);
+        assert_eq!(message, "Success (after 3 attempts)");
+    }
+
+    #[test]
+    fn test_retry_gives_up_after_max_attempts() {
+        let repo_info = RepositoryInfo::new("test-repo".to_string(), "https://github.com/user/repo.git".to_string());
+        let mut calls = 0;
+        
+        let (result, attempts) = retry_operation(&quick_retries(2), "fetching from", &repo_info, || -> anyhow::Result<()> {
+            calls += 1;
+            Err(anyhow!("Network is unreachable"))
+        });
+        
+        assert!(result.is_err());
+        assert_eq!((attempts, calls), (2, 2));
+    }
+
+    #[test]
+    fn test_retry_does_not_retry_authentication_errors() {
+        let repo_info = RepositoryInfo::new("test-repo".to_string(), "https://github.com/user/repo.git".to_string());
+        let mut calls = 0;
+        
+        let (result, attempts) = retry_operation(&quick_retries(5), "pushing to", &repo_info, || -> anyhow::Result<()> {
+            calls += 1;
+            Err(anyhow!("Authentication failed"))
+        });
+        
+        assert!(result.is_err());
+        assert_eq!((attempts, calls), (1, 1));
+        
+        let (_, status, message) = format_retry_result("pushing to", &repo_info, result, attempts);
+        assert_eq!(status, ResultStatus::Failed);
+        assert!(!message.contains("attempts"));
+    }
+
+    #[test]
+    fn test_retry_classifies_the_error_itself() {
+        let repo_info = RepositoryInfo::new("test-repo".to_string(), "https://github.com/user/repo.git".to_string());
+        
+        // A libgit2 network failure whose message has none of the keywords is still retried
+        let mut calls = 0;
+        let (_, attempts) = retry_operation(&quick_retries(2), "fetching from", &repo_info, || -> anyhow::Result<()> {
+            calls += 1;
+            Err(git2::Error::new(git2::ErrorCode::GenericError, git2::ErrorClass::Net, "unexpected EOF").into())
+        });
+        assert_eq!((attempts, calls), (2, 2));
+        
+        // A classified error keeps its type, whatever its message says
+        let mut calls = 0;
+        let (_, attempts) = retry_operation(&quick_retries(3), "pushing to", &repo_info, || -> anyhow::Result<()> {
+            calls += 1;
+            Err(GitOperationError::new("pushing to", "test-repo", "network.json is corrupt", ErrorType::Repository).into())
+        });
+        assert_eq!((attempts, calls), (1, 1));
+    }
+
+    #[test]
+    fn test_classify_error_prefers_the_git_error_class() {
+        let error = anyhow::Error::from(git2::Error::new(git2::ErrorCode::Auth, git2::ErrorClass::Http, "request failed"));
+        assert!(matches!(classify_error(&error), ErrorType::Authentication));
+        assert!(matches!(classify_error(&anyhow!("Connection refused")), ErrorType::Network));
+        assert!(matches!(classify_error(&anyhow!("something else")), ErrorType::Unknown));
+    }
+
+    #[test]
+    fn test_retry_delay_doubles() {
+        let policy = RetryPolicy { max_attempts: 4, base_delay_ms: 100 };
+        assert_eq!(policy.delay_before_retry(1), Duration::from_millis(100));
+        assert_eq!(policy.delay_before_retry(2), Duration::from_millis(200));
+        assert_eq!(policy.delay_before_retry(3), Duration::from_millis(400));
+    }
+
+    fn results(statuses: &[ResultStatus]) -> Vec<(String, ResultStatus, String)> {
+        statuses.iter().enumerate().map(|(i, status)| (format!("repo{}", i + 1), *status, String::new())).collect()
+    }
+
+    #[test]
+    fn test_summarize_results_counts_each_outcome() {
+        use ResultStatus::*;
+        let summary = summarize_results(&results(&[
+            Success, Success, Success, Success,
+            Conflict, Conflict,
+            Skipped, Skipped, Skipped,
+            Failed, Failed,
+        ]));
+        
+        assert_eq!(summary, ResultSummary { succeeded: 4, conflicts: 2, skipped: 3, failed: 2 });
+        assert_eq!(summary.total(), 11);
+        assert_eq!(summary.to_string(), "✅ 4 succeeded · ⚠ 2 conflicts · ⏭ 3 skipped · ❌ 2 failed");
+    }
+
+    #[test]
+    fn test_summary_leaves_out_zero_counts() {
+        use ResultStatus::*;
+        assert_eq!(summarize_results(&[]), ResultSummary::default());
+        assert_eq!(summarize_results(&[]).to_string(), "");
+        
+        let summary = summarize_results(&results(&[Success, Success, Success, Success, Conflict, Failed, Failed]));
+        assert_eq!(summary.to_string(), "✅ 4 succeeded · ⚠ 1 conflict · ❌ 2 failed");
+        
+        let summary = summarize_results(&results(&[Success]));
+        assert_eq!(summary.to_string(), "✅ 1 succeeded");
+    }
+
+    #[test]
+    fn test_each_git_outcome_maps_to_a_result_status() {
+        let repo_info = RepositoryInfo::new("web".to_string(), "https://github.com/user/web.git".to_string());
+        let status_of = |result: anyhow::Result<()>| format_error_result("pulling from", &repo_info, result).1;
+        
+        assert_eq!(status_of(Ok(())), ResultStatus::Success);
+        assert_eq!(status_of(Err(MergeConflicts { paths: vec!["src/main.rs".to_string()] }.into())), ResultStatus::Conflict);
+        assert_eq!(status_of(Err(MergeConflicts { paths: Vec::new() }.into())), ResultStatus::Conflict);
+        assert_eq!(status_of(Err(anyhow!("connection refused"))), ResultStatus::Failed);
+        assert_eq!(status_of(Err(anyhow!("authentication required"))), ResultStatus::Failed);
+        // Only the typed error is a conflict, not a message that mentions one
+        assert_eq!(status_of(Err(anyhow!("Merge conflicts detected in: src/main.rs"))), ResultStatus::Failed);
+        
+        let (name, status, message) = format_error_result("pulling from", &repo_info, Err(MergeConflicts { paths: vec!["a.txt".to_string()] }.into()));
+        assert_eq!((name, status), ("web".to_string(), ResultStatus::Conflict));
+        assert_eq!(message, "Error while pulling from repository 'web': Merge conflicts detected in: a.txt");
+        
+        // Retries keep the status and only add to the message
+        let (_, status, message) = format_retry_result("pulling from", &repo_info, Ok(()), 2);
+        assert_eq!((status, message.as_str()), (ResultStatus::Success, "Success (after 2 attempts)"));
+        
+        assert_eq!(conflict_check_status(true), (ResultStatus::Conflict, CONFLICTS_DETECTED.to_string()));
+        assert_eq!(conflict_check_status(false), (ResultStatus::Success, NO_CONFLICTS.to_string()));
+    }
+
+    #[test]
+    fn test_result_status_serializes_lowercase() {
+        assert_eq!(serde_json::to_string(&ResultStatus::Conflict).unwrap(), r#""conflict""#);
+        assert_eq!(serde_json::from_str::<ResultStatus>(r#""skipped""#).unwrap(), ResultStatus::Skipped);
     }
-}
\ No newline at end of file
+}
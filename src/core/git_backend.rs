@@ -0,0 +1,135 @@
+//! The local git work behind the group batch operations
+//!
+//! Batch operations go through `GitBackend` instead of calling git2 directly, so tests can
+//! hand them a mock instead of a real repository and network.
+
+use crate::core::git_operations::{
+    add_all_changes,
+    commit_changes_with_options,
+    fetch_from_remote_with_progress,
+    find_or_create_remote,
+    has_lfs,
+    pull_from_remote_with_network,
+    push_to_remote_with_progress,
+    stash_pop,
+    stash_save,
+    CommitOptions,
+    NetworkOptions,
+    TransferProgress,
+};
+use crate::core::repository::RepositoryInfo;
+use anyhow::Result;
+use git2::Repository;
+use std::path::{Path, PathBuf};
+
+/// The operations on the local repository that batch operations are built from
+pub trait GitBackend: Sync {
+    /// Stage every change in the working tree
+    fn add_all(&self) -> Result<()>;
+    
+    /// Commit the staged changes. Fails with `NothingToCommit` when the tree is unchanged.
+    fn commit(&self, message: &str, options: &CommitOptions) -> Result<()>;
+    
+    /// Add the remote of a repository to the local repository when it is missing.
+    /// Parallel batch operations call it for every repository first, adding remotes concurrently races on `.git/config`.
+    fn prepare_remote(&self, repo_info: &RepositoryInfo) -> Result<()>;
+    
+    /// Push `branch` to a remote repository
+    fn push(
+        &self,
+        repo_info: &RepositoryInfo,
+        branch: &str,
+        network_options: &NetworkOptions,
+        progress: &dyn Fn(TransferProgress),
+    ) -> Result<()>;
+    
+    /// Fetch `branch` from a remote repository and merge it into the working tree
+    fn pull(&self, repo_info: &RepositoryInfo, branch: &str, network_options: &NetworkOptions) -> Result<()>;
+    
+    /// Fetch `branch` from a remote repository
+    fn fetch(
+        &self,
+        repo_info: &RepositoryInfo,
+        branch: &str,
+        network_options: &NetworkOptions,
+        progress: &dyn Fn(TransferProgress),
+    ) -> Result<()>;
+    
+    /// Stash the local changes, including untracked files. Returns whether there was anything to stash.
+    fn stash(&self) -> Result<bool>;
+    
+    /// Apply the most recent stash to the working tree and drop it
+    fn unstash(&self) -> Result<()>;
+    
+    /// Whether the repository stores files with Git LFS
+    fn uses_lfs(&self) -> bool;
+}
+
+/// The real backend, working on a repository on disk with git2.
+/// git2 handles can't be shared between threads, so every call opens its own.
+pub struct LocalBackend {
+    path: PathBuf,
+}
+
+impl LocalBackend {
+    /// Use the repository at `path`, failing if there is none
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let repo = Repository::open(path.as_ref())?;
+        let path = repo.workdir().unwrap_or(repo.path()).to_path_buf();
+        Ok(Self { path })
+    }
+    
+    fn repo(&self) -> Result<Repository> {
+        Ok(Repository::open(&self.path)?)
+    }
+}
+
+impl GitBackend for LocalBackend {
+    fn add_all(&self) -> Result<()> {
+        add_all_changes(&self.repo()?)
+    }
+    
+    fn commit(&self, message: &str, options: &CommitOptions) -> Result<()> {
+        commit_changes_with_options(&self.repo()?, message, options).map(|_| ())
+    }
+    
+    fn prepare_remote(&self, repo_info: &RepositoryInfo) -> Result<()> {
+        find_or_create_remote(&self.repo()?, repo_info).map(|_| ())
+    }
+    
+    fn push(
+        &self,
+        repo_info: &RepositoryInfo,
+        branch: &str,
+        network_options: &NetworkOptions,
+        progress: &dyn Fn(TransferProgress),
+    ) -> Result<()> {
+        push_to_remote_with_progress(&self.repo()?, repo_info, branch, network_options, progress)
+    }
+    
+    fn pull(&self, repo_info: &RepositoryInfo, branch: &str, network_options: &NetworkOptions) -> Result<()> {
+        pull_from_remote_with_network(&self.repo()?, repo_info, branch, network_options)
+    }
+    
+    fn fetch(
+        &self,
+        repo_info: &RepositoryInfo,
+        branch: &str,
+        network_options: &NetworkOptions,
+        progress: &dyn Fn(TransferProgress),
+    ) -> Result<()> {
+        fetch_from_remote_with_progress(&self.repo()?, repo_info, branch, network_options, progress)
+    }
+    
+    fn stash(&self) -> Result<bool> {
+        Ok(stash_save(&mut self.repo()?, "Auto stash before pull")?.is_some())
+    }
+    
+    fn unstash(&self) -> Result<()> {
+        stash_pop(&mut self.repo()?)
+    }
+    
+    fn uses_lfs(&self) -> bool {
+        self.repo().is_ok_and(|repo| has_lfs(&repo))
+    }
+}
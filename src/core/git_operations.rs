@@ -1,8 +1,16 @@
 use crate::core::repository::{RepositoryInfo, RepoConfig, AuthType};
-use crate::core::error_handler::{format_error_result, handle_git_error};
+use crate::core::provider::provider_for_url;
+use crate::core::parallel::for_each_bounded;
+use crate::core::error_handler::{format_error_result, format_retry_result, handle_git_error, retry_operation, ResultStatus, ALREADY_CLONED, MERGE_CONFLICTS_DETECTED, NO_CHANGES_TO_COMMIT, SKIPPED_DISABLED};
 use git2::Repository;
 use anyhow::Result;
+use log::{error, info, warn};
+use std::collections::HashMap;
+use std::io::Write;
 use std::path::Path;
+use std::process::{Command, Stdio};
+use std::cell::Cell;
+use std::time::{Duration, Instant};
 
 pub fn add_all_changes(repo: &Repository) -> Result<()> {
     let mut index = repo.index()?;
@@ -11,53 +19,596 @@ pub fn add_all_changes(repo: &Repository) -> Result<()> {
     Ok(())
 }
 
+/// The paths `add_all_changes` would stage and commit, gitignored files left out.
+/// The staging is done in memory and thrown away, the index on disk is not touched.
+pub fn preview_staged_changes(repo: &Repository) -> Result<Vec<String>> {
+    let mut index = repo.index()?;
+    let staged = (|| -> Result<Vec<String>> {
+        index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)?;
+        // An unborn HEAD has no tree, everything in the index is new
+        let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+        let diff = repo.diff_tree_to_index(head_tree.as_ref(), Some(&index), None)?;
+        Ok(diff
+            .deltas()
+            .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+            .map(|path| path.to_string_lossy().to_string())
+            .collect())
+    })();
+    
+    // The repository shares this index, reload it so later staging starts from the file on disk
+    index.read(true)?;
+    staged
+}
+
+/// Stage only the given paths (relative to the working directory).
+/// Paths that no longer exist on disk are staged as deletions.
+pub fn add_paths(repo: &Repository, paths: &[String]) -> Result<()> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| anyhow::anyhow!("Cannot stage files in a bare repository"))?;
+    let mut index = repo.index()?;
+    
+    for path in paths {
+        let relative = Path::new(path);
+        if workdir.join(relative).exists() {
+            index.add_path(relative)?;
+        } else {
+            index.remove_path(relative)?;
+        }
+    }
+    
+    index.write()?;
+    Ok(())
+}
+
+/// Kind of uncommitted change reported for a file in the working directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkingChangeStatus {
+    Staged,
+    Modified,
+    Untracked,
+    Deleted,
+}
+
+/// A file with uncommitted changes
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileStatus {
+    pub path: String,
+    pub status: WorkingChangeStatus,
+}
+
+impl WorkingChangeStatus {
+    fn from_git_status(status: git2::Status) -> Self {
+        if status.intersects(git2::Status::WT_DELETED | git2::Status::INDEX_DELETED) {
+            WorkingChangeStatus::Deleted
+        } else if status.contains(git2::Status::WT_NEW) {
+            WorkingChangeStatus::Untracked
+        } else if status.intersects(
+            git2::Status::WT_MODIFIED | git2::Status::WT_RENAMED | git2::Status::WT_TYPECHANGE,
+        ) {
+            WorkingChangeStatus::Modified
+        } else {
+            WorkingChangeStatus::Staged
+        }
+    }
+}
+
+/// List the files with uncommitted changes (staged, modified, deleted or untracked)
+pub fn get_working_changes(repo: &Repository) -> Result<Vec<FileStatus>> {
+    let mut status_options = git2::StatusOptions::new();
+    status_options.include_untracked(true).recurse_untracked_dirs(true);
+    
+    let statuses = repo.statuses(Some(&mut status_options))?;
+    let changes = statuses
+        .iter()
+        .filter(|entry| !entry.status().contains(git2::Status::IGNORED))
+        .filter_map(|entry| {
+            entry.path().map(|path| FileStatus {
+                path: path.to_string(),
+                status: WorkingChangeStatus::from_git_status(entry.status()),
+            })
+        })
+        .collect();
+    
+    Ok(changes)
+}
+
+/// Options controlling how `commit_changes_with_options` creates a commit and what the push after it sends
+#[derive(Debug, Clone, Default)]
+pub struct CommitOptions {
+    /// GPG key id to sign the commit with. `None` or an empty id creates an unsigned commit.
+    pub signing_key: Option<String>,
+    /// Author/committer name overriding the git config identity
+    pub author_name: Option<String>,
+    /// Author/committer email overriding the git config identity
+    pub author_email: Option<String>,
+    /// Stage only these paths before committing. `None` stages everything.
+    pub paths: Option<Vec<String>>,
+    /// Reject commit messages that don't follow the conventional-commit format
+    pub enforce_conventional_commits: bool,
+    /// Replace the current HEAD commit instead of creating a new one on top of it
+    pub amend: bool,
+    /// Also push every local tag
+    pub include_tags: bool,
+    /// Push every local branch instead of only the committed one
+    pub all_branches: bool,
+    /// Create the commit even when it changes nothing
+    pub allow_empty: bool,
+    /// Branch to push per repository name, overriding the branch of the whole push
+    pub branch_overrides: HashMap<String, String>,
+}
+
+impl CommitOptions {
+    /// Build commit options from the settings stored in the configuration
+    pub fn from_config(config: &RepoConfig) -> Self {
+        Self {
+            signing_key: config.signing_key.clone(),
+            enforce_conventional_commits: config.enforce_conventional_commits,
+            ..Default::default()
+        }
+    }
+    
+    /// Branch pushed to a repository: its override, otherwise `branch` or the repository default
+    pub fn branch_for<'a>(&'a self, repo_info: &'a RepositoryInfo, branch: &'a str) -> &'a str {
+        match self.branch_overrides.get(&repo_info.name) {
+            Some(branch) => branch,
+            None => repo_info.branch_or_default(branch),
+        }
+    }
+    
+    /// Commit with the identity configured for the given account, if it has one
+    pub fn with_identity_of(mut self, repo_info: &RepositoryInfo) -> Self {
+        self.author_name = repo_info.author_name.clone();
+        self.author_email = repo_info.author_email.clone();
+        self
+    }
+}
+
+/// Network settings of remote operations. The proxy applies to all of them,
+/// the timeout only to push, pull and fetch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkOptions {
+    /// Abort a transfer that has made no progress for this many seconds
+    pub timeout_secs: u64,
+    /// HTTP(S) proxy to connect through. `None` falls back to `HTTPS_PROXY`, then to git's auto-detection.
+    pub proxy_url: Option<String>,
+}
+
+impl Default for NetworkOptions {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 30,
+            proxy_url: None,
+        }
+    }
+}
+
+/// Proxy used for a remote operation
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProxySetting {
+    Url(String),
+    /// Let libgit2 detect the proxy from the git configuration
+    Auto,
+}
+
+/// Pick the proxy: the configured URL wins over the `HTTPS_PROXY` value, blank values are ignored
+pub fn resolve_proxy(configured: Option<&str>, https_proxy_env: Option<&str>) -> ProxySetting {
+    let non_blank = |url: Option<&str>| url.map(str::trim).filter(|url| !url.is_empty()).map(str::to_string);
+    
+    non_blank(configured)
+        .or_else(|| non_blank(https_proxy_env))
+        .map(ProxySetting::Url)
+        .unwrap_or(ProxySetting::Auto)
+}
+
+impl NetworkOptions {
+    /// Build network options from the settings stored in the configuration
+    pub fn from_config(config: &RepoConfig) -> Self {
+        Self {
+            timeout_secs: config.timeout_secs,
+            proxy_url: config.proxy_url.clone(),
+        }
+    }
+    
+    /// The proxy to use, taking the `HTTPS_PROXY` environment variable into account
+    pub fn proxy(&self) -> ProxySetting {
+        let https_proxy = std::env::var("HTTPS_PROXY").or_else(|_| std::env::var("https_proxy")).ok();
+        resolve_proxy(self.proxy_url.as_deref(), https_proxy.as_deref())
+    }
+    
+    pub fn proxy_options(&self) -> git2::ProxyOptions<'static> {
+        let mut proxy_options = git2::ProxyOptions::new();
+        match self.proxy() {
+            ProxySetting::Url(url) => proxy_options.url(&url),
+            ProxySetting::Auto => proxy_options.auto(),
+        };
+        proxy_options
+    }
+    
+    /// Start the clock for a single transfer
+    pub fn start_transfer(&self) -> TransferDeadline {
+        TransferDeadline {
+            last_activity: Cell::new(Instant::now()),
+            limit: Duration::from_secs(self.timeout_secs),
+        }
+    }
+}
+
+/// Idle limit of a running transfer, checked from the git2 progress callbacks.
+/// Every callback counts as activity, so only a transfer that stops making progress expires.
+#[derive(Debug, Clone)]
+pub struct TransferDeadline {
+    last_activity: Cell<Instant>,
+    limit: Duration,
+}
+
+impl TransferDeadline {
+    pub fn limit(&self) -> Duration {
+        self.limit
+    }
+    
+    /// Note that the transfer is still making progress
+    pub fn record_activity(&self) {
+        self.last_activity.set(Instant::now());
+    }
+    
+    /// True once nothing has happened for the whole limit
+    pub fn expired(&self) -> bool {
+        self.last_activity.get().elapsed() >= self.limit
+    }
+}
+
+/// How far a push or fetch has got, as reported by the git2 progress callbacks
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TransferProgress {
+    pub objects_done: usize,
+    pub total_objects: usize,
+    pub bytes: usize,
+}
+
+impl TransferProgress {
+    /// Share of the objects transferred so far, from 0.0 to 1.0
+    pub fn fraction(&self) -> f32 {
+        if self.total_objects == 0 {
+            return 0.0;
+        }
+        self.objects_done.min(self.total_objects) as f32 / self.total_objects as f32
+    }
+}
+
+impl std::fmt::Display for TransferProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.0}% ({}/{} objects)", self.fraction() * 100.0, self.objects_done, self.total_objects)
+    }
+}
+
+// Hand transfer progress to `progress` and abort the transfer once it has been idle for the deadline's limit.
+// Each callback first checks how long it has been since the previous one, then counts as activity itself.
+fn watch_transfer<'a>(
+    callbacks: &mut git2::RemoteCallbacks<'a>,
+    deadline: &'a TransferDeadline,
+    progress: &'a dyn Fn(TransferProgress),
+) {
+    let still_active = move || {
+        let expired = deadline.expired();
+        deadline.record_activity();
+        !expired
+    };
+    callbacks.transfer_progress(move |stats| {
+        progress(TransferProgress {
+            objects_done: stats.received_objects(),
+            total_objects: stats.total_objects(),
+            bytes: stats.received_bytes(),
+        });
+        still_active()
+    });
+    callbacks.push_transfer_progress(move |objects_done, total_objects, bytes| {
+        deadline.record_activity();
+        progress(TransferProgress { objects_done, total_objects, bytes });
+    });
+    callbacks.sideband_progress(move |_| still_active());
+    callbacks.push_negotiation(move |_| {
+        if still_active() {
+            Ok(())
+        } else {
+            Err(git2::Error::from_str("transfer deadline exceeded"))
+        }
+    });
+}
+
+// Turn a failed transfer into a user-facing error. Aborts caused by the deadline are
+// reported as timeouts so that `handle_git_error` classifies them as network errors.
+fn transfer_error(operation: &str, repo_info: &RepositoryInfo, deadline: &TransferDeadline, error: git2::Error) -> anyhow::Error {
+    let error = if deadline.expired() {
+        anyhow::anyhow!("Network timeout, no progress for {} seconds: {}", deadline.limit().as_secs(), error)
+    } else {
+        anyhow::anyhow!(error)
+    };
+    // Keep the classified error so a retry can tell a network failure from the rest
+    handle_git_error(operation, repo_info, error).into()
+}
+
+// Build the commit signature, preferring the overridden identity over the git config
+fn commit_signature(repo: &Repository, options: &CommitOptions) -> Result<git2::Signature<'static>> {
+    let name = options.author_name.as_deref().map(str::trim).filter(|name| !name.is_empty());
+    let email = options.author_email.as_deref().map(str::trim).filter(|email| !email.is_empty());
+    
+    match (name, email) {
+        (None, None) => Ok(repo.signature()?),
+        (Some(name), Some(email)) => Ok(git2::Signature::now(name, email)?),
+        (name, email) => {
+            // Only part of the identity was overridden, take the rest from git config
+            let default = repo.signature()?;
+            let name = name.unwrap_or(default.name().unwrap_or_default()).to_string();
+            let email = email.unwrap_or(default.email().unwrap_or_default()).to_string();
+            Ok(git2::Signature::now(&name, &email)?)
+        }
+    }
+}
+
+/// Commit types accepted when conventional commits are enforced
+pub const CONVENTIONAL_COMMIT_TYPES: [&str; 5] = ["feat", "fix", "docs", "chore", "refactor"];
+
+/// Check that the first line of a message looks like `type(scope): description`,
+/// where the scope is optional and the type is one of `CONVENTIONAL_COMMIT_TYPES`
+pub fn is_conventional_commit(message: &str) -> bool {
+    let header = message.lines().next().unwrap_or_default();
+    
+    let Some(rest) = CONVENTIONAL_COMMIT_TYPES
+        .iter()
+        .find_map(|commit_type| header.strip_prefix(commit_type))
+    else {
+        return false;
+    };
+    
+    match rest.strip_prefix('(') {
+        // The scope must be non-empty and followed by a non-empty description
+        Some(scoped) => scoped
+            .match_indices("): ")
+            .any(|(index, separator)| index > 0 && scoped.len() > index + separator.len()),
+        None => rest.strip_prefix(": ").is_some_and(|description| !description.is_empty()),
+    }
+}
+
+/// Validate a commit message against the options before anything is staged or committed
+pub fn validate_commit_message(message: &str, options: &CommitOptions) -> Result<()> {
+    if options.enforce_conventional_commits && !is_conventional_commit(message) {
+        return Err(anyhow::anyhow!(
+            "Commit message \"{}\" does not follow the conventional-commit format \"<type>(<scope>): <description>\" (allowed types: {})",
+            message.lines().next().unwrap_or_default(),
+            CONVENTIONAL_COMMIT_TYPES.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// A commit was refused because its tree is the same as its parent's.
+/// Callers can `downcast_ref` an `anyhow::Error` to this to tell it apart from other failures.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NothingToCommit;
+
+impl std::fmt::Display for NothingToCommit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", NO_CHANGES_TO_COMMIT)
+    }
+}
+
+impl std::error::Error for NothingToCommit {}
+
+/// A pull merged but left conflicts in the working tree, in `paths` when they could be listed.
+/// `format_error_result` reports it as a conflict instead of a failure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflicts {
+    pub paths: Vec<String>,
+}
+
+impl std::fmt::Display for MergeConflicts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.paths.is_empty() {
+            write!(f, "{}", MERGE_CONFLICTS_DETECTED)
+        } else {
+            write!(f, "{} in: {}", MERGE_CONFLICTS_DETECTED, self.paths.join(", "))
+        }
+    }
+}
+
+impl std::error::Error for MergeConflicts {}
+
 pub fn commit_changes(repo: &Repository, message: &str) -> Result<git2::Oid> {
-    let signature = repo.signature()?;
+    commit_changes_with_options(repo, message, &CommitOptions::default())
+}
+
+pub fn commit_changes_with_options(repo: &Repository, message: &str, options: &CommitOptions) -> Result<git2::Oid> {
+    validate_commit_message(message, options)?;
+    
+    let signature = commit_signature(repo, options)?;
     let mut index = repo.index()?;
     let tree_id = index.write_tree()?;
     let tree = repo.find_tree(tree_id)?;
     
-    let parent_commit = if let Ok(head) = repo.head() {
+    let head_commit = if let Ok(head) = repo.head() {
         head.target().map(|target| repo.find_commit(target)).transpose()?
     } else {
         None
     };
     
-    let commit_oid = if let Some(parent) = parent_commit {
-        repo.commit(
-            Some("HEAD"),
-            &signature,
-            &signature,
-            message,
-            &tree,
-            &[&parent],
-        )?
-    } else {
-        repo.commit(
-            Some("HEAD"),
-            &signature,
-            &signature,
-            message,
-            &tree,
-            &[],
-        )?
+    if options.amend && head_commit.is_none() {
+        return Err(anyhow::anyhow!(
+            "Cannot amend: the repository has no commits yet. Create a regular commit first."
+        ));
+    }
+    
+    // Amending only to reword the message keeps the tree, so that is never empty
+    if !options.amend && !options.allow_empty && head_commit.as_ref().is_some_and(|head| head.tree_id() == tree_id) {
+        return Err(NothingToCommit.into());
+    }
+    
+    // An amended commit keeps the original author and sits on the parents of the commit it replaces
+    let (author, parent_commits): (git2::Signature<'static>, Vec<git2::Commit>) = match &head_commit {
+        Some(head) if options.amend => (head.author().to_owned(), head.parents().collect()),
+        _ => (signature.clone(), head_commit.iter().cloned().collect()),
     };
+    let parents: Vec<&git2::Commit> = parent_commits.iter().collect();
     
+    let signing_key = configured_signing_key(options);
+    
+    let commit_oid = match &head_commit {
+        Some(head) if options.amend && signing_key.is_none() => {
+            head.amend(Some("HEAD"), None, Some(&signature), None, Some(message), Some(&tree))?
+        },
+        _ => write_commit(repo, &author, &signature, message, &tree, &parents, signing_key)?,
+    };
+    
+    info!("Created commit {}{}", commit_oid, if options.amend { " (amended)" } else { "" });
     Ok(commit_oid)
 }
 
-pub fn push_to_remote(repo: &Repository, repo_info: &RepositoryInfo, branch: &str) -> Result<()> {
-    // Try to find existing remote or create new one
-    let mut remote = match repo.find_remote(&repo_info.name) {
-        Ok(remote) => remote,
-        Err(_) => {
-            repo.remote(&repo_info.name, &repo_info.url)?
-        }
+// The GPG key commits are signed with, `None` when signing is off
+fn configured_signing_key(options: &CommitOptions) -> Option<&str> {
+    options.signing_key.as_deref().map(str::trim).filter(|key| !key.is_empty())
+}
+
+// Create a commit on `parents` and move HEAD to it, signed when there is a key
+fn write_commit(
+    repo: &Repository,
+    author: &git2::Signature,
+    committer: &git2::Signature,
+    message: &str,
+    tree: &git2::Tree,
+    parents: &[&git2::Commit],
+    signing_key: Option<&str>,
+) -> Result<git2::Oid> {
+    let Some(key) = signing_key else {
+        return Ok(repo.commit(Some("HEAD"), author, committer, message, tree, parents)?);
     };
     
-    // Configure callbacks for authentication based on auth type
-    let mut callbacks = git2::RemoteCallbacks::new();
+    // Build the raw commit, sign it and then move HEAD to it ourselves
+    let buffer = repo.commit_create_buffer(author, committer, message, tree, parents)?;
+    let content = buffer
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Commit content is not valid UTF-8"))?
+        .to_string();
+    let gpg_signature = sign_with_gpg(&content, key)?;
+    let oid = repo.commit_signed(&content, &gpg_signature, None)?;
+    update_head(repo, oid, message)?;
+    Ok(oid)
+}
+
+/// Log the outcome of a git operation on one repository
+pub(crate) fn log_result<T>(operation: &str, repo_info: &RepositoryInfo, result: &Result<T>) {
+    match result {
+        Ok(_) => info!("{} {}: success", operation, repo_info.name),
+        Err(e) => error!("{} {} failed: {}", operation, repo_info.name, e),
+    }
+}
+
+// Create a detached, ASCII-armored signature for a commit buffer using the gpg binary
+fn sign_with_gpg(content: &str, key_id: &str) -> Result<String> {
+    let mut child = Command::new("gpg")
+        .args(["--detach-sign", "--armor", "--local-user", key_id])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to run gpg: {}", e))?;
+    
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(content.as_bytes())?;
+    }
     
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "gpg failed to sign the commit with key '{}': {}",
+            key_id,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+// Point HEAD (or the branch it refers to) at a newly created commit
+fn update_head(repo: &Repository, oid: git2::Oid, message: &str) -> Result<()> {
+    let head = repo.find_reference("HEAD")?;
+    match head.symbolic_target().map(|target| target.to_string()) {
+        Some(target) => {
+            repo.reference(&target, oid, true, message)?;
+        },
+        None => {
+            repo.set_head_detached(oid)?;
+        }
+    }
+    Ok(())
+}
+
+/// Username and password handed out by git's credential helper
+#[derive(Debug, Clone, PartialEq)]
+pub struct HelperCredentials {
+    pub username: String,
+    pub password: String,
+}
+
+/// Parse the `key=value` lines printed by `git credential fill`.
+/// Returns None unless the helper supplied both a username and a password.
+pub fn parse_credential_helper_output(output: &str) -> Option<HelperCredentials> {
+    let mut username = None;
+    let mut password = None;
+    
+    for line in output.lines() {
+        // Values may themselves contain '=', only the first one separates the key
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key.trim() {
+            "username" => username = Some(value.to_string()),
+            "password" => password = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    
+    match (username, password) {
+        (Some(username), Some(password)) if !password.is_empty() => Some(HelperCredentials { username, password }),
+        _ => None,
+    }
+}
+
+// Ask git's configured credential helper for the credentials of `url`
+fn credential_helper_fill(url: &str) -> Result<HelperCredentials> {
+    let mut child = Command::new("git")
+        .args(["credential", "fill"])
+        // Fail instead of prompting on a terminal the GUI doesn't own
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to run git credential fill: {}", e))?;
+    
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(format!("url={}\n\n", url).as_bytes())?;
+    }
+    
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "No credentials for {} from git credential helper: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    
+    parse_credential_helper_output(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| anyhow::anyhow!("No credentials for {} from git credential helper", url))
+}
+
+// Key used when an account doesn't configure one
+fn default_ssh_key_path() -> String {
+    format!("{}/.ssh/id_rsa", std::env::var("HOME").unwrap_or_default())
+}
+
+// Answer credential requests the way the account is configured to authenticate
+fn configure_credentials<'a>(callbacks: &mut git2::RemoteCallbacks<'a>, repo_info: &'a RepositoryInfo) {
     match &repo_info.auth_type {
         AuthType::SSH => {
             callbacks.credentials(|_url, username_from_url, _allowed_types| {
@@ -66,10 +617,44 @@ pub fn push_to_remote(repo: &Repository, repo_info: &RepositoryInfo, branch: &st
                     username,
                     None,
                     Path::new(&repo_info.ssh_key_path),
-                    None,
+                    repo_info.ssh_passphrase.as_deref(),
                 )
             });
         },
+        AuthType::SshAgent => {
+            // libgit2 asks again after a rejected credential, so the agent gets the first
+            // attempt and the default key file is used when it has no matching identity
+            let mut agent_tried = false;
+            callbacks.credentials(move |_url, username_from_url, _allowed_types| {
+                let username = username_from_url.unwrap_or("git");
+                let from_key_file = || git2::Cred::ssh_key(
+                    username,
+                    None,
+                    Path::new(&default_ssh_key_path()),
+                    repo_info.ssh_passphrase.as_deref(),
+                );
+                
+                if agent_tried {
+                    return from_key_file();
+                }
+                agent_tried = true;
+                git2::Cred::ssh_key_from_agent(username).or_else(|_| from_key_file())
+            });
+        },
+        AuthType::CredentialHelper => {
+            // Rejected helper credentials would otherwise be offered again forever
+            let mut helper_tried = false;
+            callbacks.credentials(move |url, _username_from_url, _allowed_types| {
+                if helper_tried {
+                    return Err(git2::Error::from_str("Credentials from git credential helper were rejected"));
+                }
+                helper_tried = true;
+                
+                let credentials = credential_helper_fill(url)
+                    .map_err(|e| git2::Error::from_str(&e.to_string()))?;
+                git2::Cred::userpass_plaintext(&credentials.username, &credentials.password)
+            });
+        },
         AuthType::Token => {
             callbacks.credentials(|_url, _username_from_url, _allowed_types| {
                 // For token-based auth, we typically use username/password with token as password
@@ -82,7 +667,7 @@ pub fn push_to_remote(repo: &Repository, repo_info: &RepositoryInfo, branch: &st
                     git2::Cred::ssh_key(
                         username,
                         None,
-                        Path::new(&format!("{}/.ssh/id_rsa", std::env::var("HOME").unwrap_or_default())),
+                        Path::new(&default_ssh_key_path()),
                         None,
                     )
                 } else {
@@ -92,74 +677,272 @@ pub fn push_to_remote(repo: &Repository, repo_info: &RepositoryInfo, branch: &st
             });
         }
     }
-    
-    // Push to remote
-    let mut push_options = git2::PushOptions::new();
-    push_options.remote_callbacks(callbacks);
-    
+}
+
+pub fn push_to_remote(repo: &Repository, repo_info: &RepositoryInfo, branch: &str) -> Result<()> {
+    push_to_remote_with_network(repo, repo_info, branch, &NetworkOptions::default())
+}
+
+pub fn push_to_remote_with_network(
+    repo: &Repository,
+    repo_info: &RepositoryInfo,
+    branch: &str,
+    network_options: &NetworkOptions,
+) -> Result<()> {
+    push_to_remote_with_progress(repo, repo_info, branch, network_options, &|_| {})
+}
+
+/// Push `branch`, handing transfer progress to `progress` while the pack is sent
+pub fn push_to_remote_with_progress(
+    repo: &Repository,
+    repo_info: &RepositoryInfo,
+    branch: &str,
+    network_options: &NetworkOptions,
+    progress: &dyn Fn(TransferProgress),
+) -> Result<()> {
+    let branch = repo_info.branch_or_default(branch);
     let refspec = format!("refs/heads/{}:refs/heads/{}", branch, branch);
-    remote.push(&[&refspec], Some(&mut push_options)).map_err(|e| {
-        let error = handle_git_error("pushing to", repo_info, anyhow::anyhow!(e));
-        anyhow::anyhow!(error.format_user_message())
-    })?;
+    push_refspecs_with_progress(repo, repo_info, &[refspec], network_options, progress)
+}
+
+/// Name of the branch HEAD points at, also for a new repository without commits.
+/// Fails when HEAD is detached, there is no branch to push then.
+pub fn current_branch(repo: &Repository) -> Result<String> {
+    if repo.head_detached()? {
+        return Err(anyhow::anyhow!("HEAD is detached, check out a branch first"));
+    }
     
+    // `repo.head()` fails on an unborn branch, the symbolic HEAD still names it
+    let head = repo.find_reference("HEAD")?;
+    let target = head.symbolic_target()
+        .ok_or_else(|| anyhow::anyhow!("HEAD does not point at a branch"))?;
+    Ok(target.strip_prefix("refs/heads/").unwrap_or(target).to_string())
+}
+
+/// Name and URL of every remote of the repository, sorted by name
+pub fn list_repo_remotes(repo: &Repository) -> Result<Vec<(String, String)>> {
+    let mut remotes = Vec::new();
+    for name in repo.remotes()?.iter().flatten() {
+        let remote = repo.find_remote(name)?;
+        remotes.push((name.to_string(), remote.url().unwrap_or_default().to_string()));
+    }
+    remotes.sort();
+    Ok(remotes)
+}
+
+/// Point an existing remote at another URL
+pub fn set_remote_url(repo: &Repository, name: &str, url: &str) -> Result<()> {
+    repo.find_remote(name)
+        .map_err(|_| anyhow::anyhow!("Remote '{}' not found", name))?;
+    repo.remote_set_url(name, url)?;
     Ok(())
 }
 
-pub fn pull_from_remote(repo: &Repository, repo_info: &RepositoryInfo, branch: &str) -> Result<()> {
-    // Try to find existing remote or create new one
-    let mut remote = match repo.find_remote(&repo_info.name) {
-        Ok(remote) => remote,
-        Err(_) => {
-            repo.remote(&repo_info.name, &repo_info.url)?
+/// The remote named after the repository, added with its URL when the local repository doesn't have it yet
+pub fn find_or_create_remote<'r>(repo: &'r Repository, repo_info: &RepositoryInfo) -> Result<git2::Remote<'r>> {
+    match repo.find_remote(&repo_info.name) {
+        Ok(remote) => Ok(remote),
+        Err(_) => Ok(repo.remote(&repo_info.name, &repo_info.url)?),
+    }
+}
+
+/// Make the remotes of the repository match the configured repositories: a remote named after each
+/// repository, pointing at its URL. Remotes that aren't configured are left alone.
+/// Returns the names of the remotes that were added or changed.
+pub fn sync_remotes_from_config(repo: &Repository, config: &RepoConfig) -> Result<Vec<String>> {
+    let existing: HashMap<String, String> = list_repo_remotes(repo)?.into_iter().collect();
+    let mut changed = Vec::new();
+    for repo_info in &config.repositories {
+        match existing.get(&repo_info.name) {
+            Some(url) if *url == repo_info.url => continue,
+            Some(_) => set_remote_url(repo, &repo_info.name, &repo_info.url)?,
+            None => {
+                repo.remote(&repo_info.name, &repo_info.url)?;
+            }
         }
-    };
+        changed.push(repo_info.name.clone());
+    }
+    Ok(changed)
+}
+
+/// Ask the remote which branch its HEAD points at, e.g. "main" or "develop"
+pub fn detect_default_branch(repo: &Repository, repo_info: &RepositoryInfo) -> Result<String> {
+    let mut remote = repo.remote_anonymous(&repo_info.url)?;
     
-    // Configure callbacks for authentication based on auth type
     let mut callbacks = git2::RemoteCallbacks::new();
+    configure_credentials(&mut callbacks, repo_info);
     
-    match &repo_info.auth_type {
-        AuthType::SSH => {
-            callbacks.credentials(|_url, username_from_url, _allowed_types| {
-                let username = username_from_url.unwrap_or("git");
-                git2::Cred::ssh_key(
-                    username,
-                    None,
-                    Path::new(&repo_info.ssh_key_path),
-                    None,
-                )
-            });
-        },
-        AuthType::Token => {
-            callbacks.credentials(|_url, _username_from_url, _allowed_types| {
-                git2::Cred::userpass_plaintext("token", &repo_info.auth_token)
-            });
-        },
-        AuthType::Default => {
-            callbacks.credentials(|_url, username_from_url, _allowed_types| {
-                if let Some(username) = username_from_url {
-                    git2::Cred::ssh_key(
-                        username,
-                        None,
-                        Path::new(&format!("{}/.ssh/id_rsa", std::env::var("HOME").unwrap_or_default())),
-                        None,
-                    )
-                } else {
-                    git2::Cred::default()
-                }
-                .or_else(|_| git2::Cred::default())
-            });
+    let connection = remote
+        .connect_auth(git2::Direction::Fetch, Some(callbacks), Some(NetworkOptions::default().proxy_options()))
+        .map_err(|e| {
+            let error = handle_git_error("connecting to", repo_info, anyhow::anyhow!(e));
+            anyhow::anyhow!(error.format_user_message())
+        })?;
+    let head = connection.default_branch()?;
+    let head = head.as_str().ok_or_else(|| anyhow::anyhow!("The default branch of {} is not valid UTF-8", repo_info.name))?;
+    
+    Ok(head.strip_prefix("refs/heads/").unwrap_or(head).to_string())
+}
+
+/// Whether the remote of a repository answers, connecting with its credentials without transferring anything.
+/// Failures are classified by `handle_git_error` and returned as a `GitOperationError`.
+pub fn check_remote_reachable(repo_info: &RepositoryInfo) -> Result<bool> {
+    check_remote_reachable_with_network(repo_info, &NetworkOptions::default())
+}
+
+/// Like `check_remote_reachable`, connecting through the proxy of `network_options`
+pub fn check_remote_reachable_with_network(repo_info: &RepositoryInfo, network_options: &NetworkOptions) -> Result<bool> {
+    let mut remote = git2::Remote::create_detached(repo_info.url.as_str())?;
+    
+    let mut callbacks = git2::RemoteCallbacks::new();
+    configure_credentials(&mut callbacks, repo_info);
+    
+    // The connection disconnects when it is dropped, so ask it before that
+    let mut connection = remote
+        .connect_auth(git2::Direction::Fetch, Some(callbacks), Some(network_options.proxy_options()))
+        .map_err(|e| handle_git_error("checking", repo_info, anyhow::anyhow!(e)))?;
+    
+    Ok(connection.connected())
+}
+
+/// Check that the remote of every enabled repository is reachable, `config.max_parallel_operations` at a time.
+/// Results are in config order, "Success" for a reachable remote or the classified error.
+pub fn health_check_all(config: &RepoConfig) -> Vec<(String, ResultStatus, String)> {
+    let mut results = Vec::new();
+    health_check_all_reporting(config, &mut |result| results.push(result));
+    results
+}
+
+/// Like `health_check_all`, reporting each result as soon as it is known
+pub fn health_check_all_reporting(config: &RepoConfig, report: &mut dyn FnMut((String, ResultStatus, String))) {
+    let network_options = NetworkOptions::from_config(config);
+    
+    for_each_bounded(&config.repositories, config.max_parallel_operations, |repo_info| {
+        if let Some(skipped) = skip_if_disabled(repo_info) {
+            return skipped;
+        }
+        let result = check_remote_reachable_with_network(repo_info, &network_options);
+        log_result("Health check of", repo_info, &result);
+        match result {
+            Ok(true) => (repo_info.name.clone(), ResultStatus::Success, "Success".to_string()),
+            Ok(false) => (repo_info.name.clone(), ResultStatus::Failed, format!("Remote of '{}' did not answer", repo_info.name)),
+            Err(e) => format_error_result("checking", repo_info, Err(e)),
+        }
+    }, &mut |_, result| report(result));
+}
+
+/// Refspec pushing every local tag to the tag of the same name
+pub const ALL_TAGS_REFSPEC: &str = "refs/tags/*:refs/tags/*";
+
+/// Refspec pushing every local branch to the branch of the same name
+pub const ALL_BRANCHES_REFSPEC: &str = "refs/heads/*:refs/heads/*";
+
+/// The refspecs a push of `branch` sends, widened by `include_tags` and `all_branches` of the options
+pub fn refspecs_to_push(branch: &str, options: &CommitOptions) -> Vec<String> {
+    let mut refspecs = vec![if options.all_branches {
+        ALL_BRANCHES_REFSPEC.to_string()
+    } else {
+        format!("refs/heads/{}:refs/heads/{}", branch, branch)
+    }];
+    if options.include_tags {
+        refspecs.push(ALL_TAGS_REFSPEC.to_string());
+    }
+    refspecs
+}
+
+/// Push every local tag
+pub fn push_all_tags(repo: &Repository, repo_info: &RepositoryInfo) -> Result<()> {
+    push_refspecs_with_progress(repo, repo_info, &[ALL_TAGS_REFSPEC.to_string()], &NetworkOptions::default(), &|_| {})
+}
+
+/// Push every local branch
+pub fn push_all_branches(repo: &Repository, repo_info: &RepositoryInfo) -> Result<()> {
+    push_refspecs_with_progress(repo, repo_info, &[ALL_BRANCHES_REFSPEC.to_string()], &NetworkOptions::default(), &|_| {})
+}
+
+// libgit2 can't push a glob refspec, so "refs/tags/*:refs/tags/*" becomes one refspec per local tag
+fn expand_refspecs(repo: &Repository, refspecs: &[String]) -> Result<Vec<String>> {
+    let mut expanded = Vec::new();
+    for refspec in refspecs {
+        let (source, destination) = refspec.split_once(':').unwrap_or((refspec.as_str(), refspec.as_str()));
+        let (Some(source_prefix), Some(destination_prefix)) = (source.strip_suffix('*'), destination.strip_suffix('*')) else {
+            expanded.push(refspec.clone());
+            continue;
+        };
+        for reference in repo.references_glob(source)? {
+            if let Some(name) = reference?.name() {
+                expanded.push(format!("{}:{}{}", name, destination_prefix, &name[source_prefix.len()..]));
+            }
         }
     }
+    Ok(expanded)
+}
+
+/// Push `refspecs` in one go, handing transfer progress to `progress` while the pack is sent
+pub fn push_refspecs_with_progress(
+    repo: &Repository,
+    repo_info: &RepositoryInfo,
+    refspecs: &[String],
+    network_options: &NetworkOptions,
+    progress: &dyn Fn(TransferProgress),
+) -> Result<()> {
+    info!("Pushing {} to {} ({})", refspecs.join(", "), repo_info.name, repo_info.url);
+    
+    let mut remote = find_or_create_remote(repo, repo_info)?;
+    
+    let deadline = network_options.start_transfer();
+    
+    // Configure callbacks for authentication based on auth type
+    let mut callbacks = git2::RemoteCallbacks::new();
+    configure_credentials(&mut callbacks, repo_info);
+    
+    // Report progress and give up on a stalled remote
+    watch_transfer(&mut callbacks, &deadline, progress);
+    
+    // Push to remote
+    let mut push_options = git2::PushOptions::new();
+    push_options.remote_callbacks(callbacks);
+    push_options.proxy_options(network_options.proxy_options());
+    
+    let refspecs = expand_refspecs(repo, refspecs)?;
+    remote.push(&refspecs, Some(&mut push_options))
+        .map_err(|e| transfer_error("pushing to", repo_info, &deadline, e))?;
+    
+    Ok(())
+}
+
+pub fn pull_from_remote(repo: &Repository, repo_info: &RepositoryInfo, branch: &str) -> Result<()> {
+    pull_from_remote_with_network(repo, repo_info, branch, &NetworkOptions::default())
+}
+
+pub fn pull_from_remote_with_network(
+    repo: &Repository,
+    repo_info: &RepositoryInfo,
+    branch: &str,
+    network_options: &NetworkOptions,
+) -> Result<()> {
+    let branch = repo_info.branch_or_default(branch);
+    info!("Pulling {} from {} ({})", branch, repo_info.name, repo_info.url);
+    
+    let mut remote = find_or_create_remote(repo, repo_info)?;
+    
+    let deadline = network_options.start_transfer();
+    let no_progress = |_: TransferProgress| {};
+    
+    // Configure callbacks for authentication based on auth type
+    let mut callbacks = git2::RemoteCallbacks::new();
+    configure_credentials(&mut callbacks, repo_info);
+    
+    // Give up on a stalled remote
+    watch_transfer(&mut callbacks, &deadline, &no_progress);
     
     // Fetch from remote
     let mut fetch_options = git2::FetchOptions::new();
     fetch_options.remote_callbacks(callbacks);
+    fetch_options.proxy_options(network_options.proxy_options());
     
-    remote.fetch(&[branch], Some(&mut fetch_options), None).map_err(|e| {
-        let error = handle_git_error("fetching from", repo_info, anyhow::anyhow!(e));
-        anyhow::anyhow!(error.format_user_message())
-    })?;
+    remote.fetch(&[branch], Some(&mut fetch_options), None)
+        .map_err(|e| transfer_error("fetching from", repo_info, &deadline, e))?;
     
     // Merge fetched changes
     let fetch_head = repo.find_reference("FETCH_HEAD").map_err(|e| {
@@ -184,71 +967,75 @@ pub fn pull_from_remote(repo: &Repository, repo_info: &RepositoryInfo, branch: &
         anyhow::anyhow!(error.format_user_message())
     })?;
     if index.has_conflicts() {
-        return Err(anyhow::anyhow!("Merge conflicts detected"));
+        warn!("Pulling from {} left merge conflicts", repo_info.name);
+        let paths = conflicted_files(repo).unwrap_or_default();
+        return Err(MergeConflicts { paths }.into());
     }
     
     Ok(())
 }
 
 pub fn fetch_from_remote(repo: &Repository, repo_info: &RepositoryInfo, branch: &str) -> Result<()> {
-    // Try to find existing remote or create new one
-    let mut remote = match repo.find_remote(&repo_info.name) {
-        Ok(remote) => remote,
-        Err(_) => {
-            repo.remote(&repo_info.name, &repo_info.url)?
-        }
-    };
+    fetch_from_remote_with_network(repo, repo_info, branch, &NetworkOptions::default())
+}
+
+pub fn fetch_from_remote_with_network(
+    repo: &Repository,
+    repo_info: &RepositoryInfo,
+    branch: &str,
+    network_options: &NetworkOptions,
+) -> Result<()> {
+    fetch_from_remote_with_progress(repo, repo_info, branch, network_options, &|_| {})
+}
+
+/// Fetch `branch`, handing transfer progress to `progress` while objects are received
+pub fn fetch_from_remote_with_progress(
+    repo: &Repository,
+    repo_info: &RepositoryInfo,
+    branch: &str,
+    network_options: &NetworkOptions,
+    progress: &dyn Fn(TransferProgress),
+) -> Result<()> {
+    let branch = repo_info.branch_or_default(branch);
+    info!("Fetching {} from {} ({})", branch, repo_info.name, repo_info.url);
+    
+    let mut remote = find_or_create_remote(repo, repo_info)?;
+    
+    let deadline = network_options.start_transfer();
     
     // Configure callbacks for authentication based on auth type
     let mut callbacks = git2::RemoteCallbacks::new();
+    configure_credentials(&mut callbacks, repo_info);
     
-    match &repo_info.auth_type {
-        AuthType::SSH => {
-            callbacks.credentials(|_url, username_from_url, _allowed_types| {
-                let username = username_from_url.unwrap_or("git");
-                git2::Cred::ssh_key(
-                    username,
-                    None,
-                    Path::new(&repo_info.ssh_key_path),
-                    None,
-                )
-            });
-        },
-        AuthType::Token => {
-            callbacks.credentials(|_url, _username_from_url, _allowed_types| {
-                git2::Cred::userpass_plaintext("token", &repo_info.auth_token)
-            });
-        },
-        AuthType::Default => {
-            callbacks.credentials(|_url, username_from_url, _allowed_types| {
-                if let Some(username) = username_from_url {
-                    git2::Cred::ssh_key(
-                        username,
-                        None,
-                        Path::new(&format!("{}/.ssh/id_rsa", std::env::var("HOME").unwrap_or_default())),
-                        None,
-                    )
-                } else {
-                    git2::Cred::default()
-                }
-                .or_else(|_| git2::Cred::default())
-            });
-        }
-    }
+    // Report progress and give up on a stalled remote
+    watch_transfer(&mut callbacks, &deadline, progress);
     
     // Fetch from remote
     let mut fetch_options = git2::FetchOptions::new();
     fetch_options.remote_callbacks(callbacks);
+    fetch_options.proxy_options(network_options.proxy_options());
+    // Only a pull reads FETCH_HEAD, and fetches running side by side would fight over it
+    fetch_options.update_fetchhead(false);
     
-    remote.fetch(&[branch], Some(&mut fetch_options), None).map_err(|e| {
-        let error = handle_git_error("fetching from", repo_info, anyhow::anyhow!(e));
-        anyhow::anyhow!(error.format_user_message())
-    })?;
+    remote.fetch(&[branch], Some(&mut fetch_options), None)
+        .map_err(|e| transfer_error("fetching from", repo_info, &deadline, e))?;
     
     Ok(())
 }
 
 pub fn create_and_push_tag(repo: &Repository, repo_info: &RepositoryInfo, tag_name: &str, message: &str) -> Result<()> {
+    create_and_push_tag_with_network(repo, repo_info, tag_name, message, &NetworkOptions::default())
+}
+
+pub fn create_and_push_tag_with_network(
+    repo: &Repository,
+    repo_info: &RepositoryInfo,
+    tag_name: &str,
+    message: &str,
+    network_options: &NetworkOptions,
+) -> Result<()> {
+    info!("Creating tag {} for {}", tag_name, repo_info.name);
+    
     // Get the current HEAD commit
     let head = repo.head().map_err(|e| {
         let error = handle_git_error("getting HEAD in", repo_info, anyhow::anyhow!(e));
@@ -270,53 +1057,16 @@ pub fn create_and_push_tag(repo: &Repository, repo_info: &RepositoryInfo, tag_na
     })?;
     
     // Push tag to remote
-    let mut remote = match repo.find_remote(&repo_info.name) {
-        Ok(remote) => remote,
-        Err(_) => {
-            repo.remote(&repo_info.name, &repo_info.url)?
-        }
-    };
+    let mut remote = find_or_create_remote(repo, repo_info)?;
     
     // Configure callbacks for authentication based on auth type
     let mut callbacks = git2::RemoteCallbacks::new();
-    
-    match &repo_info.auth_type {
-        AuthType::SSH => {
-            callbacks.credentials(|_url, username_from_url, _allowed_types| {
-                let username = username_from_url.unwrap_or("git");
-                git2::Cred::ssh_key(
-                    username,
-                    None,
-                    Path::new(&repo_info.ssh_key_path),
-                    None,
-                )
-            });
-        },
-        AuthType::Token => {
-            callbacks.credentials(|_url, _username_from_url, _allowed_types| {
-                git2::Cred::userpass_plaintext("token", &repo_info.auth_token)
-            });
-        },
-        AuthType::Default => {
-            callbacks.credentials(|_url, username_from_url, _allowed_types| {
-                if let Some(username) = username_from_url {
-                    git2::Cred::ssh_key(
-                        username,
-                        None,
-                        Path::new(&format!("{}/.ssh/id_rsa", std::env::var("HOME").unwrap_or_default())),
-                        None,
-                    )
-                } else {
-                    git2::Cred::default()
-                }
-                .or_else(|_| git2::Cred::default())
-            });
-        }
-    }
+    configure_credentials(&mut callbacks, repo_info);
     
     // Push tag to remote
     let mut push_options = git2::PushOptions::new();
     push_options.remote_callbacks(callbacks);
+    push_options.proxy_options(network_options.proxy_options());
     
     let refspec = format!("refs/tags/{}:refs/tags/{}", tag_name, tag_name);
     remote.push(&[&refspec], Some(&mut push_options)).map_err(|e| {
@@ -335,85 +1085,411 @@ pub fn check_merge_conflicts(repo: &Repository) -> Result<bool> {
     Ok(index.has_conflicts())
 }
 
-pub fn push_to_all_repositories(config: &RepoConfig, commit_message: &str, branch: &str) -> Vec<(String, String)> {
+/// Give up on an unfinished merge: drop the merge state and reset the index and working tree to HEAD
+pub fn abort_merge(repo: &Repository) -> Result<()> {
+    let head = repo.head()?.peel_to_commit()?;
+    repo.reset(head.as_object(), git2::ResetType::Hard, None)?;
+    repo.cleanup_state()?;
+    info!("Aborted the merge, reset to {}", head.id());
+    Ok(())
+}
+
+/// Create a new commit on HEAD that undoes `commit_id`, with the identity and signing key of `options`.
+/// When the revert conflicts, the repository is left in the reverting state with the conflicted files listed in the error.
+pub fn revert_commit(repo: &Repository, commit_id: &str, options: &CommitOptions) -> Result<()> {
+    let commit = repo.find_commit(git2::Oid::from_str(commit_id)?)?;
+    repo.revert(&commit, None)?;
+    
+    let mut index = repo.index()?;
+    if index.has_conflicts() {
+        let paths = conflicted_files(repo)?;
+        warn!("Reverting {} left conflicts in {}", commit.id(), paths.join(", "));
+        return Err(anyhow::anyhow!("Reverting {} conflicts in: {}", commit_id, paths.join(", ")));
+    }
+    
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let head = repo.head()?.peel_to_commit()?;
+    let signature = commit_signature(repo, options)?;
+    let message = format!(
+        "Revert \"{}\"\n\nThis reverts commit {}.\n",
+        commit.summary().unwrap_or_default(),
+        commit.id()
+    );
+    let revert_id = write_commit(repo, &signature, &signature, &message, &tree, &[&head], configured_signing_key(options))?;
+    repo.cleanup_state()?;
+    
+    info!("Reverted {} with {}", commit.id(), revert_id);
+    Ok(())
+}
+
+/// Added to the result of a clone that left Git LFS pointer files in place of their content
+pub const LFS_CLONE_WARNING: &str = "This repo uses Git LFS; run 'git lfs pull' separately (not yet supported)";
+
+/// Added to the result of a push from a repository whose LFS objects are not uploaded along
+pub const LFS_PUSH_WARNING: &str = "This repo uses Git LFS; run 'git lfs push' separately (not yet supported)";
+
+/// Whether `.gitattributes` routes any path through the LFS filter. The working tree copy is read,
+/// or the one in HEAD for bare repositories.
+pub fn has_lfs(repo: &Repository) -> bool {
+    let attributes = repo
+        .workdir()
+        .and_then(|workdir| std::fs::read_to_string(workdir.join(".gitattributes")).ok())
+        .or_else(|| {
+            let tree = repo.head().ok()?.peel_to_tree().ok()?;
+            let blob = repo.find_blob(tree.get_name(".gitattributes")?.id()).ok()?;
+            String::from_utf8(blob.content().to_vec()).ok()
+        });
+    
+    attributes.is_some_and(|attributes| {
+        attributes
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with('#'))
+            // The first field is the path pattern
+            .any(|line| line.split_whitespace().skip(1).any(|attribute| attribute == "filter=lfs"))
+    })
+}
+
+/// Point out the LFS content a successful clone or push did not transfer
+pub fn with_lfs_warning((name, status, message): (String, ResultStatus, String), warning: &str) -> (String, ResultStatus, String) {
+    if status == ResultStatus::Success {
+        (name, status, format!("{} ({})", message, warning))
+    } else {
+        (name, status, message)
+    }
+}
+
+/// Paths of the files with unresolved merge conflicts, in index order
+pub fn conflicted_files(repo: &Repository) -> Result<Vec<String>> {
+    let index = repo.index()?;
+    let mut paths = Vec::new();
+    
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        // A side is missing when the file was added or deleted on only one side
+        let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+        if let Some(entry) = entry {
+            paths.push(String::from_utf8_lossy(&entry.path).to_string());
+        }
+    }
+    
+    Ok(paths)
+}
+
+pub fn push_to_all_repositories(config: &RepoConfig, commit_message: &str, branch: &str) -> Vec<(String, ResultStatus, String)> {
+    push_to_all_repositories_with_options(config, commit_message, branch, &CommitOptions::from_config(config))
+}
+
+pub fn push_to_all_repositories_with_options(
+    config: &RepoConfig,
+    commit_message: &str,
+    branch: &str,
+    commit_options: &CommitOptions,
+) -> Vec<(String, ResultStatus, String)> {
     let mut results = Vec::new();
+    push_to_all_repositories_with_options_reporting(config, commit_message, branch, commit_options, &mut |result| results.push(result), &|_, _| {});
+    results
+}
+
+/// Commit once, then push to every repository, handing each result to `report` as soon as it is known
+/// and the transfer progress of each repository to `progress` while its push runs
+pub fn push_to_all_repositories_with_options_reporting(
+    config: &RepoConfig,
+    commit_message: &str,
+    branch: &str,
+    commit_options: &CommitOptions,
+    report: &mut dyn FnMut((String, ResultStatus, String)),
+    progress: &dyn Fn(&str, TransferProgress),
+) {
+    // Reject a bad message before touching the index
+    if let Err(e) = validate_commit_message(commit_message, commit_options) {
+        warn!("Rejected commit message: {}", e);
+        report(("Repository".to_string(), ResultStatus::Failed, e.to_string()));
+        return;
+    }
     
     // Get the current repository
     let repo = match Repository::open(".") {
         Ok(repo) => repo,
         Err(e) => {
-            results.push(("Repository".to_string(), format!("Failed to open repository: {}", e)));
-            return results;
+            error!("Failed to open the local repository: {}", e);
+            report(("Repository".to_string(), ResultStatus::Failed, format!("Failed to open repository: {}", e)));
+            return;
         }
     };
     
-    // Add all changes
-    if let Err(e) = add_all_changes(&repo) {
-        results.push(("Repository".to_string(), format!("Failed to add changes: {}", e)));
-        return results;
+    // Stage either the selected paths or all changes
+    let staged = match &commit_options.paths {
+        Some(paths) => add_paths(&repo, paths),
+        None => add_all_changes(&repo),
+    };
+    if let Err(e) = staged {
+        error!("Failed to stage changes: {}", e);
+        report(("Repository".to_string(), ResultStatus::Failed, format!("Failed to add changes: {}", e)));
+        return;
     }
     
-    // Commit changes
-    if let Err(e) = commit_changes(&repo, commit_message) {
-        results.push(("Repository".to_string(), format!("Failed to commit changes: {}", e)));
-        return results;
+    // Commit changes, earlier commits are still pushed when there is nothing new
+    match commit_changes_with_options(&repo, commit_message, commit_options) {
+        Ok(_) => {},
+        Err(e) if e.is::<NothingToCommit>() => {
+            info!("Nothing to commit, pushing existing commits");
+            report(("Repository".to_string(), ResultStatus::Skipped, NO_CHANGES_TO_COMMIT.to_string()));
+        },
+        Err(e) => {
+            error!("Failed to commit changes: {}", e);
+            report(("Repository".to_string(), ResultStatus::Failed, format!("Failed to commit changes: {}", e)));
+            return;
+        }
     }
     
+    let network_options = NetworkOptions::from_config(config);
+    let uses_lfs = has_lfs(&repo);
     // Push to all repositories
     for repo_info in &config.repositories {
-        let result = push_to_remote(&repo, repo_info, branch);
-        results.push(format_error_result("pushing to", repo_info, result));
+        if let Some(skipped) = skip_if_disabled(repo_info) {
+            report(skipped);
+            continue;
+        }
+        let refspecs = refspecs_to_push(commit_options.branch_for(repo_info, branch), commit_options);
+        // The extra remotes of a repository get the same refspecs
+        for target in repo_info.push_targets() {
+            let (result, attempts) = retry_operation(&config.retry, "pushing to", &target, || {
+                push_refspecs_with_progress(&repo, &target, &refspecs, &network_options, &|transfer| progress(&target.name, transfer))
+            });
+            log_result("Push to", &target, &result);
+            let result = format_retry_result("pushing to", &target, result, attempts);
+            report(if uses_lfs { with_lfs_warning(result, LFS_PUSH_WARNING) } else { result });
+        }
     }
-    
-    results
 }
 
-pub fn pull_from_all_repositories(config: &RepoConfig, branch: &str) -> Vec<(String, String)> {
+/// Commit once, then push only to the repositories at `indices` in the config
+pub fn push_to_selected(config: &RepoConfig, indices: &[usize], commit_message: &str, branch: &str) -> Vec<(String, ResultStatus, String)> {
+    let selected = config.select_repositories(indices);
+    if selected.repositories.is_empty() {
+        return vec![("Repository".to_string(), ResultStatus::Failed, "No repositories selected".to_string())];
+    }
+    push_to_all_repositories(&selected, commit_message, branch)
+}
+
+pub fn pull_from_all_repositories(config: &RepoConfig, branch: &str) -> Vec<(String, ResultStatus, String)> {
     let mut results = Vec::new();
+    pull_from_all_repositories_reporting(config, branch, false, &mut |result| results.push(result));
+    results
+}
+
+/// Pull from every repository, reporting each result as it completes.
+/// With `auto_stash`, local changes are stashed before the first pull and restored after the last.
+pub fn pull_from_all_repositories_reporting(config: &RepoConfig, branch: &str, auto_stash: bool, report: &mut dyn FnMut((String, ResultStatus, String))) {
+    // Get the current repository
+    let mut repo = match Repository::open(".") {
+        Ok(repo) => repo,
+        Err(e) => {
+            error!("Failed to open the local repository: {}", e);
+            report(("Repository".to_string(), ResultStatus::Failed, format!("Failed to open repository: {}", e)));
+            return;
+        }
+    };
+    
+    let network_options = NetworkOptions::from_config(config);
+    with_auto_stash(&mut repo, auto_stash, report, |repo, report| {
+        // Pull from all repositories
+        for repo_info in &config.repositories {
+            if let Some(skipped) = skip_if_disabled(repo_info) {
+                report(skipped);
+                continue;
+            }
+            let (result, attempts) = retry_operation(&config.retry, "pulling from", repo_info, || {
+                pull_from_remote_with_network(repo, repo_info, branch, &network_options)
+            });
+            log_result("Pull from", repo_info, &result);
+            report(format_retry_result("pulling from", repo_info, result, attempts));
+        }
+    });
+}
+
+/// Stash the local changes, including untracked files. Returns `None` when there was nothing to stash.
+pub fn stash_save(repo: &mut Repository, message: &str) -> Result<Option<git2::Oid>> {
+    let signature = repo.signature()?;
+    match repo.stash_save(&signature, message, Some(git2::StashFlags::INCLUDE_UNTRACKED)) {
+        Ok(stash_id) => Ok(Some(stash_id)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Apply the most recent stash to the working tree and drop it
+pub fn stash_pop(repo: &mut Repository) -> Result<()> {
+    repo.stash_pop(0, None)?;
+    Ok(())
+}
+
+/// Run `work` on the repository, with its local changes stashed away while it runs if `auto_stash` is set.
+/// Problems with the stash itself are reported as a "Repository" result.
+pub fn with_auto_stash(
+    repo: &mut Repository,
+    auto_stash: bool,
+    report: &mut dyn FnMut((String, ResultStatus, String)),
+    work: impl FnOnce(&Repository, &mut dyn FnMut((String, ResultStatus, String))),
+) {
+    if !auto_stash {
+        work(repo, report);
+        return;
+    }
+    
+    let stashed = match stash_save(repo, "Auto stash before pull") {
+        Ok(stash_id) => stash_id.is_some(),
+        Err(e) => {
+            error!("Failed to stash local changes: {}", e);
+            report(("Repository".to_string(), ResultStatus::Failed, format!("Failed to stash local changes: {}", e)));
+            return;
+        }
+    };
+    
+    work(repo, report);
     
+    if stashed && let Err(e) = stash_pop(repo) {
+        // The changes are still in the stash, nothing is lost
+        error!("Failed to restore stashed changes: {}", e);
+        report(("Repository".to_string(), ResultStatus::Failed, format!("Failed to restore stashed changes, they are kept in the stash: {}", e)));
+    }
+}
+
+pub fn fetch_from_all_repositories(config: &RepoConfig, branch: &str) -> Vec<(String, ResultStatus, String)> {
+    let mut results = Vec::new();
+    fetch_from_all_repositories_reporting(config, branch, &mut |result| results.push(result), &|_, _| {});
+    results
+}
+
+/// Fetch from every repository, reporting each result as it completes and the transfer progress while it runs
+pub fn fetch_from_all_repositories_reporting(
+    config: &RepoConfig,
+    branch: &str,
+    report: &mut dyn FnMut((String, ResultStatus, String)),
+    progress: &dyn Fn(&str, TransferProgress),
+) {
     // Get the current repository
     let repo = match Repository::open(".") {
         Ok(repo) => repo,
         Err(e) => {
-            results.push(("Repository".to_string(), format!("Failed to open repository: {}", e)));
-            return results;
+            error!("Failed to open the local repository: {}", e);
+            report(("Repository".to_string(), ResultStatus::Failed, format!("Failed to open repository: {}", e)));
+            return;
         }
     };
     
-    // Pull from all repositories
+    let network_options = NetworkOptions::from_config(config);
+    // Fetch from all repositories
     for repo_info in &config.repositories {
-        let result = pull_from_remote(&repo, repo_info, branch);
-        results.push(format_error_result("pulling from", repo_info, result));
+        if let Some(skipped) = skip_if_disabled(repo_info) {
+            report(skipped);
+            continue;
+        }
+        let (result, attempts) = retry_operation(&config.retry, "fetching from", repo_info, || {
+            fetch_from_remote_with_progress(&repo, repo_info, branch, &network_options, &|transfer| progress(&repo_info.name, transfer))
+        });
+        log_result("Fetch from", repo_info, &result);
+        report(format_retry_result("fetching from", repo_info, result, attempts));
     }
-    
-    results
 }
 
-pub fn fetch_from_all_repositories(config: &RepoConfig, branch: &str) -> Vec<(String, String)> {
+/// The skipped result a batch operation reports in place of a disabled repository, `None` for an enabled one
+pub fn skip_if_disabled(repo_info: &RepositoryInfo) -> Option<(String, ResultStatus, String)> {
+    if repo_info.enabled {
+        return None;
+    }
+    info!("Skipping disabled repository {}", repo_info.name);
+    Some((repo_info.name.clone(), ResultStatus::Skipped, SKIPPED_DISABLED.to_string()))
+}
+
+pub fn tag_all_repositories(config: &RepoConfig, tag_name: &str, message: &str) -> Vec<(String, ResultStatus, String)> {
     let mut results = Vec::new();
-    
+    tag_all_repositories_reporting(config, tag_name, message, &mut |result| results.push(result));
+    results
+}
+
+/// Tag and push to every enabled repository, reporting each result as it completes
+pub fn tag_all_repositories_reporting(config: &RepoConfig, tag_name: &str, message: &str, report: &mut dyn FnMut((String, ResultStatus, String))) {
     // Get the current repository
     let repo = match Repository::open(".") {
         Ok(repo) => repo,
         Err(e) => {
-            results.push(("Repository".to_string(), format!("Failed to open repository: {}", e)));
-            return results;
+            error!("Failed to open the local repository: {}", e);
+            report(("Repository".to_string(), ResultStatus::Failed, format!("Failed to open repository: {}", e)));
+            return;
         }
     };
     
-    // Fetch from all repositories
+    let network_options = NetworkOptions::from_config(config);
+    
+    // Create and push the tag for all repositories
     for repo_info in &config.repositories {
-        let result = fetch_from_remote(&repo, repo_info, branch);
-        results.push(format_error_result("fetching from", repo_info, result));
+        if let Some(skipped) = skip_if_disabled(repo_info) {
+            report(skipped);
+            continue;
+        }
+        let result = create_and_push_tag_with_network(&repo, repo_info, tag_name, message, &network_options);
+        log_result("Tag", repo_info, &result);
+        report(format_error_result("tagging", repo_info, result));
     }
+}
+
+/// Host, owner and repository name taken from a remote URL
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedRepoUrl {
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+}
+
+// A path segment of owner/repo: non-empty, no whitespace, not a relative component
+fn is_valid_path_segment(segment: &str) -> bool {
+    !segment.is_empty() && segment != "." && segment != ".." && !segment.contains(char::is_whitespace)
+}
+
+/// Parse `http(s)://host/owner/repo(.git)` or scp-style `git@host:owner/repo.git`.
+/// Nested groups such as GitLab subgroups are kept together in `owner`.
+pub fn parse_repository_url(url: &str) -> Result<ParsedRepoUrl> {
+    let url = url.trim();
     
-    results
+    let (host, path) = if let Some(rest) = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://")) {
+        let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+        // Drop credentials embedded as user:token@host
+        let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+        (host, path)
+    } else if let Some(rest) = url.strip_prefix("git@") {
+        rest.split_once(':').ok_or_else(|| {
+            anyhow::anyhow!("'{}' is missing ':' between host and path, expected git@host:owner/repo.git", url)
+        })?
+    } else {
+        return Err(anyhow::anyhow!(
+            "'{}' is not a supported URL, expected https://host/owner/repo.git or git@host:owner/repo.git",
+            url
+        ));
+    };
+    
+    if host.is_empty() || host.contains(char::is_whitespace) {
+        return Err(anyhow::anyhow!("'{}' has no valid host", url));
+    }
+    
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, repo) = path
+        .rsplit_once('/')
+        .filter(|(owner, repo)| owner.split('/').all(is_valid_path_segment) && is_valid_path_segment(repo))
+        .ok_or_else(|| anyhow::anyhow!("'{}' doesn't point to a repository, expected owner/repo after the host", url))?;
+    
+    Ok(ParsedRepoUrl {
+        host: host.to_string(),
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+    })
 }
 
 // Repository validation functions
 pub fn validate_repository_url(url: &str) -> bool {
-    // Basic URL validation
-    url.starts_with("https://") || url.starts_with("http://") || url.starts_with("git@")
+    parse_repository_url(url).is_ok()
 }
 
 pub fn verify_authentication(repo_info: &RepositoryInfo) -> Result<bool> {
@@ -436,9 +1512,17 @@ pub fn verify_authentication(repo_info: &RepositoryInfo) -> Result<bool> {
                 return Ok(false);
             }
             
-            // Actually test the token by making a simple GitHub API call
-            // This is a basic verification that the token is valid
-            test_github_token(&repo_info.auth_token)
+            // Ask the API of the host the repository lives on
+            provider_for_url(&repo_info.url).test_token(&repo_info.auth_token)
+        },
+        AuthType::SshAgent => {
+            // The agent is reachable through its socket, the default key is the fallback
+            let default_key = default_ssh_key_path();
+            Ok(std::env::var_os("SSH_AUTH_SOCK").is_some() || Path::new(&default_key).exists())
+        },
+        AuthType::CredentialHelper => {
+            // The helper only answers for URLs it has credentials for
+            Ok(credential_helper_fill(&repo_info.url).is_ok())
         },
         AuthType::Default => {
             // For default, we assume it works
@@ -447,60 +1531,41 @@ pub fn verify_authentication(repo_info: &RepositoryInfo) -> Result<bool> {
     }
 }
 
-// New function to test GitHub token validity
-fn test_github_token(token: &str) -> Result<bool> {
-    // In a real implementation, you would make an API call to GitHub
-    // For now, we'll do a basic check but with actual GitHub API call
+/// Clone a repository, passing the transfer progress of the initial fetch to `progress`
+pub fn clone_repository_with_progress(
+    repo_info: &RepositoryInfo,
+    destination_path: &str,
+    network_options: &NetworkOptions,
+    progress: &dyn Fn(TransferProgress),
+) -> Result<Repository> {
+    info!("Cloning {} ({}) into {}", repo_info.name, repo_info.url, destination_path);
     
-    // If the token is not empty, we'll assume it's valid
-    // In a real implementation, you would make an API call to GitHub
-    // tokio::runtime::Handle::current().block_on(oauth::test_github_token(token))
-    Ok(!token.is_empty())
-}
-
-// New function to clone a repository
-pub fn clone_repository(repo_info: &RepositoryInfo, destination_path: &str) -> Result<Repository> {
     // Configure callbacks for authentication based on auth type
     let mut callbacks = git2::RemoteCallbacks::new();
     
-    match &repo_info.auth_type {
-        AuthType::SSH => {
-            callbacks.credentials(|_url, username_from_url, _allowed_types| {
-                let username = username_from_url.unwrap_or("git");
-                git2::Cred::ssh_key(
-                    username,
-                    None,
-                    Path::new(&repo_info.ssh_key_path),
-                    None,
-                )
-            });
-        },
-        AuthType::Token => {
-            callbacks.credentials(|_url, _username_from_url, _allowed_types| {
-                // For GitHub, we can use the token as username with 'x-oauth-basic' as password
-                git2::Cred::userpass_plaintext(&repo_info.auth_token, "x-oauth-basic")
-            });
-        },
-        AuthType::Default => {
-            callbacks.credentials(|_url, username_from_url, _allowed_types| {
-                if let Some(username) = username_from_url {
-                    git2::Cred::ssh_key(
-                        username,
-                        None,
-                        Path::new(&format!("{}/.ssh/id_rsa", std::env::var("HOME").unwrap_or_default())),
-                        None,
-                    )
-                } else {
-                    git2::Cred::default()
-                }
-                .or_else(|_| git2::Cred::default())
-            });
-        }
+    if repo_info.auth_type == AuthType::Token {
+        callbacks.credentials(|_url, _username_from_url, _allowed_types| {
+            // For GitHub, we can use the token as username with 'x-oauth-basic' as password
+            git2::Cred::userpass_plaintext(&repo_info.auth_token, "x-oauth-basic")
+        });
+    } else {
+        configure_credentials(&mut callbacks, repo_info);
     }
     
+    // Clones have no deadline, large repositories can take a while
+    callbacks.transfer_progress(|stats| {
+        progress(TransferProgress {
+            objects_done: stats.received_objects(),
+            total_objects: stats.total_objects(),
+            bytes: stats.received_bytes(),
+        });
+        true
+    });
+    
     // Configure clone options
     let mut fo = git2::FetchOptions::new();
     fo.remote_callbacks(callbacks);
+    fo.proxy_options(network_options.proxy_options());
     
     let mut builder = git2::build::RepoBuilder::new();
     builder.fetch_options(fo);
@@ -521,15 +1586,44 @@ pub fn clone_repository(repo_info: &RepositoryInfo, destination_path: &str) -> R
 }
 
 // New function to clone all repositories in a configuration
-pub fn clone_all_repositories(config: &RepoConfig, base_path: &str) -> Vec<(String, String)> {
+pub fn clone_all_repositories(config: &RepoConfig, base_path: &str, skip_existing: bool) -> Vec<(String, ResultStatus, String)> {
     let mut results = Vec::new();
+    clone_all_repositories_reporting(config, base_path, skip_existing, &mut |result| results.push(result), &|_, _| {});
+    results
+}
+
+/// Clone every repository below `base_path`, `config.max_parallel_operations` at a time.
+/// Results are reported in config order and transfer progress per repository name.
+/// With `skip_existing`, repositories whose `<base>/<name>/.git` already exists are reported
+/// as "Already cloned" instead of failing, so re-running the clone is harmless.
+pub fn clone_all_repositories_reporting(
+    config: &RepoConfig,
+    base_path: &str,
+    skip_existing: bool,
+    report: &mut dyn FnMut((String, ResultStatus, String)),
+    progress: &(dyn Fn(&str, TransferProgress) + Sync),
+) {
+    let network_options = NetworkOptions::from_config(config);
     
-    for repo_info in &config.repositories {
+    for_each_bounded(&config.repositories, config.max_parallel_operations, |repo_info| {
         let destination_path = format!("{}/{}", base_path, repo_info.name);
         
-        let result = clone_repository(repo_info, &destination_path);
-        results.push(format_error_result("cloning", repo_info, result.map(|_| ())));
-    }
-    
-    results
+        if skip_existing && Path::new(&destination_path).join(".git").exists() {
+            info!("Skipping {}: already cloned into {}", repo_info.name, destination_path);
+            return (repo_info.name.clone(), ResultStatus::Skipped, ALREADY_CLONED.to_string());
+        }
+        
+        let result = clone_repository_with_progress(repo_info, &destination_path, &network_options, &|transfer| {
+            progress(&repo_info.name, transfer)
+        });
+        log_result("Clone", repo_info, &result);
+        let uses_lfs = result.as_ref().is_ok_and(has_lfs);
+        let result = format_error_result("cloning", repo_info, result.map(|_| ()));
+        if uses_lfs {
+            warn!("{} uses Git LFS, its LFS files were not downloaded", repo_info.name);
+            with_lfs_warning(result, LFS_CLONE_WARNING)
+        } else {
+            result
+        }
+    }, &mut |_, result| report(result));
 }
@@ -0,0 +1,139 @@
+use anyhow::Result;
+use log::{info, warn};
+use serde::Deserialize;
+use crate::core::repository::{AuthType, RepositoryInfo};
+
+const GITHUB_API_BASE: &str = "https://api.github.com";
+const REPOS_PER_PAGE: u32 = 100;
+
+/// The fields of a repository in GitHub's repository listings that are imported
+#[derive(Debug, Deserialize)]
+pub struct GitHubRepository {
+    pub name: String,
+    pub clone_url: String,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+/// Map a GitHub repository to a config entry that authenticates with `token`
+pub fn repository_from_github(repo: &GitHubRepository, token: &str) -> RepositoryInfo {
+    let mut repo_info = RepositoryInfo::with_auth(repo.name.clone(), repo.clone_url.clone(), AuthType::Token);
+    repo_info.auth_token = token.to_string();
+    repo_info
+}
+
+/// Parse one page of `GET /users/{owner}/repos` into config entries.
+/// Archived repositories are read-only, so they are left out.
+pub fn parse_github_repositories(json: &str, token: &str) -> Result<Vec<RepositoryInfo>> {
+    let repositories: Vec<GitHubRepository> = serde_json::from_str(json)?;
+    Ok(repositories
+        .iter()
+        .filter(|repo| !repo.archived)
+        .map(|repo| repository_from_github(repo, token))
+        .collect())
+}
+
+/// The `rel="next"` URL of a `Link` header, None on the last page
+pub fn next_page_url(link_header: &str) -> Option<String> {
+    link_header.split(',').find_map(|link| {
+        let (url, params) = link.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| param.trim() == r#"rel="next""#)
+            .then(|| url.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+    })
+}
+
+/// Whether a response was refused because the API rate limit is used up
+pub fn is_rate_limited(status: u16, remaining: Option<&str>) -> bool {
+    status == 429 || (status == 403 && remaining == Some("0"))
+}
+
+/// The rate limit GitHub reports in the headers of every API response
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimit {
+    /// Requests left in the current window
+    pub remaining: Option<u32>,
+    /// When the window ends and the limit is reset
+    pub reset_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl RateLimit {
+    /// Read `x-ratelimit-remaining` and `x-ratelimit-reset`, which is in seconds since the epoch
+    pub fn from_headers(headers: &reqwest::header::HeaderMap) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok()).map(str::trim);
+        Self {
+            remaining: header("x-ratelimit-remaining").and_then(|remaining| remaining.parse().ok()),
+            reset_at: header("x-ratelimit-reset")
+                .and_then(|reset| reset.parse().ok())
+                .and_then(|reset| chrono::DateTime::from_timestamp(reset, 0)),
+        }
+    }
+}
+
+/// A GitHub API request was refused because the rate limit is used up.
+/// Callers can `downcast_ref` an `anyhow::Error` to this to tell it apart from other failures.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimited {
+    pub reset_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl std::fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.reset_at {
+            Some(reset_at) => write!(f, "GitHub rate limit hit, resets at {}", reset_at.with_timezone(&chrono::Local).format("%H:%M")),
+            None => write!(f, "GitHub rate limit hit, try again later"),
+        }
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// Fail with `RateLimited` when GitHub refused the request because of the rate limit
+pub fn check_rate_limit(response: &reqwest::Response) -> Result<()> {
+    if !is_rate_limited(response.status().as_u16(), header_value(response, "x-ratelimit-remaining")) {
+        return Ok(());
+    }
+    let rate_limit = RateLimit::from_headers(response.headers());
+    Err(RateLimited { reset_at: rate_limit.reset_at }.into())
+}
+
+// A response header as text, None if it's missing or not valid UTF-8
+fn header_value<'a>(response: &'a reqwest::Response, name: &str) -> Option<&'a str> {
+    response.headers().get(name).and_then(|value| value.to_str().ok())
+}
+
+/// List the repositories of a GitHub user or organization, following the `Link` header across pages
+pub async fn list_github_repos(token: &str, owner: &str) -> Result<Vec<RepositoryInfo>> {
+    info!("Listing GitHub repositories of {}", owner);
+    let client = reqwest::Client::new();
+    let mut next_url = Some(format!("{}/users/{}/repos?per_page={}", GITHUB_API_BASE, owner, REPOS_PER_PAGE));
+    let mut repositories = Vec::new();
+    
+    while let Some(url) = next_url.take() {
+        let mut request = client
+            .get(&url)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "Multi-Repo-Pusher");
+        if !token.is_empty() {
+            request = request.header("Authorization", format!("token {}", token));
+        }
+        let response = request.send().await?;
+        
+        let status = response.status();
+        if let Err(e) = check_rate_limit(&response) {
+            warn!("GitHub API rate limit hit while listing repositories of {}", owner);
+            return Err(e);
+        }
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("GitHub API returned {} while listing repositories of {}", status, owner));
+        }
+        next_url = header_value(&response, "link").and_then(next_page_url);
+        
+        let body = response.text().await?;
+        repositories.extend(parse_github_repositories(&body, token)?);
+    }
+    
+    info!("Found {} GitHub repositories of {}", repositories.len(), owner);
+    Ok(repositories)
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::github_api::{is_rate_limited, next_page_url, parse_github_repositories, RateLimit, RateLimited};
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use crate::core::repository::AuthType;
+
+    const REPOS_PAGE: &str = r#"[
+        {
+            "id": 1296269,
+            "name": "Hello-World",
+            "full_name": "octocat/Hello-World",
+            "clone_url": "https://github.com/octocat/Hello-World.git",
+            "ssh_url": "git@github.com:octocat/Hello-World.git",
+            "archived": false
+        },
+        {
+            "id": 1300192,
+            "name": "Spoon-Knife",
+            "full_name": "octocat/Spoon-Knife",
+            "clone_url": "https://github.com/octocat/Spoon-Knife.git",
+            "archived": true
+        },
+        {
+            "id": 1300193,
+            "name": "linguist",
+            "clone_url": "https://github.com/octocat/linguist.git"
+        }
+    ]"#;
+
+    #[test]
+    fn test_github_json_maps_to_repository_info() {
+        let repositories = parse_github_repositories(REPOS_PAGE, "ghp_token").unwrap();
+        
+        // Archived repositories can't be pushed to and are skipped
+        assert_eq!(repositories.len(), 2);
+        
+        let repo = &repositories[0];
+        assert_eq!(repo.name, "Hello-World");
+        assert_eq!(repo.url, "https://github.com/octocat/Hello-World.git");
+        assert_eq!(repo.auth_type, AuthType::Token);
+        assert_eq!(repo.auth_token, "ghp_token");
+        assert_eq!(repo.group, "");
+        
+        assert_eq!(repositories[1].name, "linguist");
+        assert!(parse_github_repositories("[]", "ghp_token").unwrap().is_empty());
+        assert!(parse_github_repositories(r#"{"message": "Not Found"}"#, "ghp_token").is_err());
+    }
+
+    #[test]
+    fn test_next_page_url_from_link_header() {
+        let link = r#"<https://api.github.com/user/583231/repos?per_page=100&page=2>; rel="next", <https://api.github.com/user/583231/repos?per_page=100&page=4>; rel="last""#;
+        assert_eq!(
+            next_page_url(link),
+            Some("https://api.github.com/user/583231/repos?per_page=100&page=2".to_string())
+        );
+        
+        // The last page only links back
+        let last = r#"<https://api.github.com/user/583231/repos?per_page=100&page=3>; rel="prev", <https://api.github.com/user/583231/repos?per_page=100&page=1>; rel="first""#;
+        assert_eq!(next_page_url(last), None);
+        assert_eq!(next_page_url(""), None);
+    }
+
+    #[test]
+    fn test_rate_limit_detection() {
+        assert!(is_rate_limited(429, None));
+        assert!(is_rate_limited(403, Some("0")));
+        // A 403 with requests left is a permission problem, not the rate limit
+        assert!(!is_rate_limited(403, Some("42")));
+        assert!(!is_rate_limited(200, Some("0")));
+    }
+
+    #[test]
+    fn test_rate_limit_headers_parse() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("60"));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1372700873"));
+        
+        let rate_limit = RateLimit::from_headers(&headers);
+        assert_eq!(rate_limit, RateLimit {
+            remaining: Some(0),
+            reset_at: chrono::DateTime::from_timestamp(1372700873, 0),
+        });
+        
+        // Responses from other hosts have no rate limit headers
+        assert_eq!(RateLimit::from_headers(&HeaderMap::new()), RateLimit { remaining: None, reset_at: None });
+    }
+
+    #[test]
+    fn test_rate_limited_message() {
+        let reset_at = chrono::DateTime::from_timestamp(1372700873, 0).unwrap();
+        let expected = format!("GitHub rate limit hit, resets at {}", reset_at.with_timezone(&chrono::Local).format("%H:%M"));
+        assert_eq!(RateLimited { reset_at: Some(reset_at) }.to_string(), expected);
+        assert_eq!(RateLimited { reset_at: None }.to_string(), "GitHub rate limit hit, try again later");
+        
+        // Still recognizable once it is an anyhow error
+        let error: anyhow::Error = RateLimited { reset_at: None }.into();
+        assert!(error.downcast_ref::<RateLimited>().is_some());
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::Result;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+// GitLab OAuth configuration
+const GITLAB_CLIENT_ID: &str = "YOUR_GITLAB_CLIENT_ID";
+const GITLAB_CLIENT_SECRET: &str = "YOUR_GITLAB_CLIENT_SECRET";
+const GITLAB_REDIRECT_URI: &str = "http://localhost:8080/callback";
+const GITLAB_BASE_URL: &str = "https://gitlab.com";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitLabAccessTokenRequest {
+    pub client_id: String,
+    pub client_secret: String,
+    pub code: String,
+    pub grant_type: String,
+    pub redirect_uri: String,
+}
+
+impl GitLabAccessTokenRequest {
+    /// Request exchanging an authorization code for an access token
+    pub fn for_code(code: &str) -> Self {
+        Self {
+            client_id: GITLAB_CLIENT_ID.to_string(),
+            client_secret: GITLAB_CLIENT_SECRET.to_string(),
+            code: code.to_string(),
+            // GitLab requires the grant type, GitHub infers it
+            grant_type: "authorization_code".to_string(),
+            redirect_uri: GITLAB_REDIRECT_URI.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitLabAccessTokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    pub scope: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitLabUser {
+    pub username: String,
+    pub id: u64,
+    pub email: Option<String>,
+    pub name: Option<String>,
+}
+
+/// Get the GitLab OAuth authorization URL
+pub fn get_gitlab_auth_url() -> String {
+    format!(
+        "{}/oauth/authorize?client_id={}&redirect_uri={}&response_type=code&scope=api+read_user",
+        GITLAB_BASE_URL, GITLAB_CLIENT_ID, GITLAB_REDIRECT_URI
+    )
+}
+
+/// Exchange authorization code for access token
+pub async fn exchange_code_for_token(code: &str) -> Result<GitLabAccessTokenResponse> {
+    info!("Exchanging GitLab OAuth code for an access token");
+    let client = reqwest::Client::new();
+    
+    let params = GitLabAccessTokenRequest::for_code(code);
+    
+    let response = client
+        .post(format!("{}/oauth/token", GITLAB_BASE_URL))
+        .header("Accept", "application/json")
+        .json(&params)
+        .send()
+        .await?;
+        
+    let token_response: GitLabAccessTokenResponse = response.json().await?;
+    info!("Received a GitLab access token (scope: {})", token_response.scope);
+    Ok(token_response)
+}
+
+/// Get user information using access token
+pub async fn get_gitlab_user(access_token: &str) -> Result<GitLabUser> {
+    let client = reqwest::Client::new();
+    
+    let response = client
+        .get(format!("{}/api/v4/user", GITLAB_BASE_URL))
+        .header("Authorization", format!("Bearer {}", access_token))
+        .header("User-Agent", "Multi-Repo-Pusher")
+        .send()
+        .await?
+        .error_for_status()?;
+        
+    let user: GitLabUser = response.json().await?;
+    info!("Authenticated as GitLab user {}", user.username);
+    Ok(user)
+}
+
+/// Test if a GitLab access token is valid
+pub async fn test_gitlab_token(access_token: &str) -> Result<bool> {
+    match get_gitlab_user(access_token).await {
+        Ok(_) => Ok(true),
+        Err(e) => {
+            warn!("GitLab token check failed: {}", e);
+            Ok(false)
+        }
+    }
+}
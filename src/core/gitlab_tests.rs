@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::gitlab::{get_gitlab_auth_url, GitLabAccessTokenRequest, GitLabAccessTokenResponse};
+    use crate::core::oauth::Provider;
+
+    #[test]
+    fn test_gitlab_token_request_serialization() {
+        let request = GitLabAccessTokenRequest::for_code("abc123");
+        let json: serde_json::Value = serde_json::to_value(&request).unwrap();
+        
+        assert_eq!(json["code"], "abc123");
+        assert_eq!(json["grant_type"], "authorization_code");
+        assert_eq!(json["redirect_uri"], "http://localhost:8080/callback");
+        assert!(json["client_id"].is_string());
+        assert!(json["client_secret"].is_string());
+    }
+
+    #[test]
+    fn test_gitlab_token_response_without_refresh_token() {
+        let response: GitLabAccessTokenResponse = serde_json::from_str(
+            r#"{ "access_token": "glpat-xyz", "token_type": "Bearer", "scope": "api read_user" }"#
+        ).unwrap();
+        assert_eq!(response.access_token, "glpat-xyz");
+        assert_eq!(response.refresh_token, None);
+    }
+
+    #[test]
+    fn test_provider_auth_urls() {
+        assert!(get_gitlab_auth_url().starts_with("https://gitlab.com/oauth/authorize?"));
+        assert_eq!(Provider::GitLab.auth_url(), get_gitlab_auth_url());
+        assert!(Provider::GitHub.auth_url().starts_with("https://github.com/login/oauth/authorize?"));
+        assert_eq!(Provider::default(), Provider::GitHub);
+    }
+}
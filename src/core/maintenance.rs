@@ -0,0 +1,74 @@
+use anyhow::{anyhow, Result};
+use git2::Repository;
+use log::info;
+use std::process::Command;
+
+/// Object counts reported by `git count-objects -v`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ObjectCounts {
+    pub loose: u64,
+    pub packed: u64,
+    pub packs: u64,
+}
+
+/// Read the `count`, `in-pack` and `packs` lines of `git count-objects -v`
+pub fn parse_count_objects(output: &str) -> ObjectCounts {
+    let mut counts = ObjectCounts::default();
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let Ok(value) = value.trim().parse() else {
+            continue;
+        };
+        match key.trim() {
+            "count" => counts.loose = value,
+            "in-pack" => counts.packed = value,
+            "packs" => counts.packs = value,
+            _ => {}
+        }
+    }
+    counts
+}
+
+// Run git in `repo_path`, returning its stdout or an error with its stderr
+fn run_git(repo_path: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .map_err(|e| anyhow!("Failed to run git {}: {}", args.join(" "), e))?;
+    
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git {} failed in {}: {}",
+            args.join(" "),
+            repo_path,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn count_objects(repo_path: &str) -> Result<ObjectCounts> {
+    run_git(repo_path, &["count-objects", "-v"]).map(|output| parse_count_objects(&output))
+}
+
+/// Pack loose objects and prune unreachable ones with `git gc`, which git2 has no API for.
+/// Returns the object counts before and after.
+pub fn run_maintenance(repo_path: &str) -> Result<String> {
+    // Fail with a clear message rather than whatever git says about a missing repository
+    Repository::open(repo_path).map_err(|e| anyhow!("{} is not a git repository: {}", repo_path, e.message()))?;
+    
+    let before = count_objects(repo_path)?;
+    info!("Running git gc in {}", repo_path);
+    run_git(repo_path, &["gc", "--quiet"])?;
+    let after = count_objects(repo_path)?;
+    
+    Ok(format!(
+        "loose objects {} → {}, packed objects {} → {}, packs {} → {}",
+        before.loose, after.loose, before.packed, after.packed, before.packs, after.packs
+    ))
+}
@@ -0,0 +1,23 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::maintenance::{parse_count_objects, run_maintenance, ObjectCounts};
+
+    #[test]
+    fn test_parse_count_objects() {
+        let output = "count: 12\nsize: 48\nin-pack: 3051\npacks: 2\nsize-pack: 1206\nprune-packable: 0\ngarbage: 0\nsize-garbage: 0\n";
+        assert_eq!(parse_count_objects(output), ObjectCounts { loose: 12, packed: 3051, packs: 2 });
+        assert_eq!(parse_count_objects(""), ObjectCounts::default());
+    }
+
+    #[test]
+    fn test_maintenance_outside_a_repository_fails_cleanly() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        
+        let error = run_maintenance(&path).unwrap_err().to_string();
+        assert!(error.contains("is not a git repository"), "unexpected error: {}", error);
+        
+        let missing = dir.path().join("missing").to_string_lossy().to_string();
+        assert!(run_maintenance(&missing).is_err());
+    }
+}
@@ -1,11 +1,26 @@
 pub mod repository;
+pub mod config_migration;
+pub mod config_format;
 pub mod git_operations;
+pub mod git_backend;
 pub mod commit_history;
 pub mod error_handler;
 pub mod oauth;
+pub mod gitlab;
+pub mod provider;
+pub mod github_api;
+pub mod notifications;
 pub mod batch_operations;
+pub mod parallel;
+pub mod tag_versions;
+pub mod changelog;
+pub mod maintenance;
 pub mod repository_stats;
 pub mod repository_comparison;
+pub mod operation_log;
+
+#[cfg(test)]
+pub(crate) mod test_support;
 
 #[cfg(test)]
 mod repository_tests;
@@ -28,8 +43,44 @@ mod error_handler_tests;
 #[cfg(test)]
 mod batch_operations_tests;
 
+#[cfg(test)]
+mod parallel_tests;
+
 #[cfg(test)]
 mod repository_stats_tests;
 
 #[cfg(test)]
-mod repository_comparison_tests;
\ No newline at end of file
+mod repository_comparison_tests;
+
+#[cfg(test)]
+mod operation_log_tests;
+
+#[cfg(test)]
+mod github_api_tests;
+
+#[cfg(test)]
+mod gitlab_tests;
+
+#[cfg(test)]
+mod provider_tests;
+
+#[cfg(test)]
+mod oauth_tests;
+
+#[cfg(test)]
+mod notifications_tests;
+
+#[cfg(test)]
+mod config_migration_tests;
+
+#[cfg(test)]
+mod config_format_tests;
+
+#[cfg(test)]
+mod tag_versions_tests;
+
+#[cfg(test)]
+mod changelog_tests;
+
+#[cfg(test)]
+mod maintenance_tests;
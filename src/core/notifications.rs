@@ -0,0 +1,168 @@
+use anyhow::Result;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use crate::core::error_handler::ResultStatus;
+use crate::core::provider::block_on_request;
+use crate::core::repository::{RepoConfig, RepositoryInfo};
+use std::collections::HashSet;
+
+/// Webhooks that are told when a batch push has finished, and where to email failures
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct NotificationConfig {
+    #[serde(default)]
+    pub slack_webhook_url: Option<String>,
+    #[serde(default)]
+    pub discord_webhook_url: Option<String>,
+    #[serde(default)]
+    pub smtp: Option<SmtpConfig>,
+}
+
+/// SMTP server that failure emails are sent through, sending as `username`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SmtpConfig {
+    pub host: String,
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    pub recipient: String,
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+/// An email about the repositories that failed in a batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailureEmail {
+    pub subject: String,
+    pub body: String,
+}
+
+impl NotificationConfig {
+    /// The configured webhook URLs, blank ones left out
+    pub fn webhooks(&self) -> Vec<&str> {
+        [&self.slack_webhook_url, &self.discord_webhook_url]
+            .into_iter()
+            .flatten()
+            .map(|url| url.trim())
+            .filter(|url| !url.is_empty())
+            .collect()
+    }
+}
+
+/// One-line summary of a batch push, e.g. "Pushed to 5/6 repos, 1 failed: repoX"
+pub fn format_push_summary(results: &[(String, ResultStatus, String)], repositories: &[RepositoryInfo]) -> String {
+    let results = pushed_results(results, repositories);
+    let failed: Vec<&str> = results
+        .iter()
+        .filter(|(_, status, _)| *status != ResultStatus::Success)
+        .map(|(repo_name, _, _)| repo_name.as_str())
+        .collect();
+    let succeeded = results.len() - failed.len();
+    
+    if failed.is_empty() {
+        format!("Pushed to {}/{} repos", succeeded, results.len())
+    } else {
+        format!("Pushed to {}/{} repos, {} failed: {}", succeeded, results.len(), failed.len(), failed.join(", "))
+    }
+}
+
+// The results of the pushes to `repositories` and their extra remotes. Disabled repositories weren't pushed to,
+// and entries like "Repository" for the commit or the name of a group report on the batch, not a repository.
+fn pushed_results<'a>(results: &'a [(String, ResultStatus, String)], repositories: &[RepositoryInfo]) -> Vec<&'a (String, ResultStatus, String)> {
+    let names: HashSet<String> = repositories
+        .iter()
+        .flat_map(|repo_info| repo_info.push_targets())
+        .map(|target| target.name)
+        .collect();
+    results
+        .iter()
+        .filter(|(name, status, _)| *status != ResultStatus::Skipped && names.contains(name))
+        .collect()
+}
+
+/// Request body for a webhook. Discord reads `content`, Slack and compatible services read `text`.
+pub fn webhook_payload(webhook: &str, message: &str) -> serde_json::Value {
+    let is_discord = ["discord.com/api/webhooks", "discordapp.com/api/webhooks"]
+        .iter()
+        .any(|host| webhook.contains(host));
+    if is_discord {
+        serde_json::json!({ "content": message })
+    } else {
+        serde_json::json!({ "text": message })
+    }
+}
+
+/// Post the summary of a batch push to a Slack or Discord webhook
+pub async fn notify(results: &[(String, ResultStatus, String)], repositories: &[RepositoryInfo], webhook: &str) -> Result<()> {
+    let message = format_push_summary(results, repositories);
+    let response = reqwest::Client::new()
+        .post(webhook)
+        .json(&webhook_payload(webhook, &message))
+        .send()
+        .await?;
+    
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("Webhook returned {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Compose the email sent when a batch has failures: the error of every failed repository,
+/// while the successful ones are only counted. None when nothing failed.
+pub fn compose_failure_email(results: &[(String, ResultStatus, String)], repositories: &[RepositoryInfo]) -> Option<FailureEmail> {
+    let results = pushed_results(results, repositories);
+    let failed: Vec<&(String, ResultStatus, String)> = results.iter().copied().filter(|(_, status, _)| *status != ResultStatus::Success).collect();
+    if failed.is_empty() {
+        return None;
+    }
+    
+    let mut body = format!("{} of {} repositories failed:\n\n", failed.len(), results.len());
+    for (repo_name, _, message) in &failed {
+        body.push_str(&format!("{}: {}\n", repo_name, message));
+    }
+    body.push_str(&format!("\n{} repositories succeeded.\n", results.len() - failed.len()));
+    
+    Some(FailureEmail {
+        subject: format!("Multi-Repo Pusher: {} of {} repositories failed", failed.len(), results.len()),
+        body,
+    })
+}
+
+/// Send a failure email through the configured SMTP server, using STARTTLS
+pub fn send_failure_email(smtp: &SmtpConfig, email: &FailureEmail) -> Result<()> {
+    let message = Message::builder()
+        .from(smtp.username.parse()?)
+        .to(smtp.recipient.parse()?)
+        .subject(email.subject.clone())
+        .body(email.body.clone())?;
+    
+    let mailer = SmtpTransport::starttls_relay(&smtp.host)?
+        .port(smtp.port)
+        .credentials(Credentials::new(smtp.username.clone(), smtp.password.clone()))
+        .build();
+    mailer.send(&message)?;
+    Ok(())
+}
+
+/// Post the summary to every configured webhook and email the failures if SMTP is set up.
+/// Failures are only logged, a notification that doesn't arrive shouldn't fail the push it reports on.
+pub fn send_push_notifications(config: &RepoConfig, results: &[(String, ResultStatus, String)]) {
+    for webhook in config.notifications.webhooks() {
+        match block_on_request(notify(results, &config.repositories, webhook)).and_then(|result| result) {
+            Ok(()) => info!("Sent push notification to webhook"),
+            Err(e) => warn!("Failed to send push notification: {}", e),
+        }
+    }
+    
+    let (Some(smtp), Some(email)) = (&config.notifications.smtp, compose_failure_email(results, &config.repositories)) else {
+        return;
+    };
+    match send_failure_email(smtp, &email) {
+        Ok(()) => info!("Emailed the failed repositories to {}", smtp.recipient),
+        Err(e) => warn!("Failed to send failure email to {}: {}", smtp.recipient, e),
+    }
+}
@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::notifications::{compose_failure_email, format_push_summary, webhook_payload, NotificationConfig};
+    use crate::core::error_handler::ResultStatus;
+    use crate::core::repository::{RemoteSpec, RepoConfig, RepositoryInfo};
+
+    fn result(repo_name: &str, status: ResultStatus, message: &str) -> (String, ResultStatus, String) {
+        (repo_name.to_string(), status, message.to_string())
+    }
+
+    fn repositories(names: &[&str]) -> Vec<RepositoryInfo> {
+        names
+            .iter()
+            .map(|name| RepositoryInfo::new(name.to_string(), format!("https://example.com/{}.git", name)))
+            .collect()
+    }
+
+    #[test]
+    fn test_push_summary_with_mixed_results() {
+        let results = vec![
+            result("repo1", ResultStatus::Success, "Success"),
+            result("repo2", ResultStatus::Success, "Success (after 2 attempts)"),
+            result("repoX", ResultStatus::Failed, "Network error while pushing to repoX"),
+            result("repo4", ResultStatus::Success, "Success"),
+            result("repoY", ResultStatus::Failed, "Authentication failed for repoY"),
+            result("repo6", ResultStatus::Success, "Success"),
+        ];
+        let repositories = repositories(&["repo1", "repo2", "repoX", "repo4", "repoY", "repo6"]);
+        assert_eq!(format_push_summary(&results, &repositories), "Pushed to 4/6 repos, 2 failed: repoX, repoY");
+    }
+
+    #[test]
+    fn test_push_summary_without_failures() {
+        let results = vec![result("repo1", ResultStatus::Success, "Success"), result("repo2", ResultStatus::Success, "Success")];
+        assert_eq!(format_push_summary(&results, &repositories(&["repo1", "repo2"])), "Pushed to 2/2 repos");
+    }
+
+    #[test]
+    fn test_push_summary_ignores_disabled_repositories() {
+        let results = vec![result("repo1", ResultStatus::Success, "Success"), result("repo2", ResultStatus::Skipped, "Skipped (disabled)")];
+        let repositories = repositories(&["repo1", "repo2"]);
+        assert_eq!(format_push_summary(&results, &repositories), "Pushed to 1/1 repos");
+        assert!(compose_failure_email(&results, &repositories).is_none());
+    }
+
+    #[test]
+    fn test_push_summary_only_counts_configured_repositories() {
+        let mut repositories = repositories(&["repo1", "repo2"]);
+        repositories[1].extra_remotes.push(RemoteSpec::new("mirror".to_string(), "https://mirror.example.com/repo2.git".to_string()));
+        let results = vec![
+            result("Repository", ResultStatus::Failed, "Failed to create commit: nothing to commit"),
+            result("frontend", ResultStatus::Failed, "Group has no repositories"),
+            result("repo1", ResultStatus::Success, "Success"),
+            result("repo2", ResultStatus::Success, "Success"),
+            result("repo2-mirror", ResultStatus::Failed, "Network error while pushing to repo2-mirror"),
+        ];
+        assert_eq!(format_push_summary(&results, &repositories), "Pushed to 2/3 repos, 1 failed: repo2-mirror");
+        
+        let email = compose_failure_email(&results, &repositories).unwrap();
+        assert_eq!(email.subject, "Multi-Repo Pusher: 1 of 3 repositories failed");
+        assert!(!email.body.contains("Repository"));
+        assert!(!email.body.contains("frontend"));
+    }
+
+    #[test]
+    fn test_webhook_payload_per_service() {
+        let slack = webhook_payload("https://hooks.slack.com/services/T000/B000/XXX", "Pushed to 1/1 repos");
+        assert_eq!(slack, serde_json::json!({ "text": "Pushed to 1/1 repos" }));
+        
+        let discord = webhook_payload("https://discord.com/api/webhooks/123/abc", "Pushed to 1/1 repos");
+        assert_eq!(discord, serde_json::json!({ "content": "Pushed to 1/1 repos" }));
+    }
+
+    #[test]
+    fn test_notifications_are_optional_in_config() {
+        let config: RepoConfig = serde_json::from_str(r#"{ "repositories": [] }"#).unwrap();
+        assert_eq!(config.notifications, NotificationConfig::default());
+        assert!(config.notifications.webhooks().is_empty());
+        
+        let config: RepoConfig = serde_json::from_str(
+            r#"{ "repositories": [], "notifications": { "discord_webhook_url": "https://discord.com/api/webhooks/1/a", "slack_webhook_url": " " } }"#
+        ).unwrap();
+        assert_eq!(config.notifications.webhooks(), vec!["https://discord.com/api/webhooks/1/a"]);
+    }
+    #[test]
+    fn test_failure_email_lists_failed_repositories() {
+        let results = vec![
+            result("repo1", ResultStatus::Success, "Success"),
+            result("repoX", ResultStatus::Failed, "Network error while pushing to repoX: timed out"),
+            result("repo3", ResultStatus::Success, "Success (after 2 attempts)"),
+            result("repoY", ResultStatus::Failed, "Authentication failed for repoY"),
+        ];
+        let email = compose_failure_email(&results, &repositories(&["repo1", "repoX", "repo3", "repoY"])).unwrap();
+        
+        assert_eq!(email.subject, "Multi-Repo Pusher: 2 of 4 repositories failed");
+        assert!(email.body.contains("repoX: Network error while pushing to repoX: timed out"));
+        assert!(email.body.contains("repoY: Authentication failed for repoY"));
+        // Successful repositories are only counted
+        assert!(email.body.contains("2 repositories succeeded."));
+        assert!(!email.body.contains("repo1"));
+        assert!(!email.body.contains("repo3"));
+    }
+
+    #[test]
+    fn test_no_failure_email_when_everything_succeeded() {
+        let results = vec![result("repo1", ResultStatus::Success, "Success"), result("repo2", ResultStatus::Success, "Success")];
+        assert_eq!(compose_failure_email(&results, &repositories(&["repo1", "repo2"])), None);
+    }
+
+    #[test]
+    fn test_smtp_config_port_defaults() {
+        let config: NotificationConfig = serde_json::from_str(
+            r#"{ "smtp": { "host": "smtp.example.com", "username": "bot@example.com", "password": "secret", "recipient": "team@example.com" } }"#
+        ).unwrap();
+        let smtp = config.smtp.unwrap();
+        assert_eq!(smtp.host, "smtp.example.com");
+        assert_eq!(smtp.port, 587);
+        
+        assert_eq!(NotificationConfig::default().smtp, None);
+    }
+}
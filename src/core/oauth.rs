@@ -1,11 +1,38 @@
 use anyhow::Result;
+use log::{info, warn};
 use serde::{Deserialize, Serialize};
+use crate::core::github_api::check_rate_limit;
 
 // GitHub OAuth configuration
 const GITHUB_CLIENT_ID: &str = "YOUR_GITHUB_CLIENT_ID";
 const GITHUB_CLIENT_SECRET: &str = "YOUR_GITHUB_CLIENT_SECRET";
 const GITHUB_REDIRECT_URI: &str = "http://localhost:8080/callback";
 
+/// Hosting service an account authenticates against with OAuth
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Provider {
+    #[default]
+    GitHub,
+    GitLab,
+}
+
+impl Provider {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Provider::GitHub => "GitHub",
+            Provider::GitLab => "GitLab",
+        }
+    }
+    
+    /// The page where the user authorizes the application
+    pub fn auth_url(&self) -> String {
+        match self {
+            Provider::GitHub => get_github_auth_url(),
+            Provider::GitLab => crate::core::gitlab::get_gitlab_auth_url(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GitHubAccessTokenRequest {
     pub client_id: String,
@@ -39,6 +66,7 @@ pub fn get_github_auth_url() -> String {
 
 /// Exchange authorization code for access token
 pub async fn exchange_code_for_token(code: &str) -> Result<GitHubAccessTokenResponse> {
+    info!("Exchanging GitHub OAuth code for an access token");
     let client = reqwest::Client::new();
     
     let params = GitHubAccessTokenRequest {
@@ -56,6 +84,7 @@ pub async fn exchange_code_for_token(code: &str) -> Result<GitHubAccessTokenResp
         .await?;
         
     let token_response: GitHubAccessTokenResponse = response.json().await?;
+    info!("Received a GitHub access token (scope: {})", token_response.scope);
     Ok(token_response)
 }
 
@@ -69,8 +98,10 @@ pub async fn get_github_user(access_token: &str) -> Result<GitHubUser> {
         .header("User-Agent", "Multi-Repo-Pusher")
         .send()
         .await?;
+    check_rate_limit(&response)?;
         
     let user: GitHubUser = response.json().await?;
+    info!("Authenticated as GitHub user {}", user.login);
     Ok(user)
 }
 
@@ -78,6 +109,67 @@ pub async fn get_github_user(access_token: &str) -> Result<GitHubUser> {
 pub async fn test_github_token(access_token: &str) -> Result<bool> {
     match get_github_user(access_token).await {
         Ok(_) => Ok(true),
-        Err(_) => Ok(false),
+        Err(e) => {
+            warn!("GitHub token check failed: {}", e);
+            Ok(false)
+        }
     }
-}
\ No newline at end of file
+}
+
+/// Result of checking a token against the GitHub API
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenStatus {
+    /// Accepted, with the expiration GitHub reports for fine-grained and expiring tokens
+    Valid { expires_at: Option<String> },
+    /// Rejected with 401, the token is expired, revoked or mistyped
+    Invalid,
+    /// No usable answer, e.g. no network or an unexpected response
+    Unreachable(String),
+}
+
+/// Classify a `/user` response by its status code, body and `github-authentication-token-expiration` header
+pub fn classify_token_response(status: u16, body: &str, expiration: Option<&str>) -> TokenStatus {
+    match status {
+        200..=299 if serde_json::from_str::<GitHubUser>(body).is_ok() => TokenStatus::Valid {
+            expires_at: expiration.map(|value| value.trim().to_string()).filter(|value| !value.is_empty()),
+        },
+        401 => TokenStatus::Invalid,
+        _ => TokenStatus::Unreachable(format!("GitHub returned an unexpected response ({})", status)),
+    }
+}
+
+/// Check a token with GitHub's `/user` endpoint, telling rejected tokens apart from network problems
+pub async fn check_github_token(access_token: &str) -> TokenStatus {
+    let client = reqwest::Client::new();
+    
+    let response = match client
+        .get("https://api.github.com/user")
+        .header("Authorization", format!("token {}", access_token))
+        .header("User-Agent", "Multi-Repo-Pusher")
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            warn!("Could not reach GitHub to check a token: {}", e);
+            return TokenStatus::Unreachable(e.to_string());
+        }
+    };
+    
+    if let Err(e) = check_rate_limit(&response) {
+        warn!("Could not check a token: {}", e);
+        return TokenStatus::Unreachable(e.to_string());
+    }
+    
+    let status = response.status().as_u16();
+    let expiration = response
+        .headers()
+        .get("github-authentication-token-expiration")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    
+    match response.text().await {
+        Ok(body) => classify_token_response(status, &body, expiration.as_deref()),
+        Err(e) => TokenStatus::Unreachable(e.to_string()),
+    }
+}
@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::oauth::{classify_token_response, TokenStatus};
+
+    const USER_BODY: &str = r#"{ "login": "octocat", "id": 1, "email": null, "name": "The Octocat" }"#;
+    const UNAUTHORIZED_BODY: &str = r#"{ "message": "Bad credentials", "documentation_url": "https://docs.github.com/rest" }"#;
+
+    #[test]
+    fn test_classify_valid_token() {
+        assert_eq!(
+            classify_token_response(200, USER_BODY, None),
+            TokenStatus::Valid { expires_at: None }
+        );
+        assert_eq!(
+            classify_token_response(200, USER_BODY, Some("2030-01-31 09:00:00 UTC")),
+            TokenStatus::Valid { expires_at: Some("2030-01-31 09:00:00 UTC".to_string()) }
+        );
+    }
+
+    #[test]
+    fn test_classify_rejected_token() {
+        assert_eq!(classify_token_response(401, UNAUTHORIZED_BODY, None), TokenStatus::Invalid);
+    }
+
+    #[test]
+    fn test_classify_unexpected_response() {
+        // A success status without a user isn't proof the token works
+        assert!(matches!(classify_token_response(200, "<html></html>", None), TokenStatus::Unreachable(_)));
+        assert!(matches!(classify_token_response(503, "", None), TokenStatus::Unreachable(_)));
+    }
+}
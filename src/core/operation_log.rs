@@ -0,0 +1,133 @@
+//! History of what batch operations did to each repository
+//!
+//! Every push, pull, fetch and clone appends one JSON line per result to `~/.dola-maru/history.jsonl`,
+//! which the `log` subcommand and the History tab read back.
+
+use crate::core::error_handler::ResultStatus;
+use anyhow::Result;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Operations that are recorded in the history
+pub const LOGGED_OPERATIONS: [&str; 4] = ["push", "pull", "fetch", "clone"];
+
+/// One result of one operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub timestamp: i64, // Unix time in seconds
+    pub repo: String,
+    pub operation: String,
+    pub status: ResultStatus,
+    pub message: String, // The message from `format_error_result`
+}
+
+impl LogEntry {
+    /// Local date and time of the entry, like "2024-05-01 14:03:10"
+    pub fn formatted_time(&self) -> String {
+        chrono::DateTime::from_timestamp(self.timestamp, 0)
+            .map(|datetime| datetime.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Which entries to show, empty fields match every entry
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogFilter {
+    /// Part of the repository name, ignoring case
+    pub repo: String,
+    /// One of `LOGGED_OPERATIONS`
+    pub operation: String,
+}
+
+impl LogFilter {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        let repo_matches = self.repo.is_empty() || entry.repo.to_lowercase().contains(&self.repo.to_lowercase());
+        let operation_matches = self.operation.is_empty() || entry.operation == self.operation;
+        repo_matches && operation_matches
+    }
+}
+
+/// Append-only history file with one `LogEntry` per line
+pub struct OperationLog {
+    path: PathBuf,
+}
+
+impl OperationLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+    
+    /// The history in `~/.dola-maru/history.jsonl`
+    pub fn default_location() -> Self {
+        Self::new(format!("{}/.dola-maru/history.jsonl", std::env::var("HOME").unwrap_or_default()))
+    }
+    
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    
+    /// Append one entry per result of `operation`, all stamped with the current time
+    pub fn record(&self, operation: &str, results: &[(String, ResultStatus, String)]) -> Result<()> {
+        let timestamp = chrono::Utc::now().timestamp();
+        let entries: Vec<LogEntry> = results
+            .iter()
+            .map(|(repo, status, message)| LogEntry {
+                timestamp,
+                repo: repo.clone(),
+                operation: operation.to_string(),
+                status: *status,
+                message: message.clone(),
+            })
+            .collect();
+        self.append(&entries)
+    }
+    
+    /// Append entries to the file, creating it and its directory when needed
+    pub fn append(&self, entries: &[LogEntry]) -> Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        
+        let mut lines = String::new();
+        for entry in entries {
+            lines.push_str(&serde_json::to_string(entry)?);
+            lines.push('\n');
+        }
+        
+        // A single write keeps the lines of one operation together
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(lines.as_bytes())?;
+        Ok(())
+    }
+    
+    /// Every entry, oldest first. A missing file is an empty history and lines that can't be parsed are skipped.
+    pub fn read(&self) -> Result<Vec<LogEntry>> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        
+        let mut entries = Vec::new();
+        for (number, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => warn!("Skipping line {} of {}: {}", number + 1, self.path.display(), e),
+            }
+        }
+        Ok(entries)
+    }
+    
+    /// Up to `limit` of the newest entries that match `filter`, newest first
+    pub fn recent(&self, filter: &LogFilter, limit: usize) -> Result<Vec<LogEntry>> {
+        Ok(self.read()?.into_iter().rev().filter(|entry| filter.matches(entry)).take(limit).collect())
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::error_handler::ResultStatus;
+    use crate::core::operation_log::{LogEntry, LogFilter, OperationLog};
+    use tempfile::TempDir;
+    
+    fn entry(timestamp: i64, repo: &str, operation: &str, status: ResultStatus, message: &str) -> LogEntry {
+        LogEntry { timestamp, repo: repo.to_string(), operation: operation.to_string(), status, message: message.to_string() }
+    }
+    
+    #[test]
+    fn test_append_and_read_back() {
+        let dir = TempDir::new().unwrap();
+        // The directory is created on the first append
+        let log = OperationLog::new(dir.path().join(".dola-maru").join("history.jsonl"));
+        assert!(log.read().unwrap().is_empty());
+        
+        log.append(&[entry(100, "web", "push", ResultStatus::Success, "Success")]).unwrap();
+        log.record("fetch", &[
+            ("web".to_string(), ResultStatus::Success, "Success".to_string()),
+            ("api".to_string(), ResultStatus::Failed, "Authentication failed for repository 'api'".to_string()),
+        ]).unwrap();
+        
+        let entries = log.read().unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0], entry(100, "web", "push", ResultStatus::Success, "Success"));
+        assert_eq!((entries[2].repo.as_str(), entries[2].operation.as_str()), ("api", "fetch"));
+        assert_eq!(entries[2].status, ResultStatus::Failed);
+        assert_eq!(entries[2].message, "Authentication failed for repository 'api'");
+        assert_eq!(entries[1].timestamp, entries[2].timestamp);
+        assert!(entries[1].timestamp > 100);
+    }
+    
+    #[test]
+    fn test_recent_filters_newest_first() {
+        let dir = TempDir::new().unwrap();
+        let log = OperationLog::new(dir.path().join("history.jsonl"));
+        log.append(&[
+            entry(1, "web", "push", ResultStatus::Success, "Success"),
+            entry(2, "api", "push", ResultStatus::Success, "Success"),
+            entry(3, "Web-Admin", "pull", ResultStatus::Success, "Success"),
+            entry(4, "web", "pull", ResultStatus::Conflict, "Merge conflicts detected"),
+        ]).unwrap();
+        
+        let web = LogFilter { repo: "WEB".to_string(), ..Default::default() };
+        let timestamps: Vec<i64> = log.recent(&web, 10).unwrap().iter().map(|entry| entry.timestamp).collect();
+        assert_eq!(timestamps, vec![4, 3, 1]);
+        
+        let web_pulls = LogFilter { repo: "web".to_string(), operation: "pull".to_string() };
+        assert_eq!(log.recent(&web_pulls, 1).unwrap(), vec![entry(4, "web", "pull", ResultStatus::Conflict, "Merge conflicts detected")]);
+        assert_eq!(log.recent(&LogFilter::default(), 10).unwrap().len(), 4);
+    }
+    
+    #[test]
+    fn test_unreadable_lines_are_skipped() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("history.jsonl");
+        std::fs::write(&path, "not json\n\n{\"timestamp\":5,\"repo\":\"web\",\"operation\":\"clone\",\"status\":\"success\",\"message\":\"Success\"}\n").unwrap();
+        
+        let entries = OperationLog::new(&path).read().unwrap();
+        assert_eq!(entries, vec![entry(5, "web", "clone", ResultStatus::Success, "Success")]);
+    }
+}
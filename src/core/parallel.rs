@@ -0,0 +1,56 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+/// Run `work` on every item with at most `limit` worker threads and hand the results to
+/// `on_result` in item order. A result is passed on as soon as all earlier items are done,
+/// so a slow item only holds back the ones after it.
+pub fn for_each_bounded<T, R, F>(items: &[T], limit: usize, work: F, on_result: &mut dyn FnMut(usize, R))
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let workers = limit.clamp(1, items.len().max(1));
+    let next_item = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let next_item = &next_item;
+            let work = &work;
+            scope.spawn(move || loop {
+                let index = next_item.fetch_add(1, Ordering::SeqCst);
+                let Some(item) = items.get(index) else { break };
+                if sender.send((index, work(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        // Only the workers' senders are left, so the loop below ends once they are all done
+        drop(sender);
+        
+        let mut pending = BTreeMap::new();
+        let mut next_result = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next_result) {
+                on_result(next_result, result);
+                next_result += 1;
+            }
+        }
+    });
+}
+
+/// Like `for_each_bounded`, collecting the results in item order
+pub fn map_bounded<T, R, F>(items: &[T], limit: usize, work: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let mut results = Vec::with_capacity(items.len());
+    for_each_bounded(items, limit, work, &mut |_, result| results.push(result));
+    results
+}
@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::parallel::{for_each_bounded, map_bounded};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn test_map_bounded_keeps_item_order() {
+        let items: Vec<u64> = (0..12).collect();
+        // Later items finish first, the results still come back in item order
+        let results = map_bounded(&items, 4, |item| {
+            std::thread::sleep(Duration::from_millis(5 * (12 - item)));
+            item * 10
+        });
+        
+        assert_eq!(results, items.iter().map(|item| item * 10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_for_each_bounded_respects_limit() {
+        let items: Vec<usize> = (0..10).collect();
+        let running = AtomicUsize::new(0);
+        let most_running = AtomicUsize::new(0);
+        
+        let mut seen = Vec::new();
+        for_each_bounded(&items, 3, |item| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            most_running.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(10));
+            running.fetch_sub(1, Ordering::SeqCst);
+            *item
+        }, &mut |index, item| seen.push((index, item)));
+        
+        assert_eq!(seen, items.iter().map(|item| (*item, *item)).collect::<Vec<_>>());
+        assert!(most_running.load(Ordering::SeqCst) <= 3);
+    }
+
+    #[test]
+    fn test_map_bounded_handles_empty_input_and_zero_limit() {
+        let empty: Vec<u32> = Vec::new();
+        assert!(map_bounded(&empty, 4, |item| *item).is_empty());
+        // A limit of zero still makes progress with a single worker
+        assert_eq!(map_bounded(&[1, 2, 3], 0, |item| item + 1), vec![2, 3, 4]);
+    }
+}
@@ -0,0 +1,105 @@
+use anyhow::Result;
+use log::warn;
+use std::future::Future;
+use crate::core::git_operations::parse_repository_url;
+use crate::core::oauth::TokenStatus;
+use crate::core::{gitlab, oauth};
+
+/// A hosting service whose API can tell whether a token is accepted
+pub trait GitProvider {
+    /// Ask the service whether `token` is valid. Ok(false) means it was rejected.
+    fn test_token(&self, token: &str) -> Result<bool>;
+    
+    /// Base URL of the service's REST API, empty if it has none this app knows about
+    fn api_base(&self) -> &str;
+    
+    /// Like `test_token`, but tells a rejected token apart from a service that couldn't be asked
+    fn check_token(&self, token: &str) -> TokenStatus {
+        match self.test_token(token) {
+            Ok(true) => TokenStatus::Valid { expires_at: None },
+            Ok(false) => TokenStatus::Invalid,
+            Err(e) => TokenStatus::Unreachable(e.to_string()),
+        }
+    }
+}
+
+pub struct GitHubProvider;
+
+impl GitProvider for GitHubProvider {
+    fn test_token(&self, token: &str) -> Result<bool> {
+        match self.check_token(token) {
+            TokenStatus::Valid { .. } => Ok(true),
+            TokenStatus::Invalid => Ok(false),
+            TokenStatus::Unreachable(reason) => Err(anyhow::anyhow!("Network error while checking the token: {}", reason)),
+        }
+    }
+    
+    fn api_base(&self) -> &str {
+        "https://api.github.com"
+    }
+    
+    fn check_token(&self, token: &str) -> TokenStatus {
+        block_on_request(oauth::check_github_token(token))
+            .unwrap_or_else(|e| TokenStatus::Unreachable(e.to_string()))
+    }
+}
+
+pub struct GitLabProvider;
+
+impl GitProvider for GitLabProvider {
+    fn test_token(&self, token: &str) -> Result<bool> {
+        block_on_request(gitlab::test_gitlab_token(token))?
+    }
+    
+    fn api_base(&self) -> &str {
+        "https://gitlab.com/api/v4"
+    }
+}
+
+/// A self-hosted or unrecognized host. Its tokens can't be checked, so a
+/// token that is set is accepted rather than reported as a failure.
+pub struct UnknownProvider {
+    pub host: String,
+}
+
+impl GitProvider for UnknownProvider {
+    fn test_token(&self, token: &str) -> Result<bool> {
+        warn!("Cannot verify tokens for {}, only checking that one is set", self.host);
+        Ok(!token.is_empty())
+    }
+    
+    fn api_base(&self) -> &str {
+        ""
+    }
+}
+
+/// Pick the provider from the host of a repository URL
+pub fn provider_for_url(url: &str) -> Box<dyn GitProvider> {
+    let host = parse_repository_url(url)
+        .map(|parsed| parsed.host.to_lowercase())
+        .unwrap_or_default();
+    
+    match host.as_str() {
+        "github.com" | "www.github.com" => Box::new(GitHubProvider),
+        "gitlab.com" | "www.gitlab.com" => Box::new(GitLabProvider),
+        _ => Box::new(UnknownProvider { host }),
+    }
+}
+
+/// Run an API request to completion from sync code. The GUI and CLI already run inside
+/// the main runtime, which can't be blocked on, so the request gets a runtime on its own thread.
+pub(crate) fn block_on_request<F>(future: F) -> Result<F::Output>
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| -> Result<F::Output> {
+                let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+                Ok(runtime.block_on(future))
+            })
+            .join()
+            .map_err(|_| anyhow::anyhow!("Request stopped unexpectedly"))?
+    })
+}
@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::provider::provider_for_url;
+
+    #[test]
+    fn test_provider_inferred_from_host() {
+        assert_eq!(provider_for_url("https://github.com/user/repo.git").api_base(), "https://api.github.com");
+        assert_eq!(provider_for_url("git@github.com:user/repo.git").api_base(), "https://api.github.com");
+        assert_eq!(provider_for_url("https://gitlab.com/group/project.git").api_base(), "https://gitlab.com/api/v4");
+    }
+
+    #[test]
+    fn test_unknown_host_cannot_verify_but_is_not_fatal() {
+        let provider = provider_for_url("https://git.example.com/team/app.git");
+        assert_eq!(provider.api_base(), "");
+        
+        // A token that is set is accepted without a network call
+        assert!(provider.test_token("secret").unwrap());
+        assert!(!provider.test_token("").unwrap());
+        
+        // URLs that can't be parsed fall back the same way
+        assert!(provider_for_url("not a url").test_token("secret").unwrap());
+    }
+}
@@ -1,4 +1,10 @@
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use crate::core::config_migration::{migrate, CURRENT_CONFIG_VERSION};
+use crate::core::error_handler::RetryPolicy;
+use crate::core::notifications::NotificationConfig;
+use crate::core::git_operations::parse_repository_url;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RepositoryInfo {
@@ -10,10 +16,56 @@ pub struct RepositoryInfo {
     pub auth_token: String,
     #[serde(default)]
     pub ssh_key_path: String,
+    #[serde(skip)]
+    pub ssh_passphrase: Option<String>, // Passphrase of an encrypted SSH key, entered each session and never saved
     #[serde(default)]
     pub group: String, // New field for repository grouping
+    #[serde(default)]
+    pub author_name: Option<String>, // Overrides the git config identity for commits
+    #[serde(default)]
+    pub author_email: Option<String>,
+    #[serde(default)]
+    pub local_path: String, // Where the repository is cloned, empty if it isn't cloned locally
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>, // Used by push/pull/fetch when no branch is given
+    #[serde(default = "default_enabled")]
+    pub enabled: bool, // Disabled repositories stay configured but are skipped by batch operations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_remotes: Vec<RemoteSpec>, // Mirrors that every push also goes to
+}
+
+/// Another remote a repository is pushed to, with its own credentials
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RemoteSpec {
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub auth_type: AuthType,
+    #[serde(default)]
+    pub auth_token: String,
+    #[serde(default)]
+    pub ssh_key_path: String,
+}
+
+impl RemoteSpec {
+    pub fn new(name: String, url: String) -> Self {
+        Self {
+            name,
+            url,
+            auth_type: AuthType::default(),
+            auth_token: String::new(),
+            ssh_key_path: String::new(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
 }
 
+/// Branch used when neither the caller nor the repository names one
+pub const FALLBACK_BRANCH: &str = "main";
+
 impl RepositoryInfo {
     pub fn new(name: String, url: String) -> Self {
         Self {
@@ -22,7 +74,14 @@ impl RepositoryInfo {
             auth_type: AuthType::default(),
             auth_token: String::new(),
             ssh_key_path: String::new(),
+            ssh_passphrase: None,
             group: String::new(), // Default to no group
+            author_name: None,
+            author_email: None,
+            local_path: String::new(),
+            default_branch: None,
+            enabled: true,
+            extra_remotes: Vec::new(),
         }
     }
     
@@ -33,7 +92,14 @@ impl RepositoryInfo {
             auth_type,
             auth_token: String::new(),
             ssh_key_path: String::new(),
+            ssh_passphrase: None,
             group: String::new(), // Default to no group
+            author_name: None,
+            author_email: None,
+            local_path: String::new(),
+            default_branch: None,
+            enabled: true,
+            extra_remotes: Vec::new(),
         }
     }
     
@@ -42,12 +108,78 @@ impl RepositoryInfo {
         self.group = group;
         self
     }
+    
+    // Set the commit identity used when committing for this account
+    pub fn with_author(mut self, name: String, email: String) -> Self {
+        self.author_name = Some(name);
+        self.author_email = Some(email);
+        self
+    }
+    
+    // Set the local clone of the repository
+    pub fn with_local_path(mut self, local_path: String) -> Self {
+        self.local_path = local_path;
+        self
+    }
+    
+    pub fn with_default_branch(mut self, branch: String) -> Self {
+        self.default_branch = Some(branch);
+        self
+    }
+    
+    pub fn with_extra_remote(mut self, remote: RemoteSpec) -> Self {
+        self.extra_remotes.push(remote);
+        self
+    }
+    
+    /// Name of the local remote for one of the extra remotes, `<repository>-<remote>`,
+    /// so two repositories can each have a remote called "mirror"
+    pub fn extra_remote_name(&self, remote: &RemoteSpec) -> String {
+        format!("{}-{}", self.name, remote.name)
+    }
+    
+    /// Where a push goes: the repository itself, then one entry per extra remote.
+    /// An extra remote keeps the repository's other settings but uses its own URL and credentials,
+    /// and is named by `extra_remote_name`.
+    pub fn push_targets(&self) -> Vec<RepositoryInfo> {
+        let mut targets = vec![self.clone()];
+        for remote in &self.extra_remotes {
+            let mut target = self.clone();
+            target.name = self.extra_remote_name(remote);
+            target.url = remote.url.clone();
+            target.auth_type = remote.auth_type.clone();
+            target.auth_token = remote.auth_token.clone();
+            target.ssh_key_path = remote.ssh_key_path.clone();
+            target.ssh_passphrase = None;
+            target.extra_remotes = Vec::new();
+            targets.push(target);
+        }
+        targets
+    }
+    
+    /// The branch to work on: `requested` unless it's empty, then the repository's
+    /// default branch, then `FALLBACK_BRANCH`
+    pub fn branch_or_default<'a>(&'a self, requested: &'a str) -> &'a str {
+        let requested = requested.trim();
+        if !requested.is_empty() {
+            return requested;
+        }
+        self.default_branch
+            .as_deref()
+            .map(str::trim)
+            .filter(|branch| !branch.is_empty())
+            .unwrap_or(FALLBACK_BRANCH)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum AuthType {
     #[serde(rename = "ssh")]
     SSH,
+    #[serde(rename = "ssh_agent")]
+    SshAgent, // Keys loaded into ssh-agent
+    #[serde(rename = "credential_helper")]
+    CredentialHelper, // HTTPS credentials cached by git's credential helper
     #[serde(rename = "token")]
     Token,
     #[serde(rename = "default")]
@@ -88,30 +220,162 @@ impl RepositoryGroup {
     }
 }
 
+/// Why a repository clashes with one that is already configured
+#[derive(Debug, Clone, PartialEq)]
+pub enum DuplicateRepository {
+    /// The URL points at the repository configured under this name
+    Url(String),
+    /// The name is taken, and remotes are keyed by name
+    Name,
+}
+
+/// A problem in the configuration that would only surface once an operation runs
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigProblem {
+    /// The URL can't be parsed into host, owner and repository
+    InvalidUrl { repository: String, reason: String },
+    /// Token authentication without a token
+    MissingToken { repository: String },
+    /// SSH key authentication without a key file
+    MissingSshKey { repository: String },
+    /// A group lists a repository that isn't configured
+    UnknownGroupMember { group: String, repository: String },
+    /// An extra remote whose local name is already taken by a repository or another remote
+    DuplicateRemote { repository: String, remote: String },
+}
+
+impl std::fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigProblem::InvalidUrl { repository, reason } => write!(f, "{}: invalid URL ({})", repository, reason),
+            ConfigProblem::MissingToken { repository } => write!(f, "{}: token authentication is selected but no token is set", repository),
+            ConfigProblem::MissingSshKey { repository } => write!(f, "{}: SSH key authentication is selected but no key file is set", repository),
+            ConfigProblem::UnknownGroupMember { group, repository } => write!(f, "group {}: repository {} does not exist", group, repository),
+            ConfigProblem::DuplicateRemote { repository, remote } => write!(f, "{}: the name of remote {} is already in use", repository, remote),
+        }
+    }
+}
+
+/// How well a group's membership matches the configured repositories
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupIntegrity {
+    pub group: String,
+    /// Members that are configured repositories
+    pub members: usize,
+    /// Members that no longer exist
+    pub dangling: Vec<String>,
+    /// Repositories whose `group` field names this group without being a member
+    pub unlisted: Vec<String>,
+}
+
+impl GroupIntegrity {
+    pub fn is_intact(&self) -> bool {
+        self.dangling.is_empty() && self.unlisted.is_empty()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RepoConfig {
+    #[serde(default = "default_config_version")]
+    pub version: u32, // Shape of the config file, see `config_migration::migrate`
     pub repositories: Vec<RepositoryInfo>,
     #[serde(default)]
     pub config_name: String,
     #[serde(default)]
     pub groups: Vec<RepositoryGroup>, // New field for repository groups
+    #[serde(default)]
+    pub signing_key: Option<String>, // GPG key id used to sign commits, if any
+    #[serde(default)]
+    pub enforce_conventional_commits: bool, // Reject commit messages like "updated stuff"
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64, // Abort push/pull/fetch transfers that make no progress for this long
+    #[serde(default)]
+    pub retry: RetryPolicy, // Retries of push/pull/fetch after network errors
+    #[serde(default)]
+    pub proxy_url: Option<String>, // HTTP(S) proxy for remote operations, HTTPS_PROXY is used when unset
+    #[serde(default)]
+    pub notifications: NotificationConfig, // Webhooks told about finished pushes
+    #[serde(default = "default_max_parallel_operations")]
+    pub max_parallel_operations: usize, // Repositories worked on at the same time by parallel batch operations
+}
+
+fn default_config_version() -> u32 {
+    1
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+fn default_max_parallel_operations() -> usize {
+    4
 }
 
 impl RepoConfig {
     pub fn new() -> Self {
         Self {
+            version: CURRENT_CONFIG_VERSION,
             repositories: Vec::new(),
             config_name: "default".to_string(),
             groups: Vec::new(), // Initialize with empty groups
+            signing_key: None,
+            enforce_conventional_commits: false,
+            timeout_secs: default_timeout_secs(),
+            retry: RetryPolicy::default(),
+            proxy_url: None,
+            notifications: NotificationConfig::default(),
+            max_parallel_operations: default_max_parallel_operations(),
         }
     }
     
     pub fn with_name(name: String) -> Self {
         Self {
+            version: CURRENT_CONFIG_VERSION,
             repositories: Vec::new(),
             config_name: name,
             groups: Vec::new(), // Initialize with empty groups
+            signing_key: None,
+            enforce_conventional_commits: false,
+            timeout_secs: default_timeout_secs(),
+            retry: RetryPolicy::default(),
+            proxy_url: None,
+            notifications: NotificationConfig::default(),
+            max_parallel_operations: default_max_parallel_operations(),
+        }
+    }
+    
+    /// Whether a configured repository points at `url`, whatever its scheme, case or `.git` suffix
+    pub fn contains(&self, url: &str) -> bool {
+        let normalized = normalize_repository_url(url);
+        self.repositories.iter().any(|repo| normalize_repository_url(&repo.url) == normalized)
+    }
+    
+    /// Check a repository against the configured ones before adding it.
+    /// A matching URL is reported before a matching name.
+    pub fn find_duplicate(&self, repo: &RepositoryInfo) -> Option<DuplicateRepository> {
+        let normalized = normalize_repository_url(&repo.url);
+        if let Some(existing) = self.repositories.iter().find(|existing| normalize_repository_url(&existing.url) == normalized) {
+            return Some(DuplicateRepository::Url(existing.name.clone()));
         }
+        
+        if self.repositories.iter().any(|existing| existing.name == repo.name) {
+            return Some(DuplicateRepository::Name);
+        }
+        
+        None
+    }
+    
+    /// A copy of the configuration with only the repositories at `indices`, in config order.
+    /// Indices past the end are ignored.
+    pub fn select_repositories(&self, indices: &[usize]) -> RepoConfig {
+        let mut selected = self.clone();
+        selected.repositories = self.repositories
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| indices.contains(index))
+            .map(|(_, repo)| repo.clone())
+            .collect();
+        selected
     }
     
     pub fn add_repository(&mut self, repo: RepositoryInfo) {
@@ -131,6 +395,16 @@ impl RepoConfig {
         }
     }
     
+    /// Move the repository at `from` to position `to`, shifting the ones in between.
+    /// Does nothing when either index is out of range.
+    pub fn move_repository(&mut self, from: usize, to: usize) {
+        if from >= self.repositories.len() || to >= self.repositories.len() {
+            return;
+        }
+        let repo = self.repositories.remove(from);
+        self.repositories.insert(to, repo);
+    }
+    
     // New methods for group management
     pub fn add_group(&mut self, group: RepositoryGroup) {
         self.groups.push(group);
@@ -148,6 +422,28 @@ impl RepoConfig {
         self.groups.iter_mut().find(|group| group.name == group_name)
     }
     
+    /// Rename a group and move its repositories along. Fails if a group named `new` already exists.
+    pub fn rename_group(&mut self, old: &str, new: &str) -> Result<()> {
+        if new.trim().is_empty() {
+            return Err(anyhow!("The group name can't be empty"));
+        }
+        if old == new {
+            return Ok(());
+        }
+        if self.get_group(new).is_some() {
+            return Err(anyhow!("A group named '{}' already exists", new));
+        }
+        
+        let group = self.get_group_mut(old).ok_or_else(|| anyhow!("Group '{}' not found", old))?;
+        group.name = new.to_string();
+        for repo in &mut self.repositories {
+            if repo.group == old {
+                repo.group = new.to_string();
+            }
+        }
+        Ok(())
+    }
+    
     // Method to remove a repository from a specific group
     pub fn remove_repository_from_group(&mut self, repo_name: &str, group_name: &str) {
         if let Some(group) = self.get_group_mut(group_name) {
@@ -173,9 +469,142 @@ impl RepoConfig {
         }
     }
     
+    /// Drop group members that aren't configured repositories, e.g. after `repos.json` was
+    /// edited by hand. Returns how many members were removed.
+    pub fn prune_dangling_group_members(&mut self) -> usize {
+        let mut pruned = 0;
+        for group in &mut self.groups {
+            let before = group.repository_names.len();
+            group.repository_names.retain(|name| self.repositories.iter().any(|repo| &repo.name == name));
+            pruned += before - group.repository_names.len();
+        }
+        pruned
+    }
+    
+    /// Membership health of every group, for the Statistics tab
+    pub fn group_integrity_report(&self) -> Vec<GroupIntegrity> {
+        self.groups
+            .iter()
+            .map(|group| {
+                let (members, dangling): (Vec<&String>, Vec<&String>) = group
+                    .repository_names
+                    .iter()
+                    .partition(|name| self.repositories.iter().any(|repo| &&repo.name == name));
+                let unlisted = self.repositories
+                    .iter()
+                    .filter(|repo| repo.group == group.name && !group.repository_names.contains(&repo.name))
+                    .map(|repo| repo.name.clone())
+                    .collect();
+                
+                GroupIntegrity {
+                    group: group.name.clone(),
+                    members: members.len(),
+                    dangling: dangling.into_iter().cloned().collect(),
+                    unlisted,
+                }
+            })
+            .collect()
+    }
+    
+    /// Check the whole configuration, reporting every problem instead of stopping at the first
+    pub fn validate(&self) -> Vec<ConfigProblem> {
+        let mut problems = Vec::new();
+        
+        for repo in &self.repositories {
+            if let Err(e) = parse_repository_url(&repo.url) {
+                problems.push(ConfigProblem::InvalidUrl { repository: repo.name.clone(), reason: e.to_string() });
+            }
+            match repo.auth_type {
+                AuthType::Token if repo.auth_token.trim().is_empty() => {
+                    problems.push(ConfigProblem::MissingToken { repository: repo.name.clone() });
+                },
+                AuthType::SSH if repo.ssh_key_path.trim().is_empty() => {
+                    problems.push(ConfigProblem::MissingSshKey { repository: repo.name.clone() });
+                },
+                _ => {}
+            }
+        }
+        
+        // Extra remotes are added to the local repository next to the repositories' own remotes
+        let mut remote_names: HashSet<String> = self.repositories.iter().map(|repo| repo.name.clone()).collect();
+        for repo in &self.repositories {
+            for remote in &repo.extra_remotes {
+                if let Err(e) = parse_repository_url(&remote.url) {
+                    problems.push(ConfigProblem::InvalidUrl { repository: repo.extra_remote_name(remote), reason: e.to_string() });
+                }
+                if !remote_names.insert(repo.extra_remote_name(remote)) {
+                    problems.push(ConfigProblem::DuplicateRemote { repository: repo.name.clone(), remote: remote.name.clone() });
+                }
+            }
+        }
+        
+        for group in &self.groups {
+            for repo_name in &group.repository_names {
+                if !self.repositories.iter().any(|repo| &repo.name == repo_name) {
+                    problems.push(ConfigProblem::UnknownGroupMember { group: group.name.clone(), repository: repo_name.clone() });
+                }
+            }
+        }
+        
+        problems
+    }
+    
     // Make this function publicly accessible
     pub fn validate_repository_url(url: &str) -> bool {
-        // Basic URL validation
-        url.starts_with("https://") || url.starts_with("http://") || url.starts_with("git@")
+        parse_repository_url(url).is_ok()
     }
-}
\ No newline at end of file
+}
+
+/// Write a snapshot of the configuration to `path`, apart from the live config file.
+/// The snapshot records when it was taken next to the configuration itself.
+pub fn backup_config(config: &RepoConfig, path: &str) -> Result<()> {
+    let snapshot = serde_json::json!({
+        "created_at": chrono::Utc::now().to_rfc3339(),
+        "config": config,
+    });
+    std::fs::write(path, serde_json::to_string_pretty(&snapshot)?)
+        .map_err(|e| anyhow!("Failed to write backup {}: {}", path, e))
+}
+
+/// Read a configuration back from a snapshot written by `backup_config`.
+/// A plain config file is accepted as well, and older versions are migrated.
+pub fn restore_config(path: &str) -> Result<RepoConfig> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read backup {}: {}", path, e))?;
+    let mut backup: serde_json::Value = serde_json::from_str(&contents)?;
+    let config = if backup.get("created_at").is_some() {
+        backup["config"].take()
+    } else {
+        backup
+    };
+    migrate(config)
+}
+
+/// File name suggested for a backup taken at `time`, e.g. `repos-backup-20240131-154500.json`
+pub fn backup_file_name(time: chrono::DateTime<chrono::Local>) -> String {
+    format!("repos-backup-{}.json", time.format("%Y%m%d-%H%M%S"))
+}
+
+/// Rewrite `https://host/owner/repo.git` as `git@host:owner/repo.git`.
+/// None for invalid URLs and hosts with a port, which scp-style URLs can't express.
+pub fn to_ssh_url(url: &str) -> Option<String> {
+    let parsed = parse_repository_url(url).ok()?;
+    if parsed.host.contains(':') {
+        return None;
+    }
+    Some(format!("git@{}:{}/{}.git", parsed.host, parsed.owner, parsed.repo))
+}
+
+/// Rewrite `git@host:owner/repo.git` as `https://host/owner/repo.git`
+pub fn to_https_url(url: &str) -> Option<String> {
+    let parsed = parse_repository_url(url).ok()?;
+    Some(format!("https://{}/{}/{}.git", parsed.host, parsed.owner, parsed.repo))
+}
+
+/// Reduce a URL to `host/owner/repo` in lowercase so the SSH and HTTPS URLs of a repository compare equal
+pub fn normalize_repository_url(url: &str) -> String {
+    match parse_repository_url(url) {
+        Ok(parsed) => format!("{}/{}/{}", parsed.host, parsed.owner, parsed.repo).to_lowercase(),
+        Err(_) => url.trim().trim_end_matches('/').trim_end_matches(".git").to_lowercase(),
+    }
+}
@@ -32,6 +32,63 @@ impl DiffStats {
             deletions: 0,
         }
     }
+    
+    /// Counts computed by libgit2, without printing the patch
+    pub fn from_diff(diff: &git2::Diff) -> Result<Self> {
+        let stats = diff.stats()?;
+        Ok(Self {
+            files_changed: stats.files_changed(),
+            insertions: stats.insertions(),
+            deletions: stats.deletions(),
+        })
+    }
+    
+    /// Short form like "3 files, +40 -12"
+    pub fn summary(&self) -> String {
+        let files = if self.files_changed == 1 { "file" } else { "files" };
+        format!("{} {}, +{} -{}", self.files_changed, files, self.insertions, self.deletions)
+    }
+}
+
+/// How diffs are computed
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffSettings {
+    /// Similarity in percent (0-100) from which a deleted and an added file are shown as a rename
+    pub rename_threshold: u16,
+    /// Leave out changes that only touch whitespace, including at the end of lines
+    pub ignore_whitespace: bool,
+    /// Unchanged lines shown around each change
+    pub context_lines: u32,
+}
+
+impl Default for DiffSettings {
+    fn default() -> Self {
+        Self {
+            rename_threshold: 50, // Same default as git
+            ignore_whitespace: false,
+            context_lines: 3,
+        }
+    }
+}
+
+impl DiffSettings {
+    /// libgit2 options for computing a diff with these settings
+    pub fn diff_options(&self) -> DiffOptions {
+        let mut diff_opts = DiffOptions::new();
+        diff_opts
+            .ignore_whitespace(self.ignore_whitespace)
+            .ignore_whitespace_eol(self.ignore_whitespace)
+            .context_lines(self.context_lines);
+        diff_opts
+    }
+    
+    /// Pair up deleted and added files that are similar enough into renames
+    pub fn find_renames(&self, diff: &mut git2::Diff) -> Result<()> {
+        let mut find_options = git2::DiffFindOptions::new();
+        find_options.renames(true).rename_threshold(self.rename_threshold.min(100));
+        diff.find_similar(Some(&mut find_options))?;
+        Ok(())
+    }
 }
 
 impl RepositoryDiff {
@@ -44,12 +101,49 @@ impl RepositoryDiff {
     }
 }
 
+/// Shown in a patch instead of the content of a binary file
+pub const BINARY_FILE_PLACEHOLDER: &str = "Binary file changed";
+
+/// Append a line printed by `Diff::print` to a unified patch. Only content lines are
+/// prefixed with their origin (`+`, `-` or a space), file and hunk headers already carry their text.
+/// Binary files keep their header and get `BINARY_FILE_PLACEHOLDER` instead of their bytes.
+pub fn push_patch_line(patch: &mut String, delta: &DiffDelta, line: &DiffLine) {
+    if delta.flags().is_binary() {
+        match line.origin() {
+            'F' => patch.push_str(&String::from_utf8_lossy(line.content())),
+            // libgit2's "Binary files a/x and b/x differ" line
+            'B' => {
+                patch.push_str(BINARY_FILE_PLACEHOLDER);
+                patch.push('\n');
+            },
+            _ => {}
+        }
+        return;
+    }
+    
+    if matches!(line.origin(), '+' | '-' | ' ') {
+        patch.push(line.origin());
+    }
+    patch.push_str(&String::from_utf8_lossy(line.content()));
+}
+
+// The whole diff as unified patch text
+fn patch_text(diff: &git2::Diff) -> Result<String> {
+    let mut patch = String::new();
+    diff.print(DiffFormat::Patch, |delta: DiffDelta, _hunk: Option<DiffHunk>, line: DiffLine| -> bool {
+        push_patch_line(&mut patch, &delta, &line);
+        true
+    })?;
+    Ok(patch)
+}
+
 /// Generate a diff between two specific commits
 pub fn generate_commit_diff(
     repo_info: &RepositoryInfo,
     repo_path: &str,
     commit1_oid: &str,
     commit2_oid: &str,
+    settings: &DiffSettings,
 ) -> Result<RepositoryDiff> {
     // Open the repository
     let repo = Repository::open(repo_path)?;
@@ -67,38 +161,13 @@ pub fn generate_commit_diff(
     let tree2 = commit2.tree()?;
     
     // Generate the diff
-    let mut diff_opts = DiffOptions::new();
-    let diff = repo.diff_tree_to_tree(Some(&tree1), Some(&tree2), Some(&mut diff_opts))?;
-    
-    // Collect diff content and statistics
-    let mut diff_content = String::new();
-    let mut files_changed = 0;
-    let mut insertions = 0;
-    let mut deletions = 0;
-    
-    diff.print(DiffFormat::Patch, |delta: DiffDelta, _hunk: Option<DiffHunk>, line: DiffLine| -> bool {
-        // Count files (only count each file once)
-        if delta.status() != git2::Delta::Unmodified {
-            files_changed += 1;
-        }
-        
-        // Count insertions and deletions
-        match line.origin() {
-            '+' => insertions += 1,
-            '-' => deletions += 1,
-            _ => {}
-        }
-        
-        // Add line to diff content
-        diff_content.push_str(&format!("{}{}", line.origin(), String::from_utf8_lossy(line.content())));
-        true
-    })?;
+    let mut diff_opts = settings.diff_options();
+    let mut diff = repo.diff_tree_to_tree(Some(&tree1), Some(&tree2), Some(&mut diff_opts))?;
+    settings.find_renames(&mut diff)?;
     
-    let stats = DiffStats {
-        files_changed,
-        insertions,
-        deletions,
-    };
+    // Counts come from libgit2, the print callback runs per line and only builds the patch text
+    let stats = DiffStats::from_diff(&diff)?;
+    let diff_content = patch_text(&diff)?;
     
     Ok(RepositoryDiff::new(repo_info.name.clone(), diff_content, stats))
 }
@@ -109,6 +178,7 @@ pub fn generate_repository_diff(
     repo_path: &str,
     branch1: &str,
     branch2: &str,
+    settings: &DiffSettings,
 ) -> Result<RepositoryDiff> {
     // Open the repository
     let repo = Repository::open(repo_path)?;
@@ -122,46 +192,35 @@ pub fn generate_repository_diff(
     let tree2 = commit2.tree()?;
     
     // Generate the diff
-    let mut diff_opts = DiffOptions::new();
-    let diff = repo.diff_tree_to_tree(Some(&tree1), Some(&tree2), Some(&mut diff_opts))?;
+    let mut diff_opts = settings.diff_options();
+    let mut diff = repo.diff_tree_to_tree(Some(&tree1), Some(&tree2), Some(&mut diff_opts))?;
+    settings.find_renames(&mut diff)?;
     
     // Collect diff content and statistics
-    let mut diff_content = String::new();
-    let mut files_changed = 0;
-    let mut insertions = 0;
-    let mut deletions = 0;
+    let stats = DiffStats::from_diff(&diff)?;
+    let diff_content = patch_text(&diff)?;
     
-    diff.print(DiffFormat::Patch, |delta: DiffDelta, _hunk: Option<DiffHunk>, line: DiffLine| -> bool {
-        // Count files
-        if delta.status() != git2::Delta::Unmodified {
-            files_changed += 1;
-        }
-        
-        // Count insertions and deletions
-        match line.origin() {
-            '+' => insertions += 1,
-            '-' => deletions += 1,
-            _ => {}
-        }
-        
-        // Add line to diff content
-        diff_content.push_str(&format!("{}{}", line.origin(), String::from_utf8_lossy(line.content())));
-        true
-    })?;
+    Ok(RepositoryDiff::new(repo_info.name.clone(), diff_content, stats))
+}
+
+/// Files changed, insertions and deletions between two commits or branches.
+/// Much cheaper than `generate_repository_diff` for large diffs since no patch text is built.
+pub fn repository_diff_stats(repo_path: &str, ref1: &str, ref2: &str, settings: &DiffSettings) -> Result<DiffStats> {
+    let repo = Repository::open(repo_path)?;
     
-    let stats = DiffStats {
-        files_changed,
-        insertions,
-        deletions,
-    };
+    let tree1 = repo.revparse_single(ref1)?.peel_to_commit()?.tree()?;
+    let tree2 = repo.revparse_single(ref2)?.peel_to_commit()?.tree()?;
     
-    Ok(RepositoryDiff::new(repo_info.name.clone(), diff_content, stats))
+    let mut diff = repo.diff_tree_to_tree(Some(&tree1), Some(&tree2), Some(&mut settings.diff_options()))?;
+    settings.find_renames(&mut diff)?;
+    DiffStats::from_diff(&diff)
 }
 
 /// Generate a diff for a repository between the working directory and HEAD
 pub fn generate_working_directory_diff(
     repo_info: &RepositoryInfo,
     repo_path: &str,
+    settings: &DiffSettings,
 ) -> Result<RepositoryDiff> {
     // Open the repository
     let repo = Repository::open(repo_path)?;
@@ -172,62 +231,99 @@ pub fn generate_working_directory_diff(
     let head_tree = head_commit.tree()?;
     
     // Get the working directory diff
-    let mut diff_opts = DiffOptions::new();
-    let diff = repo.diff_tree_to_workdir_with_index(Some(&head_tree), Some(&mut diff_opts))?;
+    let mut diff_opts = settings.diff_options();
+    let mut diff = repo.diff_tree_to_workdir_with_index(Some(&head_tree), Some(&mut diff_opts))?;
+    settings.find_renames(&mut diff)?;
     
     // Collect diff content and statistics
-    let mut diff_content = String::new();
-    let mut files_changed = 0;
-    let mut insertions = 0;
-    let mut deletions = 0;
-    
-    diff.print(DiffFormat::Patch, |delta: DiffDelta, _hunk: Option<DiffHunk>, line: DiffLine| -> bool {
-        // Count files
-        if delta.status() != git2::Delta::Unmodified {
-            files_changed += 1;
-        }
-        
-        // Count insertions and deletions
-        match line.origin() {
-            '+' => insertions += 1,
-            '-' => deletions += 1,
-            _ => {}
-        }
-        
-        // Add line to diff content
-        diff_content.push_str(&format!("{}{}", line.origin(), String::from_utf8_lossy(line.content())));
-        true
-    })?;
-    
-    let stats = DiffStats {
-        files_changed,
-        insertions,
-        deletions,
-    };
+    let stats = DiffStats::from_diff(&diff)?;
+    let diff_content = patch_text(&diff)?;
     
     Ok(RepositoryDiff::new(repo_info.name.clone(), diff_content, stats))
 }
 
-/// Compare two repositories by comparing their latest commits
+/// Compare two repositories by diffing the HEAD trees of their local clones
 pub fn compare_repositories(repo1: &RepositoryInfo, repo2: &RepositoryInfo) -> Result<RepositoryDiff> {
-    // This is a simplified implementation
-    // In a real implementation, you would need to:
-    // 1. Clone both repositories locally if they're not already
-    // 2. Compare specific branches or commits
-    // 3. Generate a meaningful diff
-    
-    let diff_content = format!(
-        "Comparison between {} and {}\n\nRepository comparison functionality would compare the contents of both repositories.",
-        repo1.name, repo2.name
-    );
-    
-    let stats = DiffStats {
-        files_changed: 0,
-        insertions: 0,
-        deletions: 0,
-    };
-    
-    Ok(RepositoryDiff::new(repo1.name.clone(), diff_content, stats))
+    let repository1 = open_local_clone(repo1)?;
+    let repository2 = open_local_clone(repo2)?;
+    
+    let tree1 = repository1.head()?.peel_to_tree()?;
+    let tree2 = repository2.head()?.peel_to_tree()?;
+    
+    diff_trees_across(repo1.name.clone(), &repository1, &tree1, &repository2, &tree2)
+}
+
+/// Compare a branch of one local repository with a branch of another, e.g. a fork against its upstream.
+/// Only the two branch trees are diffed, so this works whether the repositories share history or not.
+pub fn compare_repo_branches(
+    repo1_path: &str,
+    branch1: &str,
+    repo2_path: &str,
+    branch2: &str,
+) -> Result<RepositoryDiff> {
+    let repository1 = Repository::open(repo1_path)?;
+    let repository2 = Repository::open(repo2_path)?;
+    
+    let tree1 = branch_tree(&repository1, repo1_path, branch1)?;
+    let tree2 = branch_tree(&repository2, repo2_path, branch2)?;
+    
+    let name = Path::new(repo1_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| repo1_path.to_string());
+    
+    diff_trees_across(name, &repository1, &tree1, &repository2, &tree2)
+}
+
+// Resolve a branch (or any revision) to its tree, naming the repository if it doesn't exist
+fn branch_tree<'r>(repo: &'r Repository, repo_path: &str, branch: &str) -> Result<git2::Tree<'r>> {
+    let object = repo
+        .revparse_single(branch)
+        .map_err(|_| anyhow::anyhow!("Branch '{}' not found in {}", branch, repo_path))?;
+    Ok(object.peel_to_tree()?)
+}
+
+// Diff a tree from one repository against a tree from another
+fn diff_trees_across(
+    name: String,
+    repository1: &Repository,
+    tree1: &git2::Tree,
+    repository2: &Repository,
+    tree2: &git2::Tree,
+) -> Result<RepositoryDiff> {
+    // Make the second repository's objects readable from the first so both trees can be diffed there.
+    // Unrelated histories need no special handling since no merge base is involved.
+    repository1
+        .odb()?
+        .add_disk_alternate(&repository2.path().join("objects").to_string_lossy())?;
+    let tree2 = repository1.find_tree(tree2.id())?;
+    
+    let mut diff_opts = DiffOptions::new();
+    let diff = repository1.diff_tree_to_tree(Some(tree1), Some(&tree2), Some(&mut diff_opts))?;
+    
+    let diff_content = patch_text(&diff)?;
+    let stats = DiffStats::from_diff(&diff)?;
+    
+    Ok(RepositoryDiff::new(name, diff_content, stats))
+}
+
+// Open the local clone of a repository, failing with a hint if it hasn't been cloned
+fn open_local_clone(repo_info: &RepositoryInfo) -> Result<Repository> {
+    if repo_info.local_path.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Repository '{}' is not cloned locally. Clone it from the Repositories tab before comparing.",
+            repo_info.name
+        ));
+    }
+    
+    Repository::open(&repo_info.local_path).map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to open the local clone of '{}' at {}: {}. Clone it again before comparing.",
+            repo_info.name,
+            repo_info.local_path,
+            e
+        )
+    })
 }
 
 /// Compare all repositories in a group
@@ -254,6 +350,27 @@ pub fn compare_group_repositories(
     Ok(diffs)
 }
 
+/// How far each cloned repository in a group has drifted from its HEAD, i.e. its uncommitted changes.
+/// Repositories that aren't cloned locally are left out.
+pub fn group_drift_summary(config: &RepoConfig, group_name: &str) -> Result<Vec<(String, DiffStats)>> {
+    if config.get_group(group_name).is_none() {
+        return Err(anyhow::anyhow!("Group '{}' not found", group_name));
+    }
+    
+    let mut drift = Vec::new();
+    
+    for repo_info in config.get_repositories_in_group(group_name) {
+        if repo_info.local_path.is_empty() {
+            continue;
+        }
+        
+        let diff = generate_working_directory_diff(repo_info, &repo_info.local_path, &DiffSettings::default())?;
+        drift.push((repo_info.name.clone(), diff.stats));
+    }
+    
+    Ok(drift)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -286,12 +403,10 @@ mod tests {
         let repo1 = RepositoryInfo::new("repo1".to_string(), "https://github.com/user/repo1.git".to_string());
         let repo2 = RepositoryInfo::new("repo2".to_string(), "https://github.com/user/repo2.git".to_string());
         
+        // Neither repository is cloned locally
         let result = compare_repositories(&repo1, &repo2);
-        assert!(result.is_ok());
-        
-        let diff = result.unwrap();
-        assert_eq!(diff.repository_name, "repo1");
-        assert!(diff.diff_content.contains("Comparison between repo1 and repo2"));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("not cloned locally"));
     }
     
     #[test]
@@ -2,6 +2,7 @@
 mod tests {
     use crate::core::repository::{RepositoryInfo, RepoConfig, RepositoryGroup};
     use crate::core::repository_comparison::*;
+    use crate::core::test_support::{init_temp_repo, commit_file};
 
     #[test]
     fn test_diff_stats_creation() {
@@ -30,12 +31,10 @@ mod tests {
         let repo1 = RepositoryInfo::new("repo1".to_string(), "https://github.com/user/repo1.git".to_string());
         let repo2 = RepositoryInfo::new("repo2".to_string(), "https://github.com/user/repo2.git".to_string());
         
+        // Neither repository is cloned locally
         let result = compare_repositories(&repo1, &repo2);
-        assert!(result.is_ok());
-        
-        let diff = result.unwrap();
-        assert_eq!(diff.repository_name, "repo1");
-        assert!(diff.diff_content.contains("Comparison between repo1 and repo2"));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("not cloned locally"));
     }
 
     #[test]
@@ -62,4 +61,267 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.unwrap().is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_compare_repositories_with_local_clones() {
+        let (dir1, repo1) = init_temp_repo();
+        commit_file(&repo1, "shared.txt", "same\n", "Add shared file");
+        commit_file(&repo1, "config.txt", "mode = fast\n", "Add config");
+        
+        let (dir2, repo2) = init_temp_repo();
+        commit_file(&repo2, "shared.txt", "same\n", "Add shared file");
+        commit_file(&repo2, "config.txt", "mode = safe\nretries = 3\n", "Add config");
+        
+        let info1 = RepositoryInfo::new("repo1".to_string(), "https://github.com/user/repo1.git".to_string())
+            .with_local_path(dir1.path().to_string_lossy().to_string());
+        let info2 = RepositoryInfo::new("repo2".to_string(), "https://github.com/user/repo2.git".to_string())
+            .with_local_path(dir2.path().to_string_lossy().to_string());
+        
+        let diff = compare_repositories(&info1, &info2).unwrap();
+        assert_eq!(diff.repository_name, "repo1");
+        assert_eq!(diff.stats.files_changed, 1);
+        assert_eq!(diff.stats.insertions, 2);
+        assert_eq!(diff.stats.deletions, 1);
+        assert!(diff.diff_content.contains("config.txt"));
+        assert!(diff.diff_content.contains("-mode = fast"));
+        assert!(diff.diff_content.contains("+mode = safe"));
+        assert!(!diff.diff_content.contains("shared.txt"));
+    }
+
+    #[test]
+    fn test_compare_repositories_missing_clone() {
+        let (dir1, repo1) = init_temp_repo();
+        commit_file(&repo1, "a.txt", "a\n", "Add a");
+        
+        let info1 = RepositoryInfo::new("repo1".to_string(), "https://github.com/user/repo1.git".to_string())
+            .with_local_path(dir1.path().to_string_lossy().to_string());
+        let info2 = RepositoryInfo::new("repo2".to_string(), "https://github.com/user/repo2.git".to_string());
+        
+        let error = compare_repositories(&info1, &info2).unwrap_err();
+        assert!(error.to_string().contains("'repo2' is not cloned locally"));
+    }
+
+    #[test]
+    fn test_repository_diff_stats_two_file_change() {
+        let (dir, repo) = init_temp_repo();
+        commit_file(&repo, "a.txt", "one\ntwo\n", "Add a");
+        commit_file(&repo, "b.txt", "keep\n", "Add b");
+        let base = repo.head().unwrap().peel_to_commit().unwrap().id().to_string();
+        
+        commit_file(&repo, "a.txt", "one\nTWO\nthree\n", "Change a");
+        commit_file(&repo, "c.txt", "new\nfile\n", "Add c");
+        
+        let path = dir.path().to_str().unwrap();
+        let stats = repository_diff_stats(path, &base, "HEAD", &DiffSettings::default()).unwrap();
+        assert_eq!(stats.files_changed, 2);
+        assert_eq!(stats.insertions, 4);
+        assert_eq!(stats.deletions, 1);
+        assert_eq!(stats.summary(), "2 files, +4 -1");
+        
+        assert!(repository_diff_stats(path, &base, "no-such-branch", &DiffSettings::default()).is_err());
+    }
+
+    #[test]
+    fn test_files_changed_counts_files_not_lines() {
+        let (dir, repo) = init_temp_repo();
+        let first = commit_file(&repo, "a.txt", "1\n2\n3\n", "Add a");
+        commit_file(&repo, "b.txt", "x\n", "Add b");
+        let base = repo.head().unwrap().peel_to_commit().unwrap().id();
+        
+        // Two files with many changed lines each
+        commit_file(&repo, "a.txt", "one\ntwo\nthree\nfour\n", "Rewrite a");
+        let head = commit_file(&repo, "b.txt", "y\nz\nw\n", "Rewrite b");
+        
+        let info = RepositoryInfo::new("repo".to_string(), "https://github.com/user/repo.git".to_string());
+        let path = dir.path().to_str().unwrap();
+        
+        let diff = generate_commit_diff(&info, path, &base.to_string(), &head.to_string(), &DiffSettings::default()).unwrap();
+        assert_eq!(diff.stats.files_changed, 2);
+        assert_eq!(diff.stats.insertions, 7);
+        assert_eq!(diff.stats.deletions, 4);
+        
+        let diff = generate_repository_diff(&info, path, &base.to_string(), "HEAD", &DiffSettings::default()).unwrap();
+        assert_eq!(diff.stats.files_changed, 2);
+        assert!(diff.diff_content.contains("+three"));
+        
+        // Only the file added since the first commit
+        let diff = generate_commit_diff(&info, path, &first.to_string(), &base.to_string(), &DiffSettings::default()).unwrap();
+        assert_eq!(diff.stats.files_changed, 1);
+        
+        crate::core::test_support::write_file(&repo, "a.txt", "changed\n");
+        crate::core::test_support::write_file(&repo, "b.txt", "changed too\n");
+        let diff = generate_working_directory_diff(&info, path, &DiffSettings::default()).unwrap();
+        assert_eq!(diff.stats.files_changed, 2);
+    }
+
+    #[test]
+    fn test_binary_changes_are_summarized() {
+        let (dir, repo) = init_temp_repo();
+        let before = commit_file(&repo, "logo.png", "\0PNG\x01old", "Add logo");
+        let after = commit_file(&repo, "logo.png", "\0PNG\x02new pixels", "Update logo");
+        
+        let info = RepositoryInfo::new("repo".to_string(), "https://github.com/user/repo.git".to_string());
+        let diff = generate_commit_diff(&info, dir.path().to_str().unwrap(), &before.to_string(), &after.to_string(), &DiffSettings::default()).unwrap();
+        assert_eq!(diff.stats.files_changed, 1);
+        assert!(diff.diff_content.contains(BINARY_FILE_PLACEHOLDER));
+        assert!(!diff.diff_content.contains("new pixels"));
+    }
+
+    #[test]
+    fn test_rename_threshold() {
+        let (dir, repo) = init_temp_repo();
+        let content = "one\ntwo\nthree\nfour\nfive\nsix\nseven\neight\n";
+        let before = commit_file(&repo, "old_name.txt", content, "Add file");
+        
+        // Move the file and change one of its eight lines
+        std::fs::remove_file(dir.path().join("old_name.txt")).unwrap();
+        let mut index = repo.index().unwrap();
+        index.remove_path(std::path::Path::new("old_name.txt")).unwrap();
+        index.write().unwrap();
+        let after = commit_file(&repo, "new_name.txt", &content.replace("eight", "8"), "Rename file");
+        
+        let info = RepositoryInfo::new("repo".to_string(), "https://github.com/user/repo.git".to_string());
+        let path = dir.path().to_str().unwrap();
+        
+        let diff = generate_commit_diff(&info, path, &before.to_string(), &after.to_string(), &DiffSettings::default()).unwrap();
+        assert_eq!(diff.stats.files_changed, 1);
+        assert!(diff.diff_content.contains("rename from old_name.txt"));
+        assert!(diff.diff_content.contains("rename to new_name.txt"));
+        
+        // Requiring identical content turns it back into a delete and an add
+        let strict = DiffSettings { rename_threshold: 100, ..Default::default() };
+        let diff = generate_commit_diff(&info, path, &before.to_string(), &after.to_string(), &strict).unwrap();
+        assert_eq!(diff.stats.files_changed, 2);
+        assert!(!diff.diff_content.contains("rename from"));
+    }
+
+    #[test]
+    fn test_ignore_whitespace() {
+        let (dir, repo) = init_temp_repo();
+        let before = commit_file(&repo, "code.rs", "fn main() {\n    run();\n}\n", "Add code");
+        let after = commit_file(&repo, "code.rs", "fn main() {  \n    run();\t\n}\n", "Add trailing whitespace");
+        
+        let info = RepositoryInfo::new("repo".to_string(), "https://github.com/user/repo.git".to_string());
+        let path = dir.path().to_str().unwrap();
+        let (before, after) = (before.to_string(), after.to_string());
+        
+        let diff = generate_commit_diff(&info, path, &before, &after, &DiffSettings::default()).unwrap();
+        assert_eq!((diff.stats.insertions, diff.stats.deletions), (2, 2));
+        
+        let ignoring = DiffSettings { ignore_whitespace: true, ..Default::default() };
+        let diff = generate_commit_diff(&info, path, &before, &after, &ignoring).unwrap();
+        assert_eq!((diff.stats.insertions, diff.stats.deletions), (0, 0));
+        let stats = repository_diff_stats(path, &before, &after, &ignoring).unwrap();
+        assert_eq!((stats.insertions, stats.deletions), (0, 0));
+    }
+
+    #[test]
+    fn test_context_lines() {
+        let (dir, repo) = init_temp_repo();
+        let lines: Vec<String> = (1..=30).map(|i| format!("line {}", i)).collect();
+        let before = commit_file(&repo, "long.txt", &(lines.join("\n") + "\n"), "Add long file");
+        
+        // Two changes far enough apart to be separate hunks with the default context
+        let mut changed = lines.clone();
+        changed[4] = "line five".to_string();
+        changed[24] = "line twenty-five".to_string();
+        let after = commit_file(&repo, "long.txt", &(changed.join("\n") + "\n"), "Change two lines");
+        
+        let info = RepositoryInfo::new("repo".to_string(), "https://github.com/user/repo.git".to_string());
+        let path = dir.path().to_str().unwrap();
+        let (before, after) = (before.to_string(), after.to_string());
+        let patch = |context_lines: u32| {
+            let settings = DiffSettings { context_lines, ..Default::default() };
+            generate_commit_diff(&info, path, &before, &after, &settings).unwrap().diff_content
+        };
+        let hunks = |patch: &str| patch.lines().filter(|line| line.starts_with("@@")).count();
+        
+        let (tight, default, wide) = (patch(0), patch(3), patch(20));
+        assert!(tight.len() < default.len());
+        assert!(default.len() < wide.len());
+        // The hunk header ends with the line before the hunk, so look for it as a context line
+        let has_context = |patch: &str, context: &str| patch.lines().any(|line| line.strip_prefix(' ') == Some(context));
+        assert!(!has_context(&tight, "line 4"));
+        assert!(has_context(&default, "line 4"));
+        assert_eq!((hunks(&tight), hunks(&default), hunks(&wide)), (2, 2, 1));
+    }
+
+    #[test]
+    fn test_compare_repo_branches_across_forks() {
+        // Upstream and a fork that was cloned from it and then diverged
+        let (upstream_dir, upstream) = init_temp_repo();
+        commit_file(&upstream, "shared.txt", "same\n", "Add shared file");
+        commit_file(&upstream, "config.txt", "mode = fast\n", "Add config");
+        
+        let fork_dir = tempfile::TempDir::new().unwrap();
+        let fork = git2::Repository::clone(upstream_dir.path().to_str().unwrap(), fork_dir.path()).unwrap();
+        fork.config().unwrap().set_str("user.name", "Test User").unwrap();
+        fork.config().unwrap().set_str("user.email", "test@example.com").unwrap();
+        commit_file(&fork, "config.txt", "mode = safe\n", "Switch to safe mode");
+        
+        let branch = upstream.head().unwrap().shorthand().unwrap().to_string();
+        let upstream_path = upstream_dir.path().to_str().unwrap();
+        let fork_path = fork_dir.path().to_str().unwrap();
+        
+        let diff = compare_repo_branches(upstream_path, &branch, fork_path, &branch).unwrap();
+        assert_eq!(diff.stats.files_changed, 1);
+        assert_eq!((diff.stats.insertions, diff.stats.deletions), (1, 1));
+        assert!(diff.diff_content.contains("-mode = fast"));
+        assert!(diff.diff_content.contains("+mode = safe"));
+        assert!(!diff.diff_content.contains("shared.txt"));
+        
+        let error = compare_repo_branches(upstream_path, &branch, fork_path, "no-such-branch").unwrap_err();
+        assert!(error.to_string().contains("Branch 'no-such-branch' not found"));
+    }
+
+    #[test]
+    fn test_compare_repo_branches_unrelated_histories() {
+        let (dir1, repo1) = init_temp_repo();
+        commit_file(&repo1, "a.txt", "a\n", "Add a");
+        let (dir2, repo2) = init_temp_repo();
+        commit_file(&repo2, "b.txt", "b\n", "Add b");
+        
+        let branch = repo1.head().unwrap().shorthand().unwrap().to_string();
+        let diff = compare_repo_branches(
+            dir1.path().to_str().unwrap(),
+            &branch,
+            dir2.path().to_str().unwrap(),
+            &branch,
+        ).unwrap();
+        assert_eq!(diff.stats.files_changed, 2);
+        assert!(diff.diff_content.contains("-a"));
+        assert!(diff.diff_content.contains("+b"));
+    }
+
+    #[test]
+    fn test_group_drift_summary() {
+        let (clean_dir, clean_repo) = init_temp_repo();
+        commit_file(&clean_repo, "a.txt", "a\n", "Add a");
+        
+        let (dirty_dir, dirty_repo) = init_temp_repo();
+        commit_file(&dirty_repo, "a.txt", "a\n", "Add a");
+        std::fs::write(dirty_dir.path().join("a.txt"), "a\nmore\n").unwrap();
+        
+        let mut config = RepoConfig::new();
+        let mut group = RepositoryGroup::new("team".to_string(), "Team repos".to_string());
+        for (name, dir) in [("clean", &clean_dir), ("dirty", &dirty_dir)] {
+            config.add_repository(
+                RepositoryInfo::new(name.to_string(), format!("https://github.com/user/{}.git", name))
+                    .with_local_path(dir.path().to_string_lossy().to_string()),
+            );
+            group.add_repository(name.to_string());
+        }
+        config.add_repository(RepositoryInfo::new("not-cloned".to_string(), "https://github.com/user/not-cloned.git".to_string()));
+        group.add_repository("not-cloned".to_string());
+        config.add_group(group);
+        
+        let drift = group_drift_summary(&config, "team").unwrap();
+        let names: Vec<&str> = drift.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["clean", "dirty"]);
+        assert_eq!(drift[0].1.files_changed, 0);
+        assert_eq!(drift[1].1.files_changed, 1);
+        assert_eq!(drift[1].1.insertions, 1);
+        
+        assert!(group_drift_summary(&config, "no-such-group").is_err());
+    }
+}
@@ -1,11 +1,14 @@
 use crate::core::repository::{RepoConfig, RepositoryInfo};
 use git2::Repository;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RepositoryStats {
     pub name: String,
+    #[serde(default)]
+    pub available: bool, // False when the repository could not be opened locally
     pub total_commits: usize,
     pub total_files: usize,
     pub total_lines: usize,
@@ -13,6 +16,12 @@ pub struct RepositoryStats {
     pub contributors: Vec<String>,
     pub branches: Vec<String>,
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub git_size: u64, // Bytes used by the .git directory
+    #[serde(default)]
+    pub working_tree_size: u64, // Bytes used by the checked out files
+    #[serde(default)]
+    pub languages: Vec<(String, usize)>, // Text files in HEAD per extension, most common first
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -34,10 +43,44 @@ pub struct OverallStats {
     pub group_stats: Vec<GroupStats>,
 }
 
+/// Time span that commits are grouped into for activity charts
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityBucket {
+    Day,
+    Week,
+    Month,
+}
+
+impl ActivityBucket {
+    /// Timestamp of the start (UTC) of the bucket containing `timestamp`.
+    /// Weeks start on Monday.
+    pub fn bucket_start(&self, timestamp: i64) -> i64 {
+        const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+        let day_start = timestamp - timestamp.rem_euclid(SECONDS_PER_DAY);
+        
+        match self {
+            ActivityBucket::Day => day_start,
+            ActivityBucket::Week => {
+                // 1970-01-01 was a Thursday, three days after a Monday
+                let days_since_monday = (day_start / SECONDS_PER_DAY + 3).rem_euclid(7);
+                day_start - days_since_monday * SECONDS_PER_DAY
+            },
+            ActivityBucket::Month => {
+                let Some(datetime) = chrono::DateTime::from_timestamp(timestamp, 0) else {
+                    return day_start;
+                };
+                let day_of_month = chrono::Datelike::day(&datetime) as i64;
+                day_start - (day_of_month - 1) * SECONDS_PER_DAY
+            }
+        }
+    }
+}
+
 impl RepositoryStats {
     pub fn new(name: String) -> Self {
         Self {
             name,
+            available: false,
             total_commits: 0,
             total_files: 0,
             total_lines: 0,
@@ -45,6 +88,9 @@ impl RepositoryStats {
             contributors: Vec::new(),
             branches: Vec::new(),
             tags: Vec::new(),
+            git_size: 0,
+            working_tree_size: 0,
+            languages: Vec::new(),
         }
     }
 }
@@ -74,8 +120,20 @@ impl OverallStats {
     }
 }
 
+/// Line counting stops once this many lines have been counted, so huge repositories don't stall
+pub const DEFAULT_MAX_COUNTED_LINES: usize = 1_000_000;
+
 /// Collect statistics for a single repository
 pub fn collect_repository_stats(repo_info: &RepositoryInfo, repo_path: &str) -> Result<RepositoryStats> {
+    collect_repository_stats_with_line_limit(repo_info, repo_path, DEFAULT_MAX_COUNTED_LINES)
+}
+
+/// Collect statistics for a single repository, counting at most `max_lines` lines of text
+pub fn collect_repository_stats_with_line_limit(
+    repo_info: &RepositoryInfo,
+    repo_path: &str,
+    max_lines: usize,
+) -> Result<RepositoryStats> {
     let mut stats = RepositoryStats::new(repo_info.name.clone());
     
     // Try to open the repository
@@ -86,6 +144,10 @@ pub fn collect_repository_stats(repo_info: &RepositoryInfo, repo_path: &str) ->
             return Ok(stats);
         }
     };
+    stats.available = true;
+    stats.git_size = repository_size(repo_path).unwrap_or(0);
+    stats.working_tree_size = working_tree_size(repo_path).unwrap_or(0);
+    stats.languages = language_breakdown(&repo).unwrap_or_default();
     
     // Collect commit history
     if let Ok(mut revwalk) = repo.revwalk() {
@@ -149,20 +211,26 @@ pub fn collect_repository_stats(repo_info: &RepositoryInfo, repo_path: &str) ->
             .collect();
     }
     
-    // Try to count files (this is a simplified approach)
+    // Count files and lines of text in the HEAD tree
     if let Ok(head) = repo.head() {
         if let Ok(head_commit) = head.peel_to_commit() {
             if let Ok(tree) = head_commit.tree() {
                 let mut file_count = 0;
+                let mut line_count = 0;
                 tree.walk(git2::TreeWalkMode::PreOrder, |_root, entry| {
                     // Count only files, not directories
                     if entry.kind() == Some(git2::ObjectType::Blob) {
                         file_count += 1;
+                        
+                        if line_count < max_lines && let Ok(blob) = repo.find_blob(entry.id()) {
+                            line_count += count_text_lines(blob.content());
+                        }
                     }
                     git2::TreeWalkResult::Ok
                 }).ok();
                 
                 stats.total_files = file_count;
+                stats.total_lines = line_count.min(max_lines);
             }
         }
     }
@@ -170,16 +238,155 @@ pub fn collect_repository_stats(repo_info: &RepositoryInfo, repo_path: &str) ->
     Ok(stats)
 }
 
+/// Count the text files in the HEAD tree by extension, most common first and ties by name.
+/// Binary files are skipped and files without an extension are counted as "(none)".
+pub fn language_breakdown(repo: &Repository) -> Result<Vec<(String, usize)>> {
+    let tree = repo.head()?.peel_to_tree()?;
+    let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    
+    tree.walk(git2::TreeWalkMode::PreOrder, |_root, entry| {
+        // Directories are trees and submodules are commits, only files are blobs
+        if entry.kind() != Some(git2::ObjectType::Blob) {
+            return git2::TreeWalkResult::Ok;
+        }
+        let is_binary = repo.find_blob(entry.id()).map(|blob| blob.is_binary()).unwrap_or(true);
+        if !is_binary {
+            let extension = entry
+                .name()
+                .and_then(|name| Path::new(name).extension())
+                .and_then(|extension| extension.to_str())
+                .map(str::to_lowercase)
+                .unwrap_or_else(|| "(none)".to_string());
+            *counts.entry(extension).or_insert(0) += 1;
+        }
+        git2::TreeWalkResult::Ok
+    })?;
+    
+    let mut breakdown: Vec<(String, usize)> = counts.into_iter().collect();
+    breakdown.sort_by(|(a_extension, a_count), (b_extension, b_count)| {
+        b_count.cmp(a_count).then_with(|| a_extension.cmp(b_extension))
+    });
+    Ok(breakdown)
+}
+
+/// Format a breakdown as "rs: 42, toml: 3, md: 5"
+pub fn format_language_breakdown(breakdown: &[(String, usize)]) -> String {
+    breakdown
+        .iter()
+        .map(|(extension, count)| format!("{}: {}", extension, count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Disk space used by the `.git` directory of a repository, in bytes
+pub fn repository_size(repo_path: &str) -> Result<u64> {
+    let repo = Repository::open(repo_path)?;
+    directory_size(repo.path(), false)
+}
+
+/// Disk space used by the checked out files of a repository, without `.git`, in bytes
+pub fn working_tree_size(repo_path: &str) -> Result<u64> {
+    let repo = Repository::open(repo_path)?;
+    let workdir = repo.workdir().ok_or_else(|| anyhow!("{} is a bare repository", repo_path))?;
+    directory_size(workdir, true)
+}
+
+// Sum the sizes of the files below `path` without following symlinks, optionally skipping
+// the `.git` directories of the working tree and its submodules
+fn directory_size(path: &Path, skip_git_dirs: bool) -> Result<u64> {
+    let mut size = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.path().symlink_metadata()?;
+        if metadata.is_dir() {
+            if !(skip_git_dirs && entry.file_name() == ".git") {
+                size += directory_size(&entry.path(), skip_git_dirs)?;
+            }
+        } else {
+            size += metadata.len();
+        }
+    }
+    Ok(size)
+}
+
+/// Format a byte count for display, e.g. "1.5 MB"
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Describe how long ago a unix timestamp was, e.g. "3 days ago"
+pub fn format_relative_time(timestamp: i64) -> String {
+    format_relative_time_from(timestamp, chrono::Utc::now().timestamp())
+}
+
+/// Same as `format_relative_time` but relative to `now` instead of the current time
+pub fn format_relative_time_from(timestamp: i64, now: i64) -> String {
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+    const MONTH: i64 = 30 * DAY;
+    const YEAR: i64 = 365 * DAY;
+    
+    let elapsed = now - timestamp;
+    // Clock skew between machines can put commits slightly in the future
+    if elapsed < MINUTE {
+        return "just now".to_string();
+    }
+    
+    let (amount, unit) = if elapsed < HOUR {
+        (elapsed / MINUTE, "minute")
+    } else if elapsed < DAY {
+        (elapsed / HOUR, "hour")
+    } else if elapsed < MONTH {
+        (elapsed / DAY, "day")
+    } else if elapsed < YEAR {
+        (elapsed / MONTH, "month")
+    } else {
+        (elapsed / YEAR, "year")
+    };
+    
+    if amount == 1 {
+        format!("1 {} ago", unit)
+    } else {
+        format!("{} {}s ago", amount, unit)
+    }
+}
+
+// Count the lines of a text blob, binary blobs (containing a NUL byte) count as zero
+fn count_text_lines(content: &[u8]) -> usize {
+    if content.is_empty() || content.contains(&0) {
+        return 0;
+    }
+    
+    // A trailing line without a final newline still counts
+    let newlines = content.iter().filter(|&&byte| byte == b'\n').count();
+    if content.ends_with(b"\n") { newlines } else { newlines + 1 }
+}
+
 /// Collect statistics for all repositories in a configuration
 pub fn collect_overall_stats(config: &RepoConfig) -> Result<OverallStats> {
     let mut overall_stats = OverallStats::new();
     
     // Collect stats for each repository
     for repo_info in &config.repositories {
-        // For now, we'll assume repositories are in the current directory
-        // In a real implementation, you might want to store repository paths
-        let repo_path = ".";
-        if let Ok(repo_stats) = collect_repository_stats(repo_info, repo_path) {
+        // Repositories that aren't cloned locally are reported as not available
+        let repo_stats = if repo_info.local_path.is_empty() {
+            Ok(RepositoryStats::new(repo_info.name.clone()))
+        } else {
+            collect_repository_stats(repo_info, &repo_info.local_path)
+        };
+        if let Ok(repo_stats) = repo_stats {
             overall_stats.total_commits += repo_stats.total_commits;
             overall_stats.repository_stats.push(repo_stats);
         }
@@ -202,18 +409,21 @@ pub fn collect_overall_stats(config: &RepoConfig) -> Result<OverallStats> {
         let mut group_stats = GroupStats::new(group.name.clone());
         group_stats.total_repositories = group.repository_names.len();
         
-        // Calculate total commits for repositories in this group
+        // Calculate totals for the repositories in this group that are available locally
         let mut group_commits = 0;
         let mut repo_count = 0;
+        let mut group_contributors = std::collections::HashSet::new();
         
         for repo_name in &group.repository_names {
-            if let Some(repo_stats) = overall_stats.repository_stats.iter().find(|r| &r.name == repo_name) {
+            if let Some(repo_stats) = overall_stats.repository_stats.iter().find(|r| &r.name == repo_name && r.available) {
                 group_commits += repo_stats.total_commits;
                 repo_count += 1;
+                group_contributors.extend(repo_stats.contributors.iter().cloned());
             }
         }
         
         group_stats.total_commits = group_commits;
+        group_stats.total_contributors = group_contributors.len();
         if repo_count > 0 {
             group_stats.avg_commits_per_repo = group_commits as f64 / repo_count as f64;
         }
@@ -222,4 +432,135 @@ pub fn collect_overall_stats(config: &RepoConfig) -> Result<OverallStats> {
     }
     
     Ok(overall_stats)
-}
\ No newline at end of file
+}
+
+/// Write the statistics to `path` as pretty-printed JSON
+pub fn export_stats_json(stats: &OverallStats, path: &str) -> Result<()> {
+    std::fs::write(path, stats_to_json(stats)?)?;
+    Ok(())
+}
+
+/// Serialize statistics as the same pretty JSON that `export_stats_json` writes
+pub fn stats_to_json(stats: &OverallStats) -> Result<String> {
+    Ok(serde_json::to_string_pretty(stats)?)
+}
+
+/// Write one CSV row per repository to `path`
+pub fn export_stats_csv(stats: &OverallStats, path: &str) -> Result<()> {
+    let mut csv = String::from("name,commits,files,contributors,last_commit_date\n");
+    
+    for repo_stats in &stats.repository_stats {
+        let last_commit_date = repo_stats
+            .last_commit_date
+            .and_then(|date| chrono::DateTime::from_timestamp(date, 0))
+            .map(|datetime| datetime.to_rfc3339())
+            .unwrap_or_default();
+        
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            escape_csv_field(&repo_stats.name),
+            repo_stats.total_commits,
+            repo_stats.total_files,
+            repo_stats.contributors.len(),
+            last_commit_date
+        ));
+    }
+    
+    std::fs::write(path, csv)?;
+    Ok(())
+}
+
+// Quote a CSV field if it contains a separator, quote or line break
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Count the commits reachable from HEAD per time bucket, using the author timestamps.
+/// Returns `(bucket_start, commit_count)` pairs sorted by time.
+pub fn commit_activity(repo: &Repository, bucket: ActivityBucket) -> Result<Vec<(i64, usize)>> {
+    let mut activity = std::collections::BTreeMap::new();
+    
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_head()?;
+    
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        let bucket_start = bucket.bucket_start(commit.author().when().seconds());
+        *activity.entry(bucket_start).or_insert(0) += 1;
+    }
+    
+    Ok(activity.into_iter().collect())
+}
+
+/// Combine the commit activity of every locally cloned repository in the configuration
+pub fn combined_commit_activity(config: &RepoConfig, bucket: ActivityBucket) -> Vec<(i64, usize)> {
+    let mut activity = std::collections::BTreeMap::new();
+    
+    for repo_info in config.repositories.iter().filter(|r| !r.local_path.is_empty()) {
+        let Ok(repo) = Repository::open(&repo_info.local_path) else {
+            continue;
+        };
+        
+        if let Ok(series) = commit_activity(&repo, bucket) {
+            for (bucket_start, count) in series {
+                *activity.entry(bucket_start).or_insert(0) += count;
+            }
+        }
+    }
+    
+    activity.into_iter().collect()
+}
+
+/// List local branches whose tip commit is older than `older_than_days`, with the
+/// timestamp of their last commit. The oldest branches come first.
+pub fn stale_branches(repo: &Repository, older_than_days: i64) -> Result<Vec<(String, i64)>> {
+    let cutoff = chrono::Utc::now().timestamp() - older_than_days * 24 * 60 * 60;
+    let mut stale = Vec::new();
+    
+    for branch in repo.branches(Some(git2::BranchType::Local))? {
+        let (branch, _) = branch?;
+        let Some(name) = branch.name()?.map(|name| name.to_string()) else {
+            continue;
+        };
+        
+        let last_commit_date = branch.get().peel_to_commit()?.time().seconds();
+        if last_commit_date < cutoff {
+            stale.push((name, last_commit_date));
+        }
+    }
+    
+    stale.sort_by_key(|(_, last_commit_date)| *last_commit_date);
+    Ok(stale)
+}
+
+/// A submodule of a repository and whether it is ready to use
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubmoduleInfo {
+    pub name: String,
+    pub path: String,
+    /// Checked out in the working tree, i.e. `git submodule update --init` has run
+    pub initialized: bool,
+    /// The checked out commit is the one the superproject records in HEAD
+    pub up_to_date: bool,
+}
+
+/// List the submodules declared in the repository, with how far each is set up
+pub fn submodule_status(repo: &Repository) -> Result<Vec<SubmoduleInfo>> {
+    let mut submodules = Vec::new();
+    for submodule in repo.submodules()? {
+        let initialized = submodule.open().is_ok();
+        let up_to_date = initialized && submodule.workdir_id().is_some() && submodule.workdir_id() == submodule.head_id();
+        submodules.push(SubmoduleInfo {
+            name: submodule.name().unwrap_or_default().to_string(),
+            path: submodule.path().to_string_lossy().to_string(),
+            initialized,
+            up_to_date,
+        });
+    }
+    submodules.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(submodules)
+}
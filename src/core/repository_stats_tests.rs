@@ -1,7 +1,37 @@
 #[cfg(test)]
 mod tests {
     use crate::core::repository::{RepoConfig, RepositoryInfo, RepositoryGroup};
-    use crate::core::repository_stats::{collect_overall_stats, collect_repository_stats, OverallStats, RepositoryStats, GroupStats};
+    use crate::core::repository_stats::{
+        collect_overall_stats, collect_repository_stats, collect_repository_stats_with_line_limit,
+        export_stats_csv, export_stats_json, commit_activity, combined_commit_activity, stale_branches,
+        submodule_status, repository_size, working_tree_size, format_size, format_relative_time_from,
+        language_breakdown, format_language_breakdown, ActivityBucket, SubmoduleInfo, OverallStats, RepositoryStats, GroupStats
+    };
+    use crate::core::test_support::{init_temp_repo, commit_file, commit_file_at};
+    use tempfile::TempDir;
+    
+    fn sample_stats() -> OverallStats {
+        let mut repo_stats = RepositoryStats::new("repo, one".to_string());
+        repo_stats.total_commits = 12;
+        repo_stats.total_files = 34;
+        repo_stats.last_commit_date = Some(1_700_000_000);
+        repo_stats.contributors = vec!["Alice".to_string(), "Bob".to_string()];
+        repo_stats.branches = vec!["main".to_string()];
+        
+        let mut group_stats = GroupStats::new("group".to_string());
+        group_stats.total_repositories = 1;
+        group_stats.total_commits = 12;
+        group_stats.avg_commits_per_repo = 12.0;
+        
+        let mut stats = OverallStats::new();
+        stats.total_repositories = 2;
+        stats.total_groups = 1;
+        stats.total_commits = 12;
+        stats.total_contributors = 2;
+        stats.repository_stats = vec![repo_stats, RepositoryStats::new("repo2".to_string())];
+        stats.group_stats = vec![group_stats];
+        stats
+    }
     
     #[test]
     fn test_repository_stats_creation() {
@@ -53,6 +83,7 @@ mod tests {
         let stats = result.unwrap();
         assert_eq!(stats.name, "test_repo");
         assert_eq!(stats.total_commits, 0);
+        assert!(!stats.available);
     }
     
     #[test]
@@ -142,4 +173,282 @@ mod tests {
         assert_eq!(group_stats.name, "test_group");
         assert_eq!(group_stats.total_repositories, 2);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_export_stats_json_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("stats.json");
+        let stats = sample_stats();
+        
+        export_stats_json(&stats, path.to_str().unwrap()).unwrap();
+        
+        let content = std::fs::read_to_string(&path).unwrap();
+        let restored: OverallStats = serde_json::from_str(&content).unwrap();
+        assert_eq!(
+            serde_json::to_value(&restored).unwrap(),
+            serde_json::to_value(&stats).unwrap()
+        );
+        assert_eq!(restored.repository_stats[0].name, "repo, one");
+        assert_eq!(restored.group_stats[0].avg_commits_per_repo, 12.0);
+    }
+    
+    #[test]
+    fn test_export_stats_csv_rows() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("stats.csv");
+        
+        export_stats_csv(&sample_stats(), path.to_str().unwrap()).unwrap();
+        
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines, vec![
+            "name,commits,files,contributors,last_commit_date",
+            "\"repo, one\",12,34,2,2023-11-14T22:13:20+00:00",
+            "repo2,0,0,0,",
+        ]);
+    }
+    
+    #[test]
+    fn test_collect_overall_stats_uses_local_path() {
+        let (dir, repo) = init_temp_repo();
+        commit_file(&repo, "a.txt", "a\n", "Add a");
+        commit_file(&repo, "b.txt", "b\n", "Add b");
+        
+        let mut config = RepoConfig::new();
+        config.add_repository(
+            RepositoryInfo::new("cloned".to_string(), "https://github.com/user/cloned.git".to_string())
+                .with_local_path(dir.path().to_string_lossy().to_string())
+        );
+        config.add_repository(
+            RepositoryInfo::new("remote_only".to_string(), "https://github.com/user/remote.git".to_string())
+        );
+        
+        let mut group = RepositoryGroup::new("all".to_string(), "All repositories".to_string());
+        group.add_repository("cloned".to_string());
+        group.add_repository("remote_only".to_string());
+        config.add_group(group);
+        
+        let stats = collect_overall_stats(&config).unwrap();
+        assert_eq!(stats.total_commits, 2);
+        assert_eq!(stats.total_contributors, 1);
+        
+        let cloned = &stats.repository_stats[0];
+        assert!(cloned.available);
+        assert_eq!(cloned.total_commits, 2);
+        assert_eq!(cloned.total_files, 2);
+        assert_eq!(cloned.contributors, vec!["Test User".to_string()]);
+        
+        assert!(!stats.repository_stats[1].available);
+        
+        // Only the cloned repository counts towards the group averages
+        let group_stats = &stats.group_stats[0];
+        assert_eq!(group_stats.total_commits, 2);
+        assert_eq!(group_stats.avg_commits_per_repo, 2.0);
+        assert_eq!(group_stats.total_contributors, 1);
+    }
+    
+    #[test]
+    fn test_collect_repository_stats_counts_lines() {
+        let (dir, repo) = init_temp_repo();
+        commit_file(&repo, "three.txt", "one\ntwo\nthree\n", "Add text file");
+        commit_file(&repo, "no_newline.txt", "first\nsecond", "Add file without final newline");
+        
+        // Binary blobs are skipped
+        std::fs::write(dir.path().join("image.bin"), [0u8, 1, 2, b'\n', 3]).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("image.bin")).unwrap();
+        index.write().unwrap();
+        crate::core::git_operations::commit_changes(&repo, "Add binary file").unwrap();
+        
+        let repo_info = RepositoryInfo::new("lines".to_string(), "https://github.com/user/lines.git".to_string());
+        let stats = collect_repository_stats(&repo_info, dir.path().to_str().unwrap()).unwrap();
+        
+        assert_eq!(stats.total_files, 3);
+        assert_eq!(stats.total_lines, 5);
+    }
+    
+    #[test]
+    fn test_collect_repository_stats_line_limit() {
+        let (dir, repo) = init_temp_repo();
+        commit_file(&repo, "a.txt", "1\n2\n3\n4\n", "Add a");
+        commit_file(&repo, "b.txt", "1\n2\n3\n4\n", "Add b");
+        
+        let repo_info = RepositoryInfo::new("capped".to_string(), "https://github.com/user/capped.git".to_string());
+        let stats = collect_repository_stats_with_line_limit(&repo_info, dir.path().to_str().unwrap(), 6).unwrap();
+        
+        assert_eq!(stats.total_files, 2);
+        assert_eq!(stats.total_lines, 6);
+    }
+    
+    // 2024-03-14 (a Thursday) 09:00 UTC
+    const THURSDAY_MORNING: i64 = 1_710_406_800;
+    const DAY: i64 = 24 * 60 * 60;
+    
+    #[test]
+    fn test_commit_activity_same_day_single_bucket() {
+        let (_dir, repo) = init_temp_repo();
+        commit_file_at(&repo, "a.txt", "a\n", "Morning commit", THURSDAY_MORNING);
+        commit_file_at(&repo, "b.txt", "b\n", "Evening commit", THURSDAY_MORNING + 10 * 60 * 60);
+        
+        let activity = commit_activity(&repo, ActivityBucket::Day).unwrap();
+        assert_eq!(activity, vec![(THURSDAY_MORNING - 9 * 60 * 60, 2)]);
+    }
+    
+    #[test]
+    fn test_commit_activity_week_and_month_buckets() {
+        let (_dir, repo) = init_temp_repo();
+        commit_file_at(&repo, "a.txt", "a\n", "Thursday", THURSDAY_MORNING);
+        commit_file_at(&repo, "b.txt", "b\n", "Next Monday", THURSDAY_MORNING + 4 * DAY);
+        commit_file_at(&repo, "c.txt", "c\n", "Next month", THURSDAY_MORNING + 20 * DAY);
+        
+        let midnight = THURSDAY_MORNING - 9 * 60 * 60;
+        let weekly = commit_activity(&repo, ActivityBucket::Week).unwrap();
+        assert_eq!(weekly, vec![
+            (midnight - 3 * DAY, 1),
+            (midnight + 4 * DAY, 1),
+            (midnight + 18 * DAY, 1),
+        ]);
+        
+        // 2024-03-01 and 2024-04-01
+        let monthly = commit_activity(&repo, ActivityBucket::Month).unwrap();
+        assert_eq!(monthly, vec![(1_709_251_200, 2), (1_711_929_600, 1)]);
+    }
+    
+    #[test]
+    fn test_combined_commit_activity_skips_uncloned_repositories() {
+        let (dir, repo) = init_temp_repo();
+        commit_file_at(&repo, "a.txt", "a\n", "Commit", THURSDAY_MORNING);
+        
+        let mut config = RepoConfig::new();
+        config.add_repository(
+            RepositoryInfo::new("cloned".to_string(), "https://github.com/user/cloned.git".to_string())
+                .with_local_path(dir.path().to_string_lossy().to_string())
+        );
+        config.add_repository(
+            RepositoryInfo::new("remote_only".to_string(), "https://github.com/user/remote.git".to_string())
+        );
+        
+        let activity = combined_commit_activity(&config, ActivityBucket::Day);
+        assert_eq!(activity, vec![(THURSDAY_MORNING - 9 * 60 * 60, 1)]);
+    }
+    
+    #[test]
+    fn test_stale_branches_older_than_threshold() {
+        let (_dir, repo) = init_temp_repo();
+        let now = chrono::Utc::now().timestamp();
+        
+        let old_commit = commit_file_at(&repo, "a.txt", "a\n", "Old work", now - 60 * DAY);
+        repo.branch("old-feature", &repo.find_commit(old_commit).unwrap(), false).unwrap();
+        
+        let recent_commit = commit_file_at(&repo, "b.txt", "b\n", "Recent work", now - DAY);
+        repo.branch("recent-feature", &repo.find_commit(recent_commit).unwrap(), false).unwrap();
+        
+        let stale = stale_branches(&repo, 30).unwrap();
+        assert_eq!(stale, vec![("old-feature".to_string(), now - 60 * DAY)]);
+        
+        // With a longer threshold nothing is stale
+        assert!(stale_branches(&repo, 90).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_declared_submodule_is_uninitialized() {
+        let (_dir, repo) = init_temp_repo();
+        commit_file(&repo, "README.md", "hello\n", "Initial commit");
+        assert!(submodule_status(&repo).unwrap().is_empty());
+        
+        commit_file(
+            &repo,
+            ".gitmodules",
+            "[submodule \"vendor/lib\"]\n\tpath = vendor/lib\n\turl = https://github.com/user/lib.git\n",
+            "Add lib submodule",
+        );
+        
+        assert_eq!(submodule_status(&repo).unwrap(), vec![SubmoduleInfo {
+            name: "vendor/lib".to_string(),
+            path: "vendor/lib".to_string(),
+            initialized: false,
+            up_to_date: false,
+        }]);
+    }
+
+    #[test]
+    fn test_repository_and_working_tree_size() {
+        let (dir, repo) = init_temp_repo();
+        commit_file(&repo, "README.md", "hello\n", "Initial commit");
+        commit_file(&repo, "src/main.rs", "fn main() {}\n", "Add main");
+        let path = dir.path().to_string_lossy().to_string();
+        
+        // Objects, refs, the index and config, but nothing near a large repository
+        let git_size = repository_size(&path).unwrap();
+        assert!(git_size > 100 && git_size < 10 * 1024 * 1024, "implausible .git size: {}", git_size);
+        
+        // Exactly the two files, .git is not counted
+        assert_eq!(working_tree_size(&path).unwrap(), 6 + 13);
+        
+        let missing = dir.path().join("missing").to_string_lossy().to_string();
+        assert!(repository_size(&missing).is_err());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn test_language_breakdown_counts_text_files_by_extension() {
+        let (dir, repo) = init_temp_repo();
+        commit_file(&repo, "src/main.rs", "fn main() {}\n", "Add main");
+        commit_file(&repo, "src/lib.rs", "pub mod core;\n", "Add lib");
+        commit_file(&repo, "src/core/mod.rs", "pub mod repository;\n", "Add core");
+        commit_file(&repo, "Cargo.toml", "[package]\n", "Add manifest");
+        commit_file(&repo, "README.md", "hello\n", "Add readme");
+        commit_file(&repo, "docs/USAGE.MD", "usage\n", "Add usage");
+        commit_file(&repo, "LICENSE", "MIT\n", "Add license");
+        
+        // Binary content is left out whatever its extension
+        std::fs::write(dir.path().join("logo.png"), [0x89, b'P', b'N', b'G', 0, 0, 1]).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("logo.png")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = repo.signature().unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Add logo", &tree, &[&parent]).unwrap();
+        
+        let breakdown = language_breakdown(&repo).unwrap();
+        assert_eq!(breakdown, vec![
+            ("rs".to_string(), 3),
+            ("md".to_string(), 2),
+            ("(none)".to_string(), 1),
+            ("toml".to_string(), 1),
+        ]);
+        assert_eq!(format_language_breakdown(&breakdown), "rs: 3, md: 2, (none): 1, toml: 1");
+    }
+
+    #[test]
+    fn test_format_relative_time_boundaries() {
+        let now = 1_700_000_000;
+        let minute = 60;
+        let hour = 60 * minute;
+        let day = 24 * hour;
+        
+        assert_eq!(format_relative_time_from(now, now), "just now");
+        assert_eq!(format_relative_time_from(now - 59, now), "just now");
+        assert_eq!(format_relative_time_from(now + 30, now), "just now");
+        assert_eq!(format_relative_time_from(now - minute, now), "1 minute ago");
+        assert_eq!(format_relative_time_from(now - 59 * minute, now), "59 minutes ago");
+        assert_eq!(format_relative_time_from(now - hour, now), "1 hour ago");
+        assert_eq!(format_relative_time_from(now - 23 * hour, now), "23 hours ago");
+        assert_eq!(format_relative_time_from(now - day, now), "1 day ago");
+        assert_eq!(format_relative_time_from(now - 3 * day, now), "3 days ago");
+        assert_eq!(format_relative_time_from(now - 29 * day, now), "29 days ago");
+        assert_eq!(format_relative_time_from(now - 30 * day, now), "1 month ago");
+        assert_eq!(format_relative_time_from(now - 300 * day, now), "10 months ago");
+        assert_eq!(format_relative_time_from(now - 365 * day, now), "1 year ago");
+        assert_eq!(format_relative_time_from(now - 800 * day, now), "2 years ago");
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::core::repository::{RepositoryInfo, RepoConfig, AuthType};
+    use crate::core::repository::{RepositoryInfo, RepoConfig, RepositoryGroup, RemoteSpec, AuthType, ConfigProblem, GroupIntegrity, DuplicateRepository, to_https_url, to_ssh_url, FALLBACK_BRANCH, backup_config, restore_config, backup_file_name};
 
     #[test]
     fn test_repository_creation() {
@@ -102,4 +102,370 @@ mod tests {
         let config = RepoConfig::with_name("custom".to_string());
         assert_eq!(config.config_name, "custom");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_ssh_passphrase_defaults_to_none() {
+        let repo = RepositoryInfo::new("test".to_string(), "git@github.com:user/repo.git".to_string());
+        assert_eq!(repo.ssh_passphrase, None);
+        
+        // Older config files don't have the field
+        let repo: RepositoryInfo = serde_json::from_str(
+            r#"{ "name": "test", "url": "git@github.com:user/repo.git", "auth_type": "ssh" }"#
+        ).unwrap();
+        assert_eq!(repo.ssh_passphrase, None);
+        assert!(!serde_json::to_string(&repo).unwrap().contains("ssh_passphrase"));
+    }
+
+    #[test]
+    fn test_ssh_passphrase_is_never_saved() {
+        let mut repo = RepositoryInfo::with_auth(
+            "test".to_string(),
+            "git@github.com:user/repo.git".to_string(),
+            AuthType::SSH
+        );
+        repo.ssh_passphrase = Some("correct horse".to_string());
+        
+        let json = serde_json::to_string(&repo).unwrap();
+        assert!(!json.contains("correct horse"));
+        let restored: RepositoryInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.ssh_passphrase, None);
+        
+        // Passphrases saved by earlier versions are dropped on load
+        let repo: RepositoryInfo = serde_json::from_str(
+            r#"{ "name": "test", "url": "git@github.com:user/repo.git", "auth_type": "ssh", "ssh_passphrase": "correct horse" }"#
+        ).unwrap();
+        assert_eq!(repo.ssh_passphrase, None);
+    }
+
+    #[test]
+    fn test_extra_remotes_default_to_none() {
+        // Older config files don't have the field
+        let repo: RepositoryInfo = serde_json::from_str(
+            r#"{ "name": "test", "url": "https://github.com/user/repo.git" }"#
+        ).unwrap();
+        assert!(repo.extra_remotes.is_empty());
+        assert!(!serde_json::to_string(&repo).unwrap().contains("extra_remotes"));
+        assert_eq!(repo.push_targets().len(), 1);
+    }
+
+    #[test]
+    fn test_push_targets_use_the_credentials_of_each_remote() {
+        let mut mirror = RemoteSpec::new("mirror".to_string(), "git@gitlab.com:user/repo.git".to_string());
+        mirror.auth_type = AuthType::SSH;
+        mirror.ssh_key_path = "~/.ssh/gitlab".to_string();
+        let mut repo = RepositoryInfo::with_auth("origin".to_string(), "https://github.com/user/repo.git".to_string(), AuthType::Token)
+            .with_default_branch("develop".to_string())
+            .with_extra_remote(mirror.clone());
+        repo.auth_token = "secret".to_string();
+        
+        let json = serde_json::to_string(&repo).unwrap();
+        let restored: RepositoryInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.extra_remotes, vec![mirror]);
+        
+        let targets = restored.push_targets();
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[0].name, "origin");
+        assert_eq!(targets[0].auth_token, "secret");
+        // Named after the repository it belongs to
+        assert_eq!(targets[1].name, "origin-mirror");
+        assert_eq!(targets[1].url, "git@gitlab.com:user/repo.git");
+        assert_eq!(targets[1].auth_type, AuthType::SSH);
+        assert_eq!(targets[1].auth_token, "");
+        assert_eq!(targets[1].ssh_key_path, "~/.ssh/gitlab");
+        assert_eq!(targets[1].default_branch.as_deref(), Some("develop"));
+    }
+
+    #[test]
+    fn test_ssh_agent_auth_type_round_trip() {
+        let repo = RepositoryInfo::with_auth(
+            "test".to_string(),
+            "git@github.com:user/repo.git".to_string(),
+            AuthType::SshAgent
+        );
+        
+        let json = serde_json::to_string(&repo).unwrap();
+        assert!(json.contains(r#""auth_type":"ssh_agent""#));
+        
+        let restored: RepositoryInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.auth_type, AuthType::SshAgent);
+    }
+
+    #[test]
+    fn test_convert_https_to_ssh_url() {
+        assert_eq!(to_ssh_url("https://github.com/o/r.git"), Some("git@github.com:o/r.git".to_string()));
+        assert_eq!(to_ssh_url("https://gitlab.com/group/subgroup/project"), Some("git@gitlab.com:group/subgroup/project.git".to_string()));
+        assert_eq!(to_ssh_url("https://git.example.com/team/app.git"), Some("git@git.example.com:team/app.git".to_string()));
+        
+        // scp-style URLs can't carry a port
+        assert_eq!(to_ssh_url("https://git.example.com:8443/team/app.git"), None);
+        assert_eq!(to_ssh_url("https://github.com"), None);
+    }
+
+    #[test]
+    fn test_convert_ssh_to_https_url() {
+        assert_eq!(to_https_url("git@github.com:o/r.git"), Some("https://github.com/o/r.git".to_string()));
+        assert_eq!(to_https_url("git@gitlab.com:group/project.git"), Some("https://gitlab.com/group/project.git".to_string()));
+        assert_eq!(to_https_url("git@git.example.com:team/app.git"), Some("https://git.example.com/team/app.git".to_string()));
+        assert_eq!(to_https_url("git@github.com:repo.git"), None);
+        
+        let url = "https://github.com/o/r.git";
+        assert_eq!(to_https_url(&to_ssh_url(url).unwrap()).as_deref(), Some(url));
+    }
+
+    #[test]
+    fn test_same_url_twice_is_rejected() {
+        let mut config = RepoConfig::new();
+        config.add_repository(RepositoryInfo::new("repo".to_string(), "https://github.com/user/repo.git".to_string()));
+        
+        assert!(config.contains("https://github.com/user/repo.git"));
+        // Scheme, case and the .git suffix don't make it a different repository
+        assert!(config.contains("git@github.com:User/Repo.git"));
+        assert!(config.contains("https://github.com/user/repo"));
+        assert!(!config.contains("https://github.com/user/other.git"));
+        
+        let again = RepositoryInfo::new("copy".to_string(), "https://github.com/user/repo".to_string());
+        assert_eq!(config.find_duplicate(&again), Some(DuplicateRepository::Url("repo".to_string())));
+    }
+
+    #[test]
+    fn test_same_name_twice_warns() {
+        let mut config = RepoConfig::new();
+        config.add_repository(RepositoryInfo::new("origin".to_string(), "https://github.com/user/repo.git".to_string()));
+        
+        let same_name = RepositoryInfo::new("origin".to_string(), "https://github.com/user/other.git".to_string());
+        assert_eq!(config.find_duplicate(&same_name), Some(DuplicateRepository::Name));
+        
+        let distinct = RepositoryInfo::new("other".to_string(), "https://github.com/user/other.git".to_string());
+        assert_eq!(config.find_duplicate(&distinct), None);
+    }
+    #[test]
+    fn test_validate_accepts_consistent_config() {
+        let mut config = RepoConfig::new();
+        let mut token_repo = RepositoryInfo::with_auth("github".to_string(), "https://github.com/user/repo.git".to_string(), AuthType::Token);
+        token_repo.auth_token = "ghp_abc".to_string();
+        let mut ssh_repo = RepositoryInfo::with_auth("gitlab".to_string(), "git@gitlab.com:user/repo.git".to_string(), AuthType::SSH);
+        ssh_repo.ssh_key_path = "/home/user/.ssh/id_ed25519".to_string();
+        config.add_repository(token_repo);
+        config.add_repository(ssh_repo);
+        
+        let mut group = RepositoryGroup::new("mirrors".to_string(), String::new());
+        group.add_repository("gitlab".to_string());
+        config.add_group(group);
+        
+        assert_eq!(config.validate(), Vec::new());
+    }
+
+    #[test]
+    fn test_validate_reports_invalid_urls() {
+        let mut config = RepoConfig::new();
+        config.add_repository(RepositoryInfo::new("broken".to_string(), "not a url".to_string()));
+        
+        let problems = config.validate();
+        assert_eq!(problems.len(), 1);
+        assert!(matches!(&problems[0], ConfigProblem::InvalidUrl { repository, .. } if repository == "broken"));
+    }
+
+    #[test]
+    fn test_validate_reports_invalid_and_duplicate_extra_remotes() {
+        let mut config = RepoConfig::new();
+        config.add_repository(RepositoryInfo::new("web".to_string(), "https://github.com/user/web.git".to_string())
+            .with_extra_remote(RemoteSpec::new("mirror".to_string(), "https://gitlab.com/user/web.git".to_string()))
+            .with_extra_remote(RemoteSpec::new("mirror".to_string(), "https://git.example.com/user/web.git".to_string()))
+            .with_extra_remote(RemoteSpec::new("backup".to_string(), "not a url".to_string())));
+        // Clashes with the remote of the repository named "web-backup"
+        config.add_repository(RepositoryInfo::new("web-backup".to_string(), "https://github.com/user/web-backup.git".to_string()));
+        // The same remote name on another repository is fine
+        config.add_repository(RepositoryInfo::new("api".to_string(), "https://github.com/user/api.git".to_string())
+            .with_extra_remote(RemoteSpec::new("mirror".to_string(), "https://gitlab.com/user/api.git".to_string())));
+        
+        let problems = config.validate();
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+        assert_eq!(problems[0], ConfigProblem::DuplicateRemote { repository: "web".to_string(), remote: "mirror".to_string() });
+        assert!(matches!(&problems[1], ConfigProblem::InvalidUrl { repository, .. } if repository == "web-backup"));
+        assert_eq!(problems[2], ConfigProblem::DuplicateRemote { repository: "web".to_string(), remote: "backup".to_string() });
+    }
+
+    #[test]
+    fn test_validate_reports_auth_fields_missing_for_auth_type() {
+        let mut config = RepoConfig::new();
+        config.add_repository(RepositoryInfo::with_auth("github".to_string(), "https://github.com/user/repo.git".to_string(), AuthType::Token));
+        config.add_repository(RepositoryInfo::with_auth("gitlab".to_string(), "git@gitlab.com:user/repo.git".to_string(), AuthType::SSH));
+        // The agent and helper don't need anything configured
+        config.add_repository(RepositoryInfo::with_auth("codeberg".to_string(), "git@codeberg.org:user/repo.git".to_string(), AuthType::SshAgent));
+        
+        assert_eq!(config.validate(), vec![
+            ConfigProblem::MissingToken { repository: "github".to_string() },
+            ConfigProblem::MissingSshKey { repository: "gitlab".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn test_validate_reports_unknown_group_members() {
+        let mut config = RepoConfig::new();
+        config.add_repository(RepositoryInfo::new("github".to_string(), "https://github.com/user/repo.git".to_string()));
+        let mut group = RepositoryGroup::new("mirrors".to_string(), String::new());
+        group.add_repository("github".to_string());
+        group.add_repository("gone".to_string());
+        config.add_group(group);
+        
+        let problems = config.validate();
+        assert_eq!(problems, vec![ConfigProblem::UnknownGroupMember { group: "mirrors".to_string(), repository: "gone".to_string() }]);
+        assert_eq!(problems[0].to_string(), "group mirrors: repository gone does not exist");
+    }
+    #[test]
+    fn test_prune_dangling_group_members() {
+        let mut config = RepoConfig::new();
+        config.add_repository(RepositoryInfo::new("github".to_string(), "https://github.com/user/repo.git".to_string()));
+        let mut group = RepositoryGroup::new("mirrors".to_string(), String::new());
+        group.add_repository("github".to_string());
+        config.add_group(group);
+        
+        // Removed from repos.json by hand, the group still lists it
+        config.get_group_mut("mirrors").unwrap().repository_names.push("gone".to_string());
+        assert_eq!(config.group_integrity_report()[0].dangling, vec!["gone".to_string()]);
+        
+        assert_eq!(config.prune_dangling_group_members(), 1);
+        assert_eq!(config.get_group("mirrors").unwrap().repository_names, vec!["github".to_string()]);
+        assert_eq!(config.prune_dangling_group_members(), 0);
+        assert!(config.group_integrity_report()[0].is_intact());
+    }
+
+    #[test]
+    fn test_group_integrity_report_flags_unlisted_repositories() {
+        let mut config = RepoConfig::new();
+        config.add_repository(RepositoryInfo::new("github".to_string(), "https://github.com/user/repo.git".to_string()));
+        config.add_repository(
+            RepositoryInfo::new("gitlab".to_string(), "https://gitlab.com/user/repo.git".to_string()).with_group("mirrors".to_string())
+        );
+        let mut group = RepositoryGroup::new("mirrors".to_string(), String::new());
+        group.add_repository("github".to_string());
+        config.add_group(group);
+        
+        let report = config.group_integrity_report();
+        assert_eq!(report, vec![GroupIntegrity {
+            group: "mirrors".to_string(),
+            members: 1,
+            dangling: Vec::new(),
+            unlisted: vec!["gitlab".to_string()],
+        }]);
+        assert!(!report[0].is_intact());
+    }
+    #[test]
+    fn test_rename_group_moves_repositories() {
+        let mut config = RepoConfig::new();
+        config.add_repository(
+            RepositoryInfo::new("github".to_string(), "https://github.com/user/repo.git".to_string()).with_group("mirrors".to_string())
+        );
+        config.add_repository(
+            RepositoryInfo::new("gitlab".to_string(), "https://gitlab.com/user/repo.git".to_string()).with_group("mirrors".to_string())
+        );
+        let mut group = RepositoryGroup::new("mirrors".to_string(), "Mirrors".to_string());
+        group.add_repository("github".to_string());
+        group.add_repository("gitlab".to_string());
+        config.add_group(group);
+        
+        config.rename_group("mirrors", "backups").unwrap();
+        
+        let names: Vec<&str> = config.get_repositories_in_group("backups").iter().map(|repo| repo.name.as_str()).collect();
+        assert_eq!(names, vec!["github", "gitlab"]);
+        assert!(config.get_group("mirrors").is_none());
+        assert!(config.get_repositories_in_group("mirrors").is_empty());
+        assert!(config.repositories.iter().all(|repo| repo.group == "backups"));
+    }
+
+    #[test]
+    fn test_rename_group_rejects_taken_names() {
+        let mut config = RepoConfig::new();
+        config.add_group(RepositoryGroup::new("frontend".to_string(), String::new()));
+        config.add_group(RepositoryGroup::new("backend".to_string(), String::new()));
+        
+        assert!(config.rename_group("frontend", "backend").is_err());
+        assert!(config.rename_group("missing", "other").is_err());
+        assert!(config.rename_group("frontend", " ").is_err());
+        assert!(config.get_group("frontend").is_some());
+    }
+
+    #[test]
+    fn test_branch_or_default_falls_back_to_repository_default() {
+        let repo = RepositoryInfo::new("test".to_string(), "https://github.com/user/repo.git".to_string());
+        assert_eq!(repo.branch_or_default("feature"), "feature");
+        assert_eq!(repo.branch_or_default(""), FALLBACK_BRANCH);
+        
+        let repo = repo.with_default_branch("develop".to_string());
+        assert_eq!(repo.branch_or_default(""), "develop");
+        assert_eq!(repo.branch_or_default("  "), "develop");
+        // An explicit branch still wins
+        assert_eq!(repo.branch_or_default("release"), "release");
+    }
+
+    #[test]
+    fn test_move_repository_reorders() {
+        let mut config = RepoConfig::new();
+        for name in ["a", "b", "c", "d"] {
+            config.add_repository(RepositoryInfo::new(name.to_string(), format!("https://github.com/user/{}.git", name)));
+        }
+        let names = |config: &RepoConfig| config.repositories.iter().map(|repo| repo.name.clone()).collect::<Vec<_>>();
+        
+        config.move_repository(2, 0);
+        assert_eq!(names(&config), vec!["c", "a", "b", "d"]);
+        
+        config.move_repository(0, 3);
+        assert_eq!(names(&config), vec!["a", "b", "d", "c"]);
+        
+        // Out-of-range indices leave the order alone
+        config.move_repository(4, 0);
+        config.move_repository(0, 4);
+        config.move_repository(1, 1);
+        assert_eq!(names(&config), vec!["a", "b", "d", "c"]);
+    }
+
+    #[test]
+    fn test_backup_and_restore_round_trip() {
+        let mut config = RepoConfig::with_name("work".to_string());
+        config.add_repository(RepositoryInfo::new("repo1".to_string(), "https://github.com/user/repo1.git".to_string()));
+        let mut disabled = RepositoryInfo::new("repo2".to_string(), "git@github.com:user/repo2.git".to_string())
+            .with_group("backend".to_string());
+        disabled.enabled = false;
+        disabled.ssh_passphrase = Some("correct horse".to_string());
+        config.add_repository(disabled);
+        config.timeout_secs = 90;
+        
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("backup.json").to_string_lossy().to_string();
+        backup_config(&config, &path).unwrap();
+        
+        // The snapshot is timestamped
+        let snapshot: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(snapshot["created_at"].is_string());
+        // Passphrases stay in repos.json
+        assert!(!snapshot.to_string().contains("correct horse"));
+        
+        let restored = restore_config(&path).unwrap();
+        assert_eq!(restored.config_name, "work");
+        assert_eq!(restored.timeout_secs, 90);
+        let names: Vec<&str> = restored.repositories.iter().map(|repo| repo.name.as_str()).collect();
+        assert_eq!(names, vec!["repo1", "repo2"]);
+        assert_eq!(restored.repositories[1].group, "backend");
+        assert!(!restored.repositories[1].enabled);
+        assert!(restored.validate().is_empty());
+    }
+
+    #[test]
+    fn test_restore_config_accepts_plain_config_file() {
+        let mut config = RepoConfig::new();
+        config.add_repository(RepositoryInfo::new("repo1".to_string(), "https://github.com/user/repo1.git".to_string()));
+        
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("repos.json").to_string_lossy().to_string();
+        std::fs::write(&path, serde_json::to_string(&config).unwrap()).unwrap();
+        
+        assert_eq!(restore_config(&path).unwrap().repositories.len(), 1);
+        assert!(restore_config(&dir.path().join("missing.json").to_string_lossy()).is_err());
+    }
+
+    #[test]
+    fn test_backup_file_name_is_timestamped() {
+        let time = chrono::TimeZone::with_ymd_and_hms(&chrono::Local, 2024, 1, 31, 15, 45, 0).unwrap();
+        assert_eq!(backup_file_name(time), "repos-backup-20240131-154500.json");
+    }
+}
@@ -0,0 +1,58 @@
+use anyhow::{anyhow, Result};
+use git2::Repository;
+
+/// Which part of a `vX.Y.Z` version to increase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bump {
+    Major,
+    Minor,
+    Patch,
+}
+
+// Split "v1.2.3" (or "1.2.3") into its prefix and numbers. Anything else, including
+// pre-release suffixes like "v1.2.3-rc1", isn't a version tag.
+fn parse_semver(tag: &str) -> Option<(&str, [u64; 3])> {
+    let (prefix, version) = match tag.strip_prefix('v') {
+        Some(version) => ("v", version),
+        None => ("", tag),
+    };
+    
+    let mut parts = version.split('.');
+    let mut numbers = [0; 3];
+    for number in numbers.iter_mut() {
+        let part = parts.next()?;
+        if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        *number = part.parse().ok()?;
+    }
+    if parts.next().is_some() {
+        return None;
+    }
+    
+    Some((prefix, numbers))
+}
+
+/// The version after `current`, keeping its `v` prefix: `v1.2.3` becomes `v1.2.4`,
+/// `v1.3.0` or `v2.0.0` for a patch, minor or major bump
+pub fn next_semver(current: &str, bump: Bump) -> Result<String> {
+    let (prefix, [major, minor, patch]) = parse_semver(current.trim())
+        .ok_or_else(|| anyhow!("'{}' is not a version like v1.2.3", current))?;
+    
+    let (major, minor, patch) = match bump {
+        Bump::Major => (major + 1, 0, 0),
+        Bump::Minor => (major, minor + 1, 0),
+        Bump::Patch => (major, minor, patch + 1),
+    };
+    Ok(format!("{}{}.{}.{}", prefix, major, minor, patch))
+}
+
+/// The highest `vX.Y.Z` tag of the repository. Tags that aren't versions are ignored.
+pub fn latest_semver_tag(repo: &Repository) -> Option<String> {
+    let tags = repo.tag_names(None).ok()?;
+    tags.iter()
+        .flatten()
+        .filter_map(|tag| parse_semver(tag).map(|(_, version)| (version, tag)))
+        .max_by_key(|(version, _)| *version)
+        .map(|(_, tag)| tag.to_string())
+}
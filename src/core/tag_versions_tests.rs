@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::tag_versions::{latest_semver_tag, next_semver, Bump};
+    use crate::core::test_support::{commit_file, init_temp_repo};
+
+    #[test]
+    fn test_next_semver_bumps() {
+        assert_eq!(next_semver("v1.2.3", Bump::Patch).unwrap(), "v1.2.4");
+        assert_eq!(next_semver("v1.2.3", Bump::Minor).unwrap(), "v1.3.0");
+        assert_eq!(next_semver("v1.2.3", Bump::Major).unwrap(), "v2.0.0");
+        // Tags without the prefix stay without it
+        assert_eq!(next_semver("0.9.12", Bump::Patch).unwrap(), "0.9.13");
+    }
+
+    #[test]
+    fn test_next_semver_rejects_non_versions() {
+        for tag in ["", "latest", "v1.2", "v1.2.3.4", "v1.2.3-rc1", "vx.y.z", "release-1.0.0"] {
+            assert!(next_semver(tag, Bump::Patch).is_err(), "{} was accepted", tag);
+        }
+    }
+
+    #[test]
+    fn test_latest_semver_tag_ignores_other_tags() {
+        let (_dir, repo) = init_temp_repo();
+        assert_eq!(latest_semver_tag(&repo), None);
+        
+        let commit_id = commit_file(&repo, "README.md", "hello\n", "Initial commit");
+        let commit = repo.find_object(commit_id, None).unwrap();
+        for tag in ["v1.2.3", "v1.10.0", "v1.9.9", "nightly", "v2.0.0-beta", "v1.2"] {
+            repo.tag_lightweight(tag, &commit, false).unwrap();
+        }
+        
+        // Numeric, not alphabetical, order: v1.10.0 is newer than v1.9.9
+        assert_eq!(latest_semver_tag(&repo), Some("v1.10.0".to_string()));
+    }
+}
@@ -0,0 +1,173 @@
+//! Shared helpers for tests that need a real on-disk git repository, or a stand-in for one.
+
+use crate::core::git_backend::GitBackend;
+use crate::core::git_operations::{CommitOptions, NetworkOptions, NothingToCommit, TransferProgress};
+use crate::core::repository::RepositoryInfo;
+use git2::{Oid, Repository, Signature, Time};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Initialize an empty repository in a fresh temporary directory.
+///
+/// The returned `TempDir` must be kept alive for as long as the repository is used.
+pub fn init_temp_repo() -> (TempDir, Repository) {
+    let dir = TempDir::new().expect("failed to create temp dir");
+    let repo = Repository::init(dir.path()).expect("failed to init repository");
+
+    // Make sure `repo.signature()` works regardless of the global git config
+    {
+        let mut config = repo.config().expect("failed to open repository config");
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+    }
+
+    (dir, repo)
+}
+
+/// Write `content` to `file_name` inside the repository's working directory.
+pub fn write_file(repo: &Repository, file_name: &str, content: &str) {
+    let path = repo.workdir().expect("repository has no workdir").join(file_name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).unwrap();
+    }
+    std::fs::write(path, content).unwrap();
+}
+
+/// Write a file, stage it and commit it on HEAD with the current time.
+pub fn commit_file(repo: &Repository, file_name: &str, content: &str, message: &str) -> Oid {
+    let now = chrono::Utc::now().timestamp();
+    commit_file_at(repo, file_name, content, message, now)
+}
+
+/// Write a file, stage it and commit it on HEAD with an explicit author/committer time.
+pub fn commit_file_at(repo: &Repository, file_name: &str, content: &str, message: &str, time: i64) -> Oid {
+    write_file(repo, file_name, content);
+
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new(file_name)).unwrap();
+    index.write().unwrap();
+
+    let tree_id = index.write_tree().unwrap();
+    let tree = repo.find_tree(tree_id).unwrap();
+    let signature = Signature::new("Test User", "test@example.com", &Time::new(time, 0)).unwrap();
+
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+
+    repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents).unwrap()
+}
+
+/// Count the commits reachable from HEAD.
+pub fn count_commits(repo: &Repository) -> usize {
+    let mut revwalk = repo.revwalk().unwrap();
+    revwalk.push_head().unwrap();
+    revwalk.count()
+}
+
+/// Leave the repository in the middle of a merge where both sides changed `file_name`.
+pub fn create_merge_conflict(repo: &Repository, file_name: &str) {
+    commit_file(repo, file_name, "base\n", "Base");
+    let base = repo.head().unwrap().peel_to_commit().unwrap();
+    let original = repo.head().unwrap().name().unwrap().to_string();
+
+    // Change the file on a side branch
+    repo.branch("side", &base, false).unwrap();
+    repo.set_head("refs/heads/side").unwrap();
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force())).unwrap();
+    commit_file(repo, file_name, "theirs\n", "Their change");
+    let side = repo.head().unwrap().peel_to_commit().unwrap();
+
+    // And differently on the original branch
+    repo.set_head(&original).unwrap();
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force())).unwrap();
+    commit_file(repo, file_name, "ours\n", "Our change");
+
+    let side = repo.find_annotated_commit(side.id()).unwrap();
+    repo.merge(&[&side], None, None).unwrap();
+    assert!(repo.index().unwrap().has_conflicts());
+}
+
+/// A `GitBackend` that records what it is asked to do instead of touching a repository or the network.
+#[derive(Default)]
+pub struct MockBackend {
+    /// Names of the repositories whose push, pull and fetch fail
+    pub failing: Vec<String>,
+    /// How long the push, pull or fetch of a repository takes
+    pub delays: HashMap<String, Duration>,
+    /// Make the commit fail with `NothingToCommit`
+    pub nothing_to_commit: bool,
+    /// Whether there are local changes for `stash` to put away
+    pub has_local_changes: bool,
+    calls: Mutex<Vec<String>>,
+}
+
+impl MockBackend {
+    /// The calls made so far, like "push repo1", in the order they were made.
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn record(&self, call: String) {
+        self.calls.lock().unwrap().push(call);
+    }
+
+    fn remote(&self, operation: &str, repo_info: &RepositoryInfo) -> anyhow::Result<()> {
+        if let Some(delay) = self.delays.get(&repo_info.name) {
+            std::thread::sleep(*delay);
+        }
+        self.record(format!("{} {}", operation, repo_info.name));
+
+        if self.failing.contains(&repo_info.name) {
+            return Err(anyhow::anyhow!("Mock {} of {} was rejected", operation, repo_info.name));
+        }
+        Ok(())
+    }
+}
+
+impl GitBackend for MockBackend {
+    fn add_all(&self) -> anyhow::Result<()> {
+        self.record("add".to_string());
+        Ok(())
+    }
+
+    fn commit(&self, message: &str, _options: &CommitOptions) -> anyhow::Result<()> {
+        self.record(format!("commit {}", message));
+        if self.nothing_to_commit {
+            return Err(NothingToCommit.into());
+        }
+        Ok(())
+    }
+
+    fn prepare_remote(&self, _repo_info: &RepositoryInfo) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn push(&self, repo_info: &RepositoryInfo, _branch: &str, _network_options: &NetworkOptions, _progress: &dyn Fn(TransferProgress)) -> anyhow::Result<()> {
+        self.remote("push", repo_info)
+    }
+
+    fn pull(&self, repo_info: &RepositoryInfo, _branch: &str, _network_options: &NetworkOptions) -> anyhow::Result<()> {
+        self.remote("pull", repo_info)
+    }
+
+    fn fetch(&self, repo_info: &RepositoryInfo, _branch: &str, _network_options: &NetworkOptions, _progress: &dyn Fn(TransferProgress)) -> anyhow::Result<()> {
+        self.remote("fetch", repo_info)
+    }
+
+    fn stash(&self) -> anyhow::Result<bool> {
+        self.record("stash".to_string());
+        Ok(self.has_local_changes)
+    }
+
+    fn unstash(&self) -> anyhow::Result<()> {
+        self.record("unstash".to_string());
+        Ok(())
+    }
+
+    fn uses_lfs(&self) -> bool {
+        false
+    }
+}
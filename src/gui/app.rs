@@ -1,43 +1,121 @@
 use eframe::egui;
-use crate::core::repository::{RepoConfig, RepositoryInfo, AuthType};
+use crate::core::repository::{RepoConfig, RepositoryInfo, AuthType, ConfigProblem, DuplicateRepository, to_https_url, to_ssh_url, FALLBACK_BRANCH, backup_config, restore_config, backup_file_name};
 use crate::core::git_operations::{
-    push_to_all_repositories, 
-    pull_from_all_repositories, 
-    fetch_from_all_repositories, 
-    create_and_push_tag, 
+    push_to_all_repositories_with_options_reporting, 
+    preview_staged_changes, 
+    pull_from_all_repositories_reporting, 
+    fetch_from_all_repositories_reporting, 
+    tag_all_repositories_reporting, 
     check_merge_conflicts,
-    validate_repository_url, 
+    conflicted_files,
+    abort_merge,
+    detect_default_branch,
+    current_branch,
+    parse_repository_url, 
     verify_authentication,
-    clone_all_repositories
+    clone_all_repositories_reporting,
+    health_check_all_reporting,
+    list_repo_remotes,
+    set_remote_url,
+    sync_remotes_from_config,
+    get_working_changes,
+    is_conventional_commit,
+    CommitOptions,
+    FileStatus,
+    TransferProgress,
+    WorkingChangeStatus
 };
+use crate::core::changelog::{generate_changelog, save_changelog};
+use crate::core::maintenance::run_maintenance;
+use crate::core::tag_versions::{latest_semver_tag, next_semver, Bump};
 use crate::core::batch_operations::{
-    push_to_group_repositories,
-    pull_from_group_repositories,
-    fetch_from_group_repositories
+    push_to_group_repositories_reporting,
+    pull_from_group_repositories_reporting,
+    fetch_from_group_repositories_reporting
+};
+use crate::core::repository_stats::{
+    collect_overall_stats,
+    combined_commit_activity,
+    export_stats_csv,
+    export_stats_json,
+    format_size,
+    format_language_breakdown,
+    format_relative_time,
+    stale_branches,
+    submodule_status,
+    ActivityBucket,
+    OverallStats,
+    SubmoduleInfo
 };
-use crate::core::repository_comparison::generate_repository_diff;
 // GitOperationError import removed as it's not currently used
+use crate::core::github_api::{list_github_repos, RateLimited};
+use crate::core::error_handler::{conflict_check_status, summarize_results, ResultStatus, ResultSummary, ALREADY_CLONED};
+use crate::core::notifications::send_push_notifications;
+use crate::core::oauth::{Provider, TokenStatus};
+use crate::core::provider::provider_for_url;
+use crate::core::operation_log::OperationLog;
 use crate::gui::commit_history_viewer::CommitHistoryViewer;
-use std::sync::{Arc, Mutex};
+use crate::gui::comparison_viewer::ComparisonViewer;
+use crate::gui::history_viewer::HistoryViewer;
+use crate::gui::confirmation::{confirm_action, resolve, PendingAction};
+use crate::gui::operation_runner::OperationRunner;
+use crate::gui::settings::{AppSettings, Theme, SETTINGS_FILE};
+use crate::gui::undo::UndoStack;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{mpsc, Arc, Mutex};
 use webbrowser;
 
 // Type alias for the save function
 type SaveConfigFn = fn(&RepoConfig) -> anyhow::Result<()>;
 
+// Statistics and commit activity collected in the background, or the error
+type StatsResult = Result<(OverallStats, Vec<(i64, usize)>), String>;
+
+// An empty passphrase field means the key isn't encrypted
+fn optional_passphrase(input: &str) -> Option<String> {
+    (!input.is_empty()).then(|| input.to_string())
+}
+
 pub struct MultiRepoPusherApp {
     config: Arc<Mutex<RepoConfig>>,
     commit_message: String,
     branch_name: String,
+    detected_branch: Option<String>, // Branch checked out in the local repository when last looked at
+    detached_head: bool,
+    // Selective staging fields
+    stage_all_changes: bool,
+    working_change_selection: Vec<(String, bool)>, // (path, selected)
+    working_changes: Vec<FileStatus>,
+    amend_last_commit: bool,
+    allow_empty_commit: bool,
+    push_include_tags: bool,
+    push_all_branches: bool,
     tag_name: String,
     tag_message: String,
+    changelog_from: String, // Tag the changelog starts after, empty for the whole history
+    changelog_to: String,
+    changelog: Option<String>, // Last generated changelog, in markdown
+    // Stale branch detection fields
+    stale_branch_days: i64,
+    stale_branch_results: Option<Vec<(String, i64)>>, // (branch, last commit timestamp)
+    submodules: Option<Vec<SubmoduleInfo>>, // Submodules of the local repository, once checked
+    repo_remotes: Option<Vec<(String, String, String)>>, // (name, URL in the local repository, URL being edited)
+    proxy_url_input: String,
+    slack_webhook_input: String,
+    discord_webhook_input: String,
     status_message: String,
+    config_problems: Vec<ConfigProblem>, // Shown as warnings until the configuration is fixed
     is_operation_running: bool,
-    operation_results: Vec<(String, String)>, // (repo_name, status)
+    operation_results: Vec<(String, ResultStatus, String)>, // (repo_name, status, message)
+    operation_runner: OperationRunner<Operation>,
     new_repo_name: String,
     new_repo_url: String,
     new_repo_auth_type: AuthType,
     new_repo_token: String,
     new_repo_ssh_key: String,
+    new_repo_ssh_passphrase: String,
+    confirmed_duplicate_name: Option<String>, // Name the user chose to add a second time
     config_name_input: String,
     show_auth_fields: bool,
     active_tab: Tab,
@@ -47,14 +125,23 @@ pub struct MultiRepoPusherApp {
     account_email: String,
     account_token: String,
     account_ssh_key_path: String,
+    account_ssh_passphrase: String,
     account_auth_type: AuthType,
+    account_signing_key: String,
     // Animation variables
     animation_timer: f32,
     // New fields for cloning and group management
     clone_destination_path: String,
+    clone_skip_existing: bool, // Report existing checkouts as "Already cloned" instead of failing
     show_group_form: bool,
+    conflicted_files: Vec<String>, // Files left conflicted by a pull, listed in the conflicts modal
+    show_conflicts_modal: bool,
+    has_merge_conflicts: bool, // Set by the last conflict check, shows "Abort Merge"
+    auto_stash_on_pull: bool, // Stash local changes while pulling
     new_group_name: String,
     new_group_description: String,
+    rename_group_name: String,
+    selected_repositories: BTreeSet<usize>, // Config indices ticked for "Push to Selected"
     selected_group: String,
     // First-time setup fields
     show_first_time_setup: bool,
@@ -62,6 +149,7 @@ pub struct MultiRepoPusherApp {
     // OAuth fields
     oauth_token: String,
     oauth_code: String,
+    oauth_provider: Provider,
     // Account selection and editing fields
     selected_account_index: usize,
     edit_account_name: String,
@@ -69,31 +157,48 @@ pub struct MultiRepoPusherApp {
     edit_account_auth_type: AuthType,
     edit_account_token: String,
     edit_account_ssh_key: String,
+    edit_account_ssh_passphrase: String,
+    edit_account_author_name: String,
+    edit_account_author_email: String,
+    edit_account_local_path: String,
+    edit_account_default_branch: String,
     // Commit history viewer
     commit_history_viewer: CommitHistoryViewer,
     // Search and filter fields
     search_text: String,
     filter_by_group: String,
     filter_by_auth_type: Option<AuthType>,
-    // Theme field
-    dark_mode: bool,
+    repository_sort: RepositorySort,
+    theme: Theme, // Kept in the settings file
+    pending_confirmation: Option<PendingAction>, // Destructive action waiting for the user to confirm it
+    undo_stack: UndoStack, // Configuration as it was before each edit
+    window_size: Option<[f32; 2]>, // Last known inner size, saved with the settings
     // Save function for backup/restore
     save_config_fn: SaveConfigFn,
-    // Repository comparison fields
-    selected_repo_for_diff: usize,
-    diff_branch1: String,
-    diff_branch2: String,
-    diff_content: String,
-    show_diff_viewer: bool,
+    // Repository comparison viewer
+    comparison_viewer: ComparisonViewer,
+    // Results of earlier operations
+    history_viewer: HistoryViewer,
+    // Statistics fields
+    overall_stats: Option<OverallStats>,
+    commit_activity: Vec<(i64, usize)>, // (bucket start, commit count)
+    activity_bucket: ActivityBucket,
+    stats_receiver: Option<mpsc::Receiver<StatsResult>>,
+    // GitHub import fields
+    github_import_owner: String,
+    github_import_token: String,
+    github_import_receiver: Option<mpsc::Receiver<Result<Vec<RepositoryInfo>, String>>>,
 }
 
-#[derive(PartialEq, Clone, Copy)]
-enum Tab {
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Tab {
     Commit,
     Repositories,
     CommitHistory,
+    Comparison,
     Advanced,
     Statistics,
+    History,
 }
 
 impl Default for Tab {
@@ -102,60 +207,142 @@ impl Default for Tab {
     }
 }
 
+// A batch operation that runs in the background, with what is needed to wrap it up
+enum Operation {
+    Push,
+    Pull,
+    Fetch,
+    PushGroup,
+    PullGroup,
+    FetchGroup,
+    Tag,
+    Clone { destination: String },
+    Maintenance,
+    HealthCheck,
+}
+
+impl Operation {
+    // Shown in the status bar once the operation has finished
+    fn label(&self) -> &'static str {
+        match self {
+            Operation::Push => "Push",
+            Operation::Pull => "Pull",
+            Operation::Fetch => "Fetch",
+            Operation::PushGroup => "Push to group",
+            Operation::PullGroup => "Pull from group",
+            Operation::FetchGroup => "Fetch from group",
+            Operation::Tag => "Tag creation and push",
+            Operation::Clone { .. } => "Cloning",
+            Operation::Maintenance => "Optimization",
+            Operation::HealthCheck => "Health check",
+        }
+    }
+    
+    // Name the results are kept under in the operation history, if they are
+    fn history_name(&self) -> Option<&'static str> {
+        match self {
+            Operation::Push | Operation::PushGroup => Some("push"),
+            Operation::Pull | Operation::PullGroup => Some("pull"),
+            Operation::Fetch | Operation::FetchGroup => Some("fetch"),
+            Operation::Clone { .. } => Some("clone"),
+            Operation::Tag | Operation::Maintenance | Operation::HealthCheck => None,
+        }
+    }
+}
+
+// Order of the repository list in the Repositories tab
+#[derive(PartialEq, Clone, Copy)]
+enum RepositorySort {
+    Configured,
+    Name,
+    Group,
+}
+
+#[derive(Clone, Copy)]
+enum StatsExportFormat {
+    Json,
+    Csv,
+}
+
 impl MultiRepoPusherApp {
-    pub fn new(cc: &eframe::CreationContext<'_>, config: Arc<Mutex<RepoConfig>>, save_config_fn: SaveConfigFn) -> Self {
-        // Customize the look of the GUI with premium styling
-        let mut visuals = egui::Visuals::dark();
-        visuals.panel_fill = egui::Color32::from_rgb(25, 25, 35); // Deep dark background
-        visuals.window_fill = egui::Color32::from_rgb(35, 35, 50); // Slightly lighter window
-        visuals.window_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(70, 70, 100));
-        visuals.widgets.noninteractive.bg_fill = egui::Color32::from_rgb(40, 40, 60);
-        visuals.widgets.noninteractive.fg_stroke.color = egui::Color32::from_rgb(200, 200, 220);
-        visuals.widgets.inactive.bg_fill = egui::Color32::from_rgb(55, 55, 80);
-        visuals.widgets.hovered.bg_fill = egui::Color32::from_rgb(80, 80, 130);
-        visuals.widgets.active.bg_fill = egui::Color32::from_rgb(100, 100, 180);
-        visuals.widgets.active.fg_stroke.color = egui::Color32::WHITE;
-        visuals.selection.bg_fill = egui::Color32::from_rgb(90, 90, 150);
-        cc.egui_ctx.set_visuals(visuals);
+    pub fn new(cc: &eframe::CreationContext<'_>, config: Arc<Mutex<RepoConfig>>, save_config_fn: SaveConfigFn, settings: AppSettings) -> Self {
+        // Customize the look of the GUI with the theme picked last time
+        Self::apply_theme(&cc.egui_ctx, settings.theme);
         
         // Check if this is first time setup
         let config_lock = config.lock().unwrap();
+        let proxy_url_input = config_lock.proxy_url.clone().unwrap_or_default();
+        let slack_webhook_input = config_lock.notifications.slack_webhook_url.clone().unwrap_or_default();
+        let discord_webhook_input = config_lock.notifications.discord_webhook_url.clone().unwrap_or_default();
+        let config_problems = config_lock.validate();
         let is_first_time = config_lock.repositories.is_empty() || 
             (config_lock.repositories.len() == 1 && 
              config_lock.repositories[0].url.contains("YOUR_USERNAME"));
         drop(config_lock);
         
-        Self {
+        let mut app = Self {
             config: config.clone(),
             commit_message: "Auto commit".to_string(),
-            branch_name: "main".to_string(),
+            branch_name: String::new(), // Filled in from the checked out branch
+            detected_branch: None,
+            detached_head: false,
+            stage_all_changes: true,
+            working_change_selection: Vec::new(),
+            working_changes: Vec::new(),
+            amend_last_commit: false,
+            allow_empty_commit: false,
+            push_include_tags: false,
+            push_all_branches: false,
             tag_name: String::new(),
             tag_message: String::new(),
+            changelog_from: String::new(),
+            changelog_to: "HEAD".to_string(),
+            changelog: None,
+            stale_branch_days: 90,
+            stale_branch_results: None,
+            submodules: None,
+            repo_remotes: None,
+            proxy_url_input,
+            slack_webhook_input,
+            discord_webhook_input,
             status_message: "Ready".to_string(),
+            config_problems,
             is_operation_running: false,
             operation_results: Vec::new(),
+            operation_runner: OperationRunner::new(),
             new_repo_name: String::new(),
             new_repo_url: String::new(),
             new_repo_auth_type: AuthType::Default,
             new_repo_token: String::new(),
             new_repo_ssh_key: String::new(),
+            new_repo_ssh_passphrase: String::new(),
+            confirmed_duplicate_name: None,
             config_name_input: "default".to_string(),
             show_auth_fields: false,
-            active_tab: Tab::Commit,
+            active_tab: settings.active_tab,
             // Initialize new account fields
             show_account_form: false,
             account_username: String::new(),
             account_email: String::new(),
             account_token: String::new(),
             account_ssh_key_path: String::new(),
+            account_ssh_passphrase: String::new(),
             account_auth_type: AuthType::Default,
+            account_signing_key: String::new(),
             // Initialize animation timer
             animation_timer: 0.0,
             // Initialize new fields for cloning and group management
             clone_destination_path: String::new(),
+            clone_skip_existing: true,
             show_group_form: false,
+            conflicted_files: Vec::new(),
+            show_conflicts_modal: false,
+            has_merge_conflicts: false,
+            auto_stash_on_pull: false,
             new_group_name: String::new(),
             new_group_description: String::new(),
+            rename_group_name: String::new(),
+            selected_repositories: BTreeSet::new(),
             selected_group: String::new(),
             // First-time setup fields
             show_first_time_setup: is_first_time,
@@ -163,6 +350,7 @@ impl MultiRepoPusherApp {
             // OAuth fields
             oauth_token: String::new(),
             oauth_code: String::new(),
+            oauth_provider: Provider::GitHub,
             // Account selection and editing fields
             selected_account_index: 0,
             edit_account_name: String::new(),
@@ -170,96 +358,185 @@ impl MultiRepoPusherApp {
             edit_account_auth_type: AuthType::Default,
             edit_account_token: String::new(),
             edit_account_ssh_key: String::new(),
+            edit_account_ssh_passphrase: String::new(),
+            edit_account_author_name: String::new(),
+            edit_account_author_email: String::new(),
+            edit_account_local_path: String::new(),
+            edit_account_default_branch: String::new(),
             // Commit history viewer
             commit_history_viewer: CommitHistoryViewer::new(config.clone()),
             // Search and filter fields
             search_text: String::new(),
             filter_by_group: String::new(),
             filter_by_auth_type: None,
-            // Theme field
-            dark_mode: true,
+            repository_sort: RepositorySort::Configured,
+            theme: settings.theme,
+            pending_confirmation: None,
+            undo_stack: UndoStack::default(),
+            window_size: settings.window_size,
             // Save function for backup/restore
             save_config_fn,
-            // Repository comparison fields
-            selected_repo_for_diff: 0,
-            diff_branch1: "main".to_string(),
-            diff_branch2: "develop".to_string(),
-            diff_content: String::new(),
-            show_diff_viewer: false,
+            // Repository comparison viewer
+            comparison_viewer: ComparisonViewer::new(config.clone()),
+            history_viewer: HistoryViewer::new(OperationLog::default_location()),
+            overall_stats: None,
+            commit_activity: Vec::new(),
+            activity_bucket: ActivityBucket::Week,
+            stats_receiver: None,
+            github_import_owner: String::new(),
+            github_import_token: String::new(),
+            github_import_receiver: None,
+        };
+        
+        app.refresh_working_changes();
+        app.refresh_current_branch();
+        app.history_viewer.refresh();
+        app
+    }
+    
+    // Show the files that staging everything would commit, and push once they are confirmed
+    fn confirm_push_to_all(&mut self) {
+        // Files ticked in the Commit tab were already picked by hand
+        if !self.stage_all_changes {
+            self.push_to_all_repositories();
+            return;
+        }
+        
+        let preview = git2::Repository::open(".")
+            .map_err(anyhow::Error::from)
+            .and_then(|repo| preview_staged_changes(&repo));
+        match preview {
+            Ok(files) if files.is_empty() => self.push_to_all_repositories(),
+            Ok(files) => self.pending_confirmation = Some(PendingAction::PushAll { files }),
+            Err(e) => self.status_message = format!("Failed to list the changes to commit: {}", e),
         }
     }
     
     fn push_to_all_repositories(&mut self) {
-        self.is_operation_running = true;
-        self.status_message = "Pushing to repositories...".to_string();
-        self.operation_results.clear();
+        let config = self.config.lock().unwrap().clone();
+        self.start_push(config, "Pushing to repositories...".to_string());
+    }
+    
+    // Push to the repositories ticked in the Repositories tab only
+    fn push_to_selected_repositories(&mut self) {
+        let indices: Vec<usize> = self.selected_repositories.iter().copied().collect();
+        let config = self.config.lock().unwrap().select_repositories(&indices);
+        if config.repositories.is_empty() {
+            self.status_message = "Please select at least one repository".to_string();
+            return;
+        }
         
-        // Clone config for iteration
-        let config_clone = self.config.clone();
-        let config = config_clone.lock().unwrap();
+        let status_message = format!("Pushing to {} selected repositories...", config.repositories.len());
+        self.start_push(config, status_message);
+    }
+    
+    // Commit with the settings of the Commit tab and push to the repositories of `config`
+    fn start_push(&mut self, config: RepoConfig, status_message: String) {
         let commit_message = self.commit_message.clone();
         let branch_name = self.branch_name.clone();
         
-        // Push to all repositories
-        self.operation_results = push_to_all_repositories(&config, &commit_message, &branch_name);
+        // Commit with the identity of the selected account, if it has one
+        let mut commit_options = CommitOptions::from_config(&config);
+        if let Some(account) = self.config.lock().unwrap().repositories.get(self.selected_account_index) {
+            commit_options = commit_options.with_identity_of(account);
+        }
+        
+        commit_options.amend = self.amend_last_commit;
+        commit_options.allow_empty = self.allow_empty_commit;
+        commit_options.include_tags = self.push_include_tags;
+        commit_options.all_branches = self.push_all_branches;
         
-        // Check if any operations failed
-        let failed_count = self.operation_results.iter().filter(|(_, status)| !status.contains("Success")).count();
-        if failed_count > 0 {
-            self.status_message = format!("Push completed with {} errors!", failed_count);
-        } else {
-            self.status_message = "Push completed successfully!".to_string();
+        // Only stage the files ticked in the Commit tab when not staging everything
+        if !self.stage_all_changes {
+            let selected_paths: Vec<String> = self.working_change_selection
+                .iter()
+                .filter(|(_, selected)| *selected)
+                .map(|(path, _)| path.clone())
+                .collect();
+            
+            if selected_paths.is_empty() {
+                self.status_message = "Please select at least one file to stage".to_string();
+                return;
+            }
+            commit_options.paths = Some(selected_paths);
         }
         
-        self.is_operation_running = false;
+        // Push to the repositories
+        self.start_operation(Operation::Push, status_message, move |report, progress| {
+            let mut results = Vec::new();
+            push_to_all_repositories_with_options_reporting(&config, &commit_message, &branch_name, &commit_options, &mut |result| {
+                results.push(result.clone());
+                report(result);
+            }, progress);
+            send_push_notifications(&config, &results);
+        });
     }
     
     fn pull_from_all_repositories(&mut self) {
-        self.is_operation_running = true;
-        self.status_message = "Pulling from repositories...".to_string();
-        self.operation_results.clear();
-        
-        // Clone config for iteration
-        let config_clone = self.config.clone();
-        let config = config_clone.lock().unwrap();
+        let config = self.config.lock().unwrap().clone();
         let branch_name = self.branch_name.clone();
+        let auto_stash = self.auto_stash_on_pull;
         
         // Pull from all repositories
-        self.operation_results = pull_from_all_repositories(&config, &branch_name);
-        
-        // Check if any operations failed
-        let failed_count = self.operation_results.iter().filter(|(_, status)| !status.contains("Success")).count();
-        if failed_count > 0 {
-            self.status_message = format!("Pull completed with {} errors!", failed_count);
-        } else {
-            self.status_message = "Pull completed successfully!".to_string();
-        }
-        
-        self.is_operation_running = false;
+        self.start_operation(Operation::Pull, "Pulling from repositories...".to_string(), move |report, _| {
+            pull_from_all_repositories_reporting(&config, &branch_name, auto_stash, report);
+        });
     }
     
     fn fetch_from_all_repositories(&mut self) {
-        self.is_operation_running = true;
-        self.status_message = "Fetching from repositories...".to_string();
-        self.operation_results.clear();
-        
-        // Clone config for iteration
-        let config_clone = self.config.clone();
-        let config = config_clone.lock().unwrap();
+        let config = self.config.lock().unwrap().clone();
         let branch_name = self.branch_name.clone();
         
         // Fetch from all repositories
-        self.operation_results = fetch_from_all_repositories(&config, &branch_name);
+        self.start_operation(Operation::Fetch, "Fetching from repositories...".to_string(), move |report, progress| {
+            fetch_from_all_repositories_reporting(&config, &branch_name, report, progress);
+        });
+    }
+    
+    // Follow the branch checked out in the local repository. A branch typed by
+    // hand is kept unless it was the previously detected one.
+    fn refresh_current_branch(&mut self) {
+        let Ok(repo) = git2::Repository::open(".") else {
+            return;
+        };
+        self.detached_head = repo.head_detached().unwrap_or(false);
         
-        // Check if any operations failed
-        let failed_count = self.operation_results.iter().filter(|(_, status)| !status.contains("Success")).count();
-        if failed_count > 0 {
-            self.status_message = format!("Fetch completed with {} errors!", failed_count);
-        } else {
-            self.status_message = "Fetch completed successfully!".to_string();
+        let Ok(branch) = current_branch(&repo) else {
+            return;
+        };
+        let typed_by_hand = !self.branch_name.is_empty() && self.detected_branch.as_deref() != Some(self.branch_name.as_str());
+        if !typed_by_hand {
+            self.branch_name = branch.clone();
         }
+        self.detected_branch = Some(branch);
+    }
+    
+    // Reload the list of changed files, keeping the selection of files that are still changed
+    fn refresh_working_changes(&mut self) {
+        let repo = match git2::Repository::open(".") {
+            Ok(repo) => repo,
+            Err(e) => {
+                self.status_message = format!("Failed to open repository: {}", e);
+                return;
+            }
+        };
         
-        self.is_operation_running = false;
+        match get_working_changes(&repo) {
+            Ok(changes) => {
+                let previous = std::mem::take(&mut self.working_change_selection);
+                self.working_change_selection = changes
+                    .iter()
+                    .map(|change| {
+                        let selected = previous.iter().any(|(p, selected)| *p == change.path && *selected);
+                        (change.path.clone(), selected)
+                    })
+                    .collect();
+                self.working_changes = changes;
+            }
+            Err(e) => {
+                self.status_message = format!("Failed to read working tree status: {}", e);
+            }
+        }
     }
     
     // Render statistics tab
@@ -269,7 +546,7 @@ impl MultiRepoPusherApp {
         
         // Add a refresh button to collect statistics
         ui.horizontal(|ui| {
-            if self.is_operation_running {
+            if self.stats_receiver.is_some() {
                 ui.add(egui::Spinner::new().size(20.0));
                 ui.label("Collecting statistics...");
             } else {
@@ -284,6 +561,14 @@ impl MultiRepoPusherApp {
                 if ui.add(refresh_button).clicked() {
                     self.collect_statistics();
                 }
+                
+                if ui.button("💾 Export JSON").clicked() {
+                    self.export_statistics(StatsExportFormat::Json);
+                }
+                
+                if ui.button("💾 Export CSV").clicked() {
+                    self.export_statistics(StatsExportFormat::Csv);
+                }
             }
         });
         
@@ -292,6 +577,10 @@ impl MultiRepoPusherApp {
         // Display overall statistics
         let config = self.config.clone();
         let config_lock = config.lock().unwrap();
+        let stats = self.overall_stats.as_ref();
+        
+        // Values that haven't been collected yet are shown as "-"
+        let format_count = |value: Option<usize>| value.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string());
         
         // Overall stats
         ui.group(|ui| {
@@ -309,15 +598,41 @@ impl MultiRepoPusherApp {
                 ui.vertical(|ui| {
                     ui.label(egui::RichText::new(format!("{}", config_lock.repositories.len())));
                     ui.label(egui::RichText::new(format!("{}", config_lock.groups.len())));
-                    // We'll update these with actual stats when collected
-                    ui.label(egui::RichText::new("0"));
-                    ui.label(egui::RichText::new("0"));
+                    ui.label(egui::RichText::new(format_count(stats.map(|s| s.total_commits))));
+                    ui.label(egui::RichText::new(format_count(stats.map(|s| s.total_contributors))));
                 });
             });
         });
         
         ui.add_space(10.0);
         
+        // Commit activity chart
+        let mut recollect = false;
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.heading("📅 Commit Activity");
+                
+                let previous_bucket = self.activity_bucket;
+                egui::ComboBox::from_id_source("activity_bucket")
+                    .selected_text(format!("Per {:?}", self.activity_bucket))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.activity_bucket, ActivityBucket::Day, "Per Day");
+                        ui.selectable_value(&mut self.activity_bucket, ActivityBucket::Week, "Per Week");
+                        ui.selectable_value(&mut self.activity_bucket, ActivityBucket::Month, "Per Month");
+                    });
+                recollect = self.activity_bucket != previous_bucket && self.overall_stats.is_some();
+            });
+            ui.add_space(10.0);
+            
+            if self.commit_activity.is_empty() {
+                ui.label(egui::RichText::new("No activity data. Refresh statistics for cloned repositories.").weak().size(12.0));
+            } else {
+                Self::render_activity_chart(ui, &self.commit_activity);
+            }
+        });
+        
+        ui.add_space(10.0);
+        
         // Repository-specific stats
         if !config_lock.repositories.is_empty() {
             ui.group(|ui| {
@@ -326,19 +641,48 @@ impl MultiRepoPusherApp {
                 
                 egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
                     for repo in &config_lock.repositories {
+                        let repo_stats = stats.and_then(|s| s.repository_stats.iter().find(|r| r.name == repo.name));
+                        
                         ui.group(|ui| {
                             ui.horizontal(|ui| {
                                 ui.vertical(|ui| {
                                     ui.label(egui::RichText::new(&repo.name).size(16.0).strong());
                                     ui.label(egui::RichText::new(&repo.url).weak().size(12.0));
                                     
-                                    // Stats placeholders
-                                    ui.label(egui::RichText::new("Commits: 0").weak().size(11.0));
-                                    ui.label(egui::RichText::new("Files: 0").weak().size(11.0));
-                                    ui.label(egui::RichText::new("Contributors: 0").weak().size(11.0));
+                                    match repo_stats {
+                                        Some(repo_stats) if repo_stats.available => {
+                                            ui.label(egui::RichText::new(format!("Commits: {}", repo_stats.total_commits)).weak().size(11.0));
+                                            ui.label(egui::RichText::new(format!("Files: {}", repo_stats.total_files)).weak().size(11.0));
+                                            ui.label(egui::RichText::new(format!("Lines: {}", repo_stats.total_lines)).weak().size(11.0));
+                                            ui.label(egui::RichText::new(format!("Contributors: {}", repo_stats.contributors.len())).weak().size(11.0));
+                                            if let Some(last_commit_date) = repo_stats.last_commit_date {
+                                                let absolute = chrono::DateTime::from_timestamp(last_commit_date, 0)
+                                                    .map(|datetime| datetime.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                                                    .unwrap_or_default();
+                                                ui.label(egui::RichText::new(format!("Last commit: {}", format_relative_time(last_commit_date))).weak().size(11.0))
+                                                    .on_hover_text(absolute);
+                                            }
+                                            ui.label(egui::RichText::new(format!(
+                                                "Size: {} ({} in .git)",
+                                                format_size(repo_stats.working_tree_size + repo_stats.git_size),
+                                                format_size(repo_stats.git_size)
+                                            )).weak().size(11.0));
+                                            if !repo_stats.languages.is_empty() {
+                                                // The most common extensions are enough for an overview
+                                                let top = &repo_stats.languages[..repo_stats.languages.len().min(6)];
+                                                ui.label(egui::RichText::new(format!("Files by type: {}", format_language_breakdown(top))).weak().size(11.0));
+                                            }
+                                        },
+                                        Some(_) => {
+                                            ui.label(egui::RichText::new("Statistics not available (repository is not cloned locally)").weak().size(11.0));
+                                        },
+                                        None => {
+                                            ui.label(egui::RichText::new("Statistics not collected yet").weak().size(11.0));
+                                        }
+                                    }
                                     
                                     if !repo.group.is_empty() {
-                                        ui.label(egui::RichText::new(format!("📁 Group: {}", repo.group)).weak().size(11.0).color(egui::Color32::from_rgb(200, 150, 200)));
+                                        ui.label(egui::RichText::new(format!("📁 Group: {}", repo.group)).weak().size(11.0).color(self.theme.accent(egui::Color32::from_rgb(200, 150, 200))));
                                     }
                                 });
                             });
@@ -358,16 +702,21 @@ impl MultiRepoPusherApp {
                 ui.add_space(10.0);
                 
                 for group in &config_lock.groups {
+                    let group_stats = stats.and_then(|s| s.group_stats.iter().find(|g| g.name == group.name));
+                    
                     ui.group(|ui| {
                         ui.horizontal(|ui| {
                             ui.vertical(|ui| {
                                 ui.label(egui::RichText::new(&group.name).size(16.0).strong());
                                 ui.label(egui::RichText::new(&group.description).weak().size(12.0));
                                 
-                                // Stats placeholders
                                 ui.label(egui::RichText::new(format!("Repositories: {}", group.repository_names.len())).weak().size(11.0));
-                                ui.label(egui::RichText::new("Total Commits: 0").weak().size(11.0));
-                                ui.label(egui::RichText::new("Avg Commits/Repo: 0.0").weak().size(11.0));
+                                ui.label(egui::RichText::new(format!("Total Commits: {}", format_count(group_stats.map(|g| g.total_commits)))).weak().size(11.0));
+                                ui.label(egui::RichText::new(format!(
+                                    "Avg Commits/Repo: {}",
+                                    group_stats.map(|g| format!("{:.1}", g.avg_commits_per_repo)).unwrap_or_else(|| "-".to_string())
+                                )).weak().size(11.0));
+                                ui.label(egui::RichText::new(format!("Contributors: {}", format_count(group_stats.map(|g| g.total_contributors)))).weak().size(11.0));
                             });
                         });
                     });
@@ -375,26 +724,309 @@ impl MultiRepoPusherApp {
                 }
             });
         }
+        
+        // Group membership that doesn't match the repositories
+        let mut prune_groups = false;
+        if !config_lock.groups.is_empty() {
+            ui.add_space(10.0);
+            
+            ui.group(|ui| {
+                ui.heading("🧩 Group Integrity");
+                ui.add_space(10.0);
+                
+                let report = config_lock.group_integrity_report();
+                for integrity in &report {
+                    ui.horizontal(|ui| {
+                        if integrity.is_intact() {
+                            ui.label(egui::RichText::new("✓").color(egui::Color32::GREEN));
+                        } else {
+                            ui.label(egui::RichText::new("⚠").color(egui::Color32::YELLOW));
+                        }
+                        ui.label(egui::RichText::new(format!("{}: {} repositories", integrity.group, integrity.members)).strong());
+                    });
+                    if !integrity.dangling.is_empty() {
+                        ui.label(egui::RichText::new(format!("Missing repositories: {}", integrity.dangling.join(", "))).weak().size(11.0));
+                    }
+                    if !integrity.unlisted.is_empty() {
+                        ui.label(egui::RichText::new(format!("Not listed as members: {}", integrity.unlisted.join(", "))).weak().size(11.0));
+                    }
+                }
+                
+                if report.iter().any(|integrity| !integrity.dangling.is_empty()) {
+                    ui.add_space(5.0);
+                    prune_groups = ui.button("🧹 Remove Missing Repositories").clicked();
+                }
+            });
+        }
+        
+        // Collect again with the newly selected bucket size, once the config is no longer locked
+        drop(config_lock);
+        if recollect {
+            self.collect_statistics();
+        }
+        if prune_groups {
+            self.record_undo();
+            let pruned = self.config.lock().unwrap().prune_dangling_group_members();
+            self.status_message = format!("Removed {} missing repositories from groups", pruned);
+            if let Err(e) = self.save_current_configuration() {
+                self.status_message = e;
+            }
+        }
+    }
+    
+    // One bar split into green, yellow, gray and red in proportion to the result counts
+    fn render_summary_bar(ui: &mut egui::Ui, summary: &ResultSummary) {
+        let (rect, _) = ui.allocate_exact_size(egui::Vec2::new(ui.available_width(), 8.0), egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 4.0, egui::Color32::from_rgb(30, 30, 45));
+        
+        let segments = [
+            (summary.succeeded, egui::Color32::from_rgb(100, 200, 100)),
+            (summary.conflicts, egui::Color32::YELLOW),
+            (summary.skipped, egui::Color32::GRAY),
+            (summary.failed, egui::Color32::from_rgb(230, 100, 100)),
+        ];
+        let mut left = rect.left();
+        for (count, color) in segments {
+            let width = rect.width() * count as f32 / summary.total() as f32;
+            if width > 0.0 {
+                let segment = egui::Rect::from_min_max(
+                    egui::Pos2::new(left, rect.top()),
+                    egui::Pos2::new(left + width, rect.bottom()),
+                );
+                painter.rect_filled(segment, 0.0, color);
+            }
+            left += width;
+        }
+    }
+    
+    // Draw the commit activity series as a simple bar chart
+    fn render_activity_chart(ui: &mut egui::Ui, activity: &[(i64, usize)]) {
+        let max_count = activity.iter().map(|(_, count)| *count).max().unwrap_or(1).max(1);
+        let (rect, response) = ui.allocate_exact_size(
+            egui::Vec2::new(ui.available_width(), 120.0),
+            egui::Sense::hover(),
+        );
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 4.0, egui::Color32::from_rgb(30, 30, 45));
+        
+        let bar_slot = rect.width() / activity.len() as f32;
+        let bar_width = (bar_slot * 0.8).max(1.0);
+        let mut hovered = None;
+        
+        for (i, (bucket_start, count)) in activity.iter().enumerate() {
+            let height = (rect.height() - 10.0) * (*count as f32 / max_count as f32);
+            let left = rect.left() + i as f32 * bar_slot + (bar_slot - bar_width) / 2.0;
+            let bar = egui::Rect::from_min_max(
+                egui::Pos2::new(left, rect.bottom() - height),
+                egui::Pos2::new(left + bar_width, rect.bottom()),
+            );
+            
+            let slot = egui::Rect::from_min_max(
+                egui::Pos2::new(rect.left() + i as f32 * bar_slot, rect.top()),
+                egui::Pos2::new(rect.left() + (i + 1) as f32 * bar_slot, rect.bottom()),
+            );
+            let is_hovered = response.hover_pos().is_some_and(|pos| slot.contains(pos));
+            if is_hovered {
+                hovered = Some((*bucket_start, *count));
+            }
+            
+            let color = if is_hovered {
+                egui::Color32::from_rgb(150, 150, 230)
+            } else {
+                egui::Color32::from_rgb(100, 100, 180)
+            };
+            painter.rect_filled(bar, 2.0, color);
+        }
+        
+        let format_date = |timestamp: i64| {
+            chrono::DateTime::from_timestamp(timestamp, 0)
+                .map(|datetime| datetime.format("%Y-%m-%d").to_string())
+                .unwrap_or_default()
+        };
+        
+        if let Some((bucket_start, count)) = hovered {
+            response.on_hover_text(format!("{}: {} commits", format_date(bucket_start), count));
+        }
+        
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(format_date(activity[0].0)).weak().size(11.0));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.label(egui::RichText::new(format_date(activity[activity.len() - 1].0)).weak().size(11.0));
+            });
+        });
     }
     
-    // Method to collect statistics
+    // Collect statistics on a background thread so the spinner keeps animating
     fn collect_statistics(&mut self) {
+        if self.stats_receiver.is_some() {
+            return;
+        }
+        
         self.is_operation_running = true;
         self.status_message = "Collecting repository statistics...".to_string();
         
-        // In a real implementation, this would collect actual statistics
-        // For now, we'll just simulate the process
+        let config = self.config.lock().unwrap().clone();
+        let bucket = self.activity_bucket;
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = collect_overall_stats(&config)
+                .map(|stats| (stats, combined_commit_activity(&config, bucket)))
+                .map_err(|e| e.to_string());
+            let _ = sender.send(result);
+        });
         
-        // Simulate some work
-        std::thread::sleep(std::time::Duration::from_millis(500));
+        self.stats_receiver = Some(receiver);
+    }
+    
+    // Pick up the result of a running statistics collection, if it has finished
+    fn poll_statistics(&mut self) {
+        let Some(receiver) = &self.stats_receiver else {
+            return;
+        };
+        
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err("Statistics collection stopped unexpectedly".to_string()),
+        };
         
+        self.stats_receiver = None;
         self.is_operation_running = false;
-        self.status_message = "Statistics collected successfully!".to_string();
+        match result {
+            Ok((stats, activity)) => {
+                self.overall_stats = Some(stats);
+                self.commit_activity = activity;
+                self.status_message = "Statistics collected successfully!".to_string();
+            }
+            Err(e) => {
+                self.status_message = format!("Failed to collect statistics: {}", e);
+            }
+        }
+    }
+    
+    // List the repositories of a GitHub user or organization on a background thread
+    fn start_github_import(&mut self) {
+        if self.github_import_receiver.is_some() {
+            return;
+        }
+        
+        let owner = self.github_import_owner.trim().to_string();
+        let token = self.github_import_token.trim().to_string();
+        if owner.is_empty() || token.is_empty() {
+            self.status_message = "Enter a GitHub user or organization and a token to import from".to_string();
+            return;
+        }
+        
+        self.status_message = format!("Listing GitHub repositories of {}...", owner);
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            // The GUI thread already runs inside the main runtime, so the request gets its own
+            let result = tokio::runtime::Runtime::new()
+                .map_err(anyhow::Error::from)
+                .and_then(|runtime| runtime.block_on(list_github_repos(&token, &owner)))
+                .map_err(|e| match e.downcast_ref::<RateLimited>() {
+                    // Already says what happened and when to try again
+                    Some(rate_limited) => rate_limited.to_string(),
+                    None => format!("Failed to import from GitHub: {}", e),
+                });
+            let _ = sender.send(result);
+        });
+        
+        self.github_import_receiver = Some(receiver);
+    }
+    
+    // Add the imported repositories to the config once the listing has finished
+    fn poll_github_import(&mut self) {
+        let Some(receiver) = &self.github_import_receiver else {
+            return;
+        };
+        
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err("Failed to import from GitHub: the import stopped unexpectedly".to_string()),
+        };
+        
+        self.github_import_receiver = None;
+        let repositories = match result {
+            Ok(repositories) => repositories,
+            Err(e) => {
+                self.status_message = e;
+                return;
+            }
+        };
+        
+        let mut config = self.config.lock().unwrap();
+        self.undo_stack.push(config.clone());
+        let total = repositories.len();
+        let mut imported = 0;
+        for repo_info in repositories {
+            // Repositories that are already configured, or whose name is taken, are left alone
+            if config.find_duplicate(&repo_info).is_none() {
+                config.add_repository(repo_info);
+                imported += 1;
+            }
+        }
+        drop(config);
+        
+        self.status_message = format!(
+            "Imported {} of {} repositories from {} ({} already configured)",
+            imported,
+            total,
+            self.github_import_owner.trim(),
+            total - imported
+        );
+        if let Err(e) = self.save_current_configuration() {
+            self.status_message = e;
+        }
+    }
+    
+    // Save the statistics to a file picked by the user
+    fn export_statistics(&mut self, format: StatsExportFormat) {
+        let (extension, filter_name) = match format {
+            StatsExportFormat::Json => ("json", "JSON"),
+            StatsExportFormat::Csv => ("csv", "CSV"),
+        };
+        
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(format!("repository_stats.{}", extension))
+            .add_filter(filter_name, &[extension])
+            .save_file()
+        else {
+            return;
+        };
+        
+        // Export what is shown in the tab, collecting it first if needed
+        let stats = match &self.overall_stats {
+            Some(stats) => stats.clone(),
+            None => {
+                let config = self.config.lock().unwrap();
+                match collect_overall_stats(&config) {
+                    Ok(stats) => stats,
+                    Err(e) => {
+                        self.status_message = format!("Failed to collect statistics: {}", e);
+                        return;
+                    }
+                }
+            }
+        };
+        
+        let path = path.to_string_lossy().to_string();
+        let result = match format {
+            StatsExportFormat::Json => export_stats_json(&stats, &path),
+            StatsExportFormat::Csv => export_stats_csv(&stats, &path),
+        };
+        
+        self.status_message = match result {
+            Ok(()) => format!("Statistics exported to {}", path),
+            Err(e) => format!("Failed to export statistics: {}", e),
+        };
     }
     
-    // Method to apply theme based on current setting
-    fn apply_theme(&mut self, ctx: &egui::Context) {
-        if self.dark_mode {
+    // Set the visuals of the given theme
+    fn apply_theme(ctx: &egui::Context, theme: Theme) {
+        if theme == Theme::Dark {
             // Dark theme
             let mut visuals = egui::Visuals::dark();
             visuals.panel_fill = egui::Color32::from_rgb(25, 25, 35); // Deep dark background
@@ -425,6 +1057,24 @@ impl MultiRepoPusherApp {
         }
     }
     
+    // What is restored the next time the app starts
+    fn current_settings(&self) -> AppSettings {
+        AppSettings {
+            theme: self.theme,
+            window_size: self.window_size,
+            active_tab: self.active_tab,
+        }
+    }
+    
+    // Switch between the dark and light theme and remember the choice
+    fn toggle_theme(&mut self, ctx: &egui::Context) {
+        self.theme = self.theme.toggled();
+        Self::apply_theme(ctx, self.theme);
+        if let Err(e) = self.current_settings().save(SETTINGS_FILE) {
+            self.status_message = format!("Failed to save settings: {}", e);
+        }
+    }
+    
     // Batch operations for repository groups
     fn push_to_group_repositories(&mut self) {
         if self.selected_group.is_empty() {
@@ -432,29 +1082,21 @@ impl MultiRepoPusherApp {
             return;
         }
         
-        self.is_operation_running = true;
-        self.status_message = format!("Pushing to repositories in group '{}'...", self.selected_group);
-        self.operation_results.clear();
-        
-        // Clone config for iteration
-        let config_clone = self.config.clone();
-        let config = config_clone.lock().unwrap();
+        let config = self.config.lock().unwrap().clone();
         let commit_message = self.commit_message.clone();
         let branch_name = self.branch_name.clone();
         let group_name = self.selected_group.clone();
+        let status = format!("Pushing to repositories in group '{}'...", group_name);
         
         // Push to all repositories in the group
-        self.operation_results = push_to_group_repositories(&config, &group_name, &commit_message, &branch_name);
-        
-        // Check if any operations failed
-        let failed_count = self.operation_results.iter().filter(|(_, status)| !status.contains("Success")).count();
-        if failed_count > 0 {
-            self.status_message = format!("Push to group completed with {} errors!", failed_count);
-        } else {
-            self.status_message = "Push to group completed successfully!".to_string();
-        }
-        
-        self.is_operation_running = false;
+        self.start_operation(Operation::PushGroup, status, move |report, progress| {
+            let mut results = Vec::new();
+            push_to_group_repositories_reporting(&config, &group_name, &commit_message, &branch_name, &mut |result| {
+                results.push(result.clone());
+                report(result);
+            }, progress);
+            send_push_notifications(&config, &results);
+        });
     }
     
     fn pull_from_group_repositories(&mut self) {
@@ -463,28 +1105,16 @@ impl MultiRepoPusherApp {
             return;
         }
         
-        self.is_operation_running = true;
-        self.status_message = format!("Pulling from repositories in group '{}'...", self.selected_group);
-        self.operation_results.clear();
-        
-        // Clone config for iteration
-        let config_clone = self.config.clone();
-        let config = config_clone.lock().unwrap();
+        let config = self.config.lock().unwrap().clone();
         let branch_name = self.branch_name.clone();
         let group_name = self.selected_group.clone();
+        let auto_stash = self.auto_stash_on_pull;
+        let status = format!("Pulling from repositories in group '{}'...", group_name);
         
         // Pull from all repositories in the group
-        self.operation_results = pull_from_group_repositories(&config, &group_name, &branch_name);
-        
-        // Check if any operations failed
-        let failed_count = self.operation_results.iter().filter(|(_, status)| !status.contains("Success")).count();
-        if failed_count > 0 {
-            self.status_message = format!("Pull from group completed with {} errors!", failed_count);
-        } else {
-            self.status_message = "Pull from group completed successfully!".to_string();
-        }
-        
-        self.is_operation_running = false;
+        self.start_operation(Operation::PullGroup, status, move |report, _| {
+            pull_from_group_repositories_reporting(&config, &group_name, &branch_name, auto_stash, report);
+        });
     }
     
     fn fetch_from_group_repositories(&mut self) {
@@ -493,28 +1123,15 @@ impl MultiRepoPusherApp {
             return;
         }
         
-        self.is_operation_running = true;
-        self.status_message = format!("Fetching from repositories in group '{}'...", self.selected_group);
-        self.operation_results.clear();
-        
-        // Clone config for iteration
-        let config_clone = self.config.clone();
-        let config = config_clone.lock().unwrap();
+        let config = self.config.lock().unwrap().clone();
         let branch_name = self.branch_name.clone();
         let group_name = self.selected_group.clone();
+        let status = format!("Fetching from repositories in group '{}'...", group_name);
         
         // Fetch from all repositories in the group
-        self.operation_results = fetch_from_group_repositories(&config, &group_name, &branch_name);
-        
-        // Check if any operations failed
-        let failed_count = self.operation_results.iter().filter(|(_, status)| !status.contains("Success")).count();
-        if failed_count > 0 {
-            self.status_message = format!("Fetch from group completed with {} errors!", failed_count);
-        } else {
-            self.status_message = "Fetch from group completed successfully!".to_string();
-        }
-        
-        self.is_operation_running = false;
+        self.start_operation(Operation::FetchGroup, status, move |report, progress| {
+            fetch_from_group_repositories_reporting(&config, &group_name, &branch_name, report, progress);
+        });
     }
     
     fn create_and_push_tag(&mut self) {
@@ -523,13 +1140,7 @@ impl MultiRepoPusherApp {
             return;
         }
         
-        self.is_operation_running = true;
-        self.status_message = "Creating and pushing tag...".to_string();
-        self.operation_results.clear();
-        
-        // Clone config for iteration
-        let config_clone = self.config.clone();
-        let config = config_clone.lock().unwrap();
+        let config = self.config.lock().unwrap().clone();
         let tag_name = self.tag_name.clone();
         let tag_message = if self.tag_message.is_empty() {
             format!("Release {}", tag_name)
@@ -537,37 +1148,48 @@ impl MultiRepoPusherApp {
             self.tag_message.clone()
         };
         
-        // Get the current repository
-        let repo = match git2::Repository::open(".") {
-            Ok(repo) => repo,
-            Err(e) => {
-                self.status_message = format!("Failed to open repository: {}", e);
-                self.is_operation_running = false;
-                return;
-            }
-        };
-        
         // Create and push tag for all repositories
-        for repo_info in &config.repositories {
-            match create_and_push_tag(&repo, repo_info, &tag_name, &tag_message) {
-                Ok(_) => {
-                    self.operation_results.push((repo_info.name.clone(), "Success".to_string()));
-                }
-                Err(e) => {
-                    self.operation_results.push((repo_info.name.clone(), e.to_string()));
-                }
+        self.start_operation(Operation::Tag, "Creating and pushing tag...".to_string(), move |report, _| {
+            tag_all_repositories_reporting(&config, &tag_name, &tag_message, report);
+        });
+    }
+    
+    fn generate_changelog(&mut self) {
+        let to_ref = if self.changelog_to.trim().is_empty() { "HEAD" } else { self.changelog_to.trim() };
+        
+        match generate_changelog(".", self.changelog_from.trim(), to_ref) {
+            Ok(changelog) => {
+                self.status_message = "Changelog generated".to_string();
+                self.changelog = Some(changelog);
+            }
+            Err(e) => {
+                self.status_message = format!("Failed to generate changelog: {}", e);
+                self.changelog = None;
             }
         }
+    }
+    
+    // Prefill the tag with the version after the latest vX.Y.Z tag of the local repository
+    fn bump_tag(&mut self, bump: Bump) {
+        let latest = git2::Repository::open(".")
+            .ok()
+            .and_then(|repo| latest_semver_tag(&repo));
+        // Without a version tag yet, count from v0.0.0
+        let current = latest.clone().unwrap_or_else(|| "v0.0.0".to_string());
         
-        // Check if any operations failed
-        let failed_count = self.operation_results.iter().filter(|(_, status)| !status.contains("Success")).count();
-        if failed_count > 0 {
-            self.status_message = format!("Tag creation and push completed with {} errors!", failed_count);
-        } else {
-            self.status_message = "Tag creation and push completed successfully!".to_string();
+        match next_semver(&current, bump) {
+            Ok(next) => {
+                self.status_message = match latest {
+                    Some(latest) => format!("Latest tag is {}, next is {}", latest, next),
+                    None => format!("No version tags yet, starting at {}", next),
+                };
+                self.tag_message = format!("Release {}", next);
+                self.tag_name = next;
+            }
+            Err(e) => {
+                self.status_message = e.to_string();
+            }
         }
-        
-        self.is_operation_running = false;
     }
     
     fn check_merge_conflicts(&mut self) {
@@ -587,23 +1209,135 @@ impl MultiRepoPusherApp {
         
         match check_merge_conflicts(&repo) {
             Ok(has_conflicts) => {
+                self.has_merge_conflicts = has_conflicts;
                 if has_conflicts {
                     self.status_message = "Merge conflicts detected!".to_string();
-                    self.operation_results.push(("Repository".to_string(), "Conflicts detected".to_string()));
+                    self.conflicted_files = conflicted_files(&repo).unwrap_or_default();
+                    self.show_conflicts_modal = true;
                 } else {
                     self.status_message = "No merge conflicts found".to_string();
-                    self.operation_results.push(("Repository".to_string(), "No conflicts".to_string()));
                 }
+                let (status, message) = conflict_check_status(has_conflicts);
+                self.operation_results.push(("Repository".to_string(), status, message));
             }
             Err(e) => {
                 self.status_message = format!("Error checking conflicts: {}", e);
-                self.operation_results.push(("Repository".to_string(), e.to_string()));
+                self.operation_results.push(("Repository".to_string(), ResultStatus::Failed, e.to_string()));
             }
         }
         
         self.is_operation_running = false;
     }
     
+    fn find_stale_branches(&mut self) {
+        let repo = match git2::Repository::open(".") {
+            Ok(repo) => repo,
+            Err(e) => {
+                self.status_message = format!("Failed to open repository: {}", e);
+                return;
+            }
+        };
+        
+        match stale_branches(&repo, self.stale_branch_days) {
+            Ok(branches) => {
+                self.status_message = format!(
+                    "Found {} branches without commits in the last {} days",
+                    branches.len(),
+                    self.stale_branch_days
+                );
+                self.stale_branch_results = Some(branches);
+            }
+            Err(e) => {
+                self.status_message = format!("Error finding stale branches: {}", e);
+                self.stale_branch_results = None;
+            }
+        }
+    }
+    
+    // Read the remotes of the local repository, to compare them with the configuration
+    fn load_repo_remotes(&mut self) {
+        let result = git2::Repository::open(".")
+            .map_err(anyhow::Error::from)
+            .and_then(|repo| list_repo_remotes(&repo));
+        
+        match result {
+            Ok(remotes) => {
+                self.status_message = format!("Found {} remotes", remotes.len());
+                self.repo_remotes = Some(remotes.into_iter().map(|(name, url)| (name, url.clone(), url)).collect());
+            }
+            Err(e) => {
+                self.status_message = format!("Error reading remotes: {}", e);
+                self.repo_remotes = None;
+            }
+        }
+    }
+    
+    fn apply_remote_url(&mut self, name: &str, url: &str) {
+        let result = git2::Repository::open(".")
+            .map_err(anyhow::Error::from)
+            .and_then(|repo| set_remote_url(&repo, name, url.trim()));
+        
+        match result {
+            Ok(()) => {
+                self.load_repo_remotes();
+                self.status_message = format!("Remote '{}' now points at {}", name, url.trim());
+            }
+            Err(e) => self.status_message = format!("Failed to update remote '{}': {}", name, e),
+        }
+    }
+    
+    // Point the remotes of the local repository at the configured URLs
+    fn sync_remotes_from_config(&mut self) {
+        let config = self.config.lock().unwrap().clone();
+        let result = git2::Repository::open(".")
+            .map_err(anyhow::Error::from)
+            .and_then(|repo| sync_remotes_from_config(&repo, &config));
+        
+        match result {
+            Ok(changed) => {
+                self.load_repo_remotes();
+                self.status_message = if changed.is_empty() {
+                    "Remotes already match the configuration".to_string()
+                } else {
+                    format!("Updated remotes: {}", changed.join(", "))
+                };
+            }
+            Err(e) => self.status_message = format!("Failed to sync remotes: {}", e),
+        }
+    }
+    
+    // Run git gc in the local clone of a repository in the background
+    fn optimize_repository(&mut self, repo: &RepositoryInfo) {
+        let name = repo.name.clone();
+        let local_path = repo.local_path.clone();
+        self.start_operation(Operation::Maintenance, format!("Optimizing {}...", name), move |report, _progress| {
+            let result = match run_maintenance(&local_path) {
+                Ok(summary) => (name, ResultStatus::Success, format!("Success: {}", summary)),
+                Err(e) => (name, ResultStatus::Failed, format!("Optimization failed: {}", e)),
+            };
+            report(result);
+        });
+    }
+    
+    // Check which submodules of the local repository still need to be initialized or updated
+    fn check_submodules(&mut self) {
+        let result = git2::Repository::open(".")
+            .map_err(anyhow::Error::from)
+            .and_then(|repo| submodule_status(&repo));
+        
+        match result {
+            Ok(submodules) => {
+                let uninitialized = submodules.iter().filter(|submodule| !submodule.initialized).count();
+                self.status_message = format!("Found {} submodules, {} not initialized", submodules.len(), uninitialized);
+                self.submodules = Some(submodules);
+            }
+            Err(e) => {
+                self.status_message = format!("Error checking submodules: {}", e);
+                self.submodules = None;
+            }
+        }
+    }
+    
     // New method for cloning all repositories
     fn clone_all_repositories(&mut self) {
         if self.clone_destination_path.is_empty() {
@@ -611,26 +1345,95 @@ impl MultiRepoPusherApp {
             return;
         }
         
-        self.is_operation_running = true;
-        self.status_message = "Cloning repositories...".to_string();
+        let config = self.config.lock().unwrap().clone();
+        let destination = self.clone_destination_path.clone();
+        let skip_existing = self.clone_skip_existing;
+        let operation = Operation::Clone { destination: destination.clone() };
+        
+        // Clone all repositories
+        self.start_operation(operation, "Cloning repositories...".to_string(), move |report, progress| {
+            clone_all_repositories_reporting(&config, &destination, skip_existing, report, progress);
+        });
+    }
+    
+    fn run_health_check(&mut self) {
+        let config = self.config.lock().unwrap().clone();
+        
+        // Only connects to each remote, nothing is transferred
+        self.start_operation(Operation::HealthCheck, "Checking remotes...".to_string(), move |report, _progress| {
+            health_check_all_reporting(&config, report);
+        });
+    }
+    
+    // Run a batch operation in the background, streaming its results into the results area
+    fn start_operation<F>(&mut self, operation: Operation, status_message: String, work: F)
+    where
+        F: FnOnce(&mut dyn FnMut((String, ResultStatus, String)), &(dyn Fn(&str, TransferProgress) + Sync)) + Send + 'static,
+    {
+        if self.operation_runner.is_running() {
+            return;
+        }
+        
         self.operation_results.clear();
+        self.operation_runner.start(operation, work);
+        self.is_operation_running = true;
+        self.status_message = status_message;
+    }
+    
+    // Pick up results of the running operation and wrap up once it has finished
+    fn poll_operation(&mut self) {
+        let Some(operation) = self.operation_runner.poll(&mut self.operation_results) else {
+            return;
+        };
+        self.is_operation_running = false;
         
-        // Clone config for iteration
-        let config_clone = self.config.clone();
-        let config = config_clone.lock().unwrap();
+        if let Some(name) = operation.history_name() {
+            self.history_viewer.record(name, &self.operation_results);
+        }
         
-        // Clone all repositories
-        self.operation_results = clone_all_repositories(&config, &self.clone_destination_path);
+        match &operation {
+            // The commit consumed the staged changes
+            Operation::Push => self.refresh_working_changes(),
+            Operation::Clone { destination } => {
+                // Remember where each cloned repository lives, including the ones that already were
+                let mut config = self.config.lock().unwrap();
+                for (repo_name, status, message) in &self.operation_results {
+                    if *status != ResultStatus::Success && message != ALREADY_CLONED {
+                        continue;
+                    }
+                    if let Some(repo_info) = config.repositories.iter_mut().find(|repo| &repo.name == repo_name) {
+                        repo_info.local_path = format!("{}/{}", destination, repo_info.name);
+                    }
+                }
+                drop(config);
+                if let Err(e) = self.save_current_configuration() {
+                    self.status_message = e;
+                }
+            },
+            _ => {}
+        }
         
         // Check if any operations failed
-        let failed_count = self.operation_results.iter().filter(|(_, status)| !status.contains("Success")).count();
+        let summary = summarize_results(&self.operation_results);
+        let failed_count = summary.failed + summary.conflicts;
         if failed_count > 0 {
-            self.status_message = format!("Cloning completed with {} errors!", failed_count);
+            self.status_message = format!("{} completed with {} errors!", operation.label(), failed_count);
         } else {
-            self.status_message = "Cloning completed successfully!".to_string();
+            self.status_message = format!("{} completed successfully!", operation.label());
         }
         
-        self.is_operation_running = false;
+        // A pull that stopped on conflicts leaves them in the local repository
+        let pulled = matches!(operation, Operation::Pull | Operation::PullGroup);
+        if pulled && self.operation_results.iter().any(|(_, status, _)| *status == ResultStatus::Conflict) && let Ok(repo) = git2::Repository::open(".") {
+            self.conflicted_files = conflicted_files(&repo).unwrap_or_default();
+            self.show_conflicts_modal = !self.conflicted_files.is_empty();
+            self.has_merge_conflicts = check_merge_conflicts(&repo).unwrap_or(false);
+            self.status_message = format!(
+                "{} left {} conflicted files, see the Advanced tab",
+                operation.label(),
+                self.conflicted_files.len()
+            );
+        }
     }
     
     // New method for creating a repository group
@@ -646,6 +1449,7 @@ impl MultiRepoPusherApp {
         );
         
         let mut config = self.config.lock().unwrap();
+        self.undo_stack.push(config.clone());
         config.add_group(group);
         
         self.status_message = format!("Group '{}' created successfully", self.new_group_name);
@@ -656,6 +1460,104 @@ impl MultiRepoPusherApp {
         self.show_group_form = false;
     }
     
+    // Snapshot the configuration before an edit so it can be undone
+    fn record_undo(&mut self) {
+        let snapshot = self.config.lock().unwrap().clone();
+        self.undo_stack.push(snapshot);
+    }
+    
+    // Put the configuration back as it was before the last edit
+    fn undo_config_edit(&mut self) {
+        let undone = self.undo_stack.undo(&mut self.config.lock().unwrap());
+        if !undone {
+            self.status_message = "Nothing to undo".to_string();
+            return;
+        }
+        
+        // Indices into the list before the undo
+        self.selected_repositories.clear();
+        self.selected_account_index = 0;
+        self.status_message = "Last configuration change undone".to_string();
+        if let Err(e) = self.save_current_configuration() {
+            self.status_message = e;
+        }
+    }
+    
+    // Remove the repository at `index` from the configuration, once confirmed
+    fn remove_repository(&mut self, index: usize) {
+        self.record_undo();
+        self.config.lock().unwrap().remove_repository(index);
+        // The indices after the removed repository have shifted
+        self.selected_repositories.clear();
+        if let Err(e) = self.save_current_configuration() {
+            self.status_message = e;
+        }
+    }
+    
+    // Delete a group, once confirmed. Its repositories stay configured.
+    fn remove_group(&mut self, group_name: &str) {
+        self.record_undo();
+        {
+            let mut config = self.config.lock().unwrap();
+            config.remove_group(group_name);
+            for repo in config.repositories.iter_mut().filter(|repo| repo.group == group_name) {
+                repo.group.clear();
+            }
+        }
+        if self.selected_group == group_name {
+            self.selected_group.clear();
+        }
+        self.status_message = format!("Group '{}' deleted", group_name);
+        if let Err(e) = self.save_current_configuration() {
+            self.status_message = e;
+        }
+    }
+    
+    // Show the question for the pending action and run it once confirmed
+    fn render_confirmation(&mut self, ctx: &egui::Context) {
+        let Some(action) = &self.pending_confirmation else {
+            return;
+        };
+        let answer = confirm_action(ctx, action.title(), &action.message());
+        if let Some(action) = resolve(&mut self.pending_confirmation, answer) {
+            self.perform_action(action);
+        }
+    }
+    
+    fn perform_action(&mut self, action: PendingAction) {
+        match action {
+            PendingAction::RemoveRepository { index, .. } => self.remove_repository(index),
+            PendingAction::DeleteAccount { index, .. } => self.delete_account(index),
+            PendingAction::RemoveGroup { name } => self.remove_group(&name),
+            PendingAction::AbortMerge => self.abort_merge(),
+            PendingAction::PushAll { .. } => self.push_to_all_repositories(),
+        }
+    }
+    
+    // Rename the selected group, moving its repositories along
+    fn rename_selected_group(&mut self) {
+        let new_name = self.rename_group_name.trim().to_string();
+        if new_name.is_empty() {
+            self.status_message = "Please enter a new group name".to_string();
+            return;
+        }
+        
+        let snapshot = self.config.lock().unwrap().clone();
+        let renamed = self.config.lock().unwrap().rename_group(&self.selected_group, &new_name);
+        match renamed {
+            Ok(()) => {
+                self.undo_stack.push(snapshot);
+                self.status_message = format!("Group '{}' renamed to '{}'", self.selected_group, new_name);
+                self.selected_group = new_name;
+                self.rename_group_name.clear();
+                if let Err(e) = self.save_current_configuration() {
+                    self.status_message = e;
+                }
+            },
+            Err(e) => self.status_message = e.to_string(),
+        }
+    }
+    
     // New method for adding a repository to a group
     fn add_repository_to_group(&mut self, repo_index: usize, group_name: String) {
         let mut config = self.config.lock().unwrap();
@@ -670,6 +1572,7 @@ impl MultiRepoPusherApp {
         let repo_name = config.repositories[repo_index].name.clone();
         
         // Add the repository to the group
+        self.undo_stack.push(config.clone());
         if let Some(group) = config.get_group_mut(&group_name) {
             group.add_repository(repo_name.clone());
             // Also update the repository's group field
@@ -680,6 +1583,88 @@ impl MultiRepoPusherApp {
         }
     }
     
+    // Check the credentials of a repository and report the outcome in the status bar
+    fn validate_repository(&mut self, repo: &RepositoryInfo) {
+        // Tokens are checked against the host's API, which can say why a token doesn't work
+        if repo.auth_type == AuthType::Token && !repo.auth_token.is_empty() {
+            self.status_message = match provider_for_url(&repo.url).check_token(&repo.auth_token) {
+                TokenStatus::Valid { expires_at: Some(expires_at) } => {
+                    format!("Repository {} token is valid (token expires on {})", repo.name, expires_at)
+                },
+                TokenStatus::Valid { expires_at: None } => {
+                    format!("Repository {} authentication verified", repo.name)
+                },
+                TokenStatus::Invalid => {
+                    format!("The token for repository {} was rejected. It may have expired or been revoked; please generate a new one.", repo.name)
+                },
+                TokenStatus::Unreachable(reason) => {
+                    format!("Network error for repository {}: couldn't check the token ({}). Please check your connection.", repo.name, reason)
+                }
+            };
+            return;
+        }
+        
+        match verify_authentication(repo) {
+            Ok(true) => {
+                self.status_message = format!("Repository {} authentication verified", repo.name);
+            },
+            Ok(false) => {
+                self.status_message = format!("Repository {} authentication failed. Please check your credentials.", repo.name);
+            },
+            Err(e) => {
+                let error_msg = e.to_string();
+                if error_msg.contains("authentication") || error_msg.contains("Authentication") {
+                    self.status_message = format!("Authentication failed for repository {}. Please check your credentials.", repo.name);
+                } else if error_msg.contains("network") || error_msg.contains("Network") {
+                    self.status_message = format!("Network error for repository {}. Please check your connection.", repo.name);
+                } else {
+                    self.status_message = format!("Validation error for {}: {}", repo.name, error_msg);
+                }
+            }
+        }
+    }
+    
+    // Switch a repository between its SSH and HTTPS URL and pick an auth type that fits
+    fn convert_repository_url(&mut self, repo_name: &str, repo_url: &str) {
+        let mut config = self.config.lock().unwrap();
+        let snapshot = config.clone();
+        let Some(repo_info) = config.repositories
+            .iter_mut()
+            .find(|repo| repo.name == repo_name && repo.url == repo_url)
+        else {
+            self.status_message = "Repository not found".to_string();
+            return;
+        };
+        
+        let converted = if repo_info.url.starts_with("git@") {
+            to_https_url(&repo_info.url).map(|url| (url, AuthType::Token))
+        } else {
+            to_ssh_url(&repo_info.url).map(|url| (url, AuthType::SSH))
+        };
+        
+        let Some((url, auth_type)) = converted else {
+            self.status_message = format!("Can't convert the URL of '{}'", repo_name);
+            return;
+        };
+        
+        self.undo_stack.push(snapshot);
+        // ssh-agent and the credential helper already fit the new scheme
+        let keep_auth = matches!(
+            (&repo_info.auth_type, &auth_type),
+            (AuthType::SshAgent, AuthType::SSH) | (AuthType::CredentialHelper, AuthType::Token)
+        );
+        if !keep_auth {
+            repo_info.auth_type = auth_type;
+        }
+        self.status_message = format!("Repository '{}' now uses {}", repo_name, url);
+        repo_info.url = url;
+        drop(config);
+        
+        if let Err(e) = self.save_current_configuration() {
+            self.status_message = e;
+        }
+    }
+    
     // New method for removing a repository from a group
     fn remove_repository_from_group(&mut self, repo_index: usize, group_name: String) {
         let mut config = self.config.lock().unwrap();
@@ -694,6 +1679,7 @@ impl MultiRepoPusherApp {
         let repo_name = config.repositories[repo_index].name.clone();
         
         // Remove the repository from the group
+        self.undo_stack.push(config.clone());
         config.remove_repository_from_group(&repo_name, &group_name);
         self.status_message = format!("Repository '{}' removed from group '{}'", repo_name, group_name);
     }
@@ -705,8 +1691,8 @@ impl MultiRepoPusherApp {
         }
         
         // Validate repository URL
-        if !validate_repository_url(&self.new_repo_url) {
-            self.status_message = "Invalid repository URL format".to_string();
+        if let Err(e) = parse_repository_url(&self.new_repo_url) {
+            self.status_message = format!("Invalid repository URL: {}", e);
             return;
         }
         
@@ -724,12 +1710,32 @@ impl MultiRepoPusherApp {
             },
             AuthType::SSH => {
                 repo_info.ssh_key_path = self.new_repo_ssh_key.clone();
+                repo_info.ssh_passphrase = optional_passphrase(&self.new_repo_ssh_passphrase);
             },
             _ => {}
         }
         
-        // Add to config
         let mut config = self.config.lock().unwrap();
+        match config.find_duplicate(&repo_info) {
+            Some(DuplicateRepository::Url(existing)) => {
+                self.status_message = format!("This repository is already configured as '{}'", existing);
+                return;
+            },
+            Some(DuplicateRepository::Name) if self.confirmed_duplicate_name.as_ref() != Some(&repo_info.name) => {
+                // Ask once, a second click on Add confirms
+                self.status_message = format!(
+                    "A repository named '{}' already exists and both would share one remote. Click Add again to add it anyway.",
+                    repo_info.name
+                );
+                self.confirmed_duplicate_name = Some(repo_info.name.clone());
+                return;
+            },
+            _ => {}
+        }
+        self.confirmed_duplicate_name = None;
+        
+        // Add to config
+        self.undo_stack.push(config.clone());
         config.add_repository(repo_info);
         
         // Clear form fields
@@ -737,6 +1743,7 @@ impl MultiRepoPusherApp {
         self.new_repo_url.clear();
         self.new_repo_token.clear();
         self.new_repo_ssh_key.clear();
+        self.new_repo_ssh_passphrase.clear();
         self.status_message = "Repository added successfully".to_string();
     }
     
@@ -751,6 +1758,13 @@ impl MultiRepoPusherApp {
         self.status_message = "Validating account and saving configuration...".to_string();
         self.is_operation_running = true;
         
+        // Remember the GPG key so future commits get signed
+        let signing_key = self.account_signing_key.trim().to_string();
+        if !signing_key.is_empty() {
+            let mut config = self.config.lock().unwrap();
+            config.signing_key = Some(signing_key);
+        }
+        
         // In a real implementation, you would validate the account credentials here
         // For now, we'll just show a success message
         self.is_operation_running = false;
@@ -764,6 +1778,8 @@ impl MultiRepoPusherApp {
         self.account_email.clear();
         self.account_token.clear();
         self.account_ssh_key_path.clear();
+        self.account_ssh_passphrase.clear();
+        self.account_signing_key.clear();
     }
     
     // New function to save account changes
@@ -774,8 +1790,8 @@ impl MultiRepoPusherApp {
         }
         
         // Validate repository URL
-        if !validate_repository_url(&self.edit_account_url) {
-            self.status_message = "Invalid repository URL format".to_string();
+        if let Err(e) = parse_repository_url(&self.edit_account_url) {
+            self.status_message = format!("Invalid repository URL: {}", e);
             return;
         }
         
@@ -795,11 +1811,22 @@ impl MultiRepoPusherApp {
                 },
                 AuthType::SSH => {
                     repo_info.ssh_key_path = self.edit_account_ssh_key.clone();
+                    repo_info.ssh_passphrase = optional_passphrase(&self.edit_account_ssh_passphrase);
                 },
                 _ => {}
             }
             
+            // Commit identity for this account, empty fields fall back to git config
+            let author_name = self.edit_account_author_name.trim();
+            let author_email = self.edit_account_author_email.trim();
+            repo_info.author_name = (!author_name.is_empty()).then(|| author_name.to_string());
+            repo_info.author_email = (!author_email.is_empty()).then(|| author_email.to_string());
+            repo_info.local_path = self.edit_account_local_path.trim().to_string();
+            let default_branch = self.edit_account_default_branch.trim();
+            repo_info.default_branch = (!default_branch.is_empty()).then(|| default_branch.to_string());
+            
             // Replace the repository at the selected index
+            self.undo_stack.push(config.clone());
             config.repositories[self.selected_account_index] = repo_info;
             
             self.status_message = "Account changes saved successfully".to_string();
@@ -808,12 +1835,65 @@ impl MultiRepoPusherApp {
         }
     }
     
-    // New function to delete the selected account
-    fn delete_selected_account(&mut self) {
+    // Change the position of a repository, which is the order batch operations use
+    fn move_repository(&mut self, from: usize, to: usize) {
+        self.record_undo();
+        self.config.lock().unwrap().move_repository(from, to);
+        // The selection is by index, so it no longer points at the same repositories
+        self.selected_repositories.clear();
+        if let Err(e) = self.save_current_configuration() {
+            self.status_message = e;
+        }
+    }
+    
+    // Include or exclude repositories from batch operations without removing them
+    fn set_repositories_enabled(&mut self, indices: &[usize], enabled: bool) {
+        self.record_undo();
+        {
+            let mut config = self.config.lock().unwrap();
+            for &index in indices {
+                if let Some(repo) = config.repositories.get_mut(index) {
+                    repo.enabled = enabled;
+                }
+            }
+        }
+        if let Err(e) = self.save_current_configuration() {
+            self.status_message = e;
+        }
+    }
+    
+    // Fill in the default branch of the selected account from its remote HEAD
+    fn detect_default_branch(&mut self) {
+        let Some(mut repo_info) = self.config.lock().unwrap().repositories.get(self.selected_account_index).cloned() else {
+            self.status_message = "Invalid account selection".to_string();
+            return;
+        };
+        // Ask the URL being edited, not the saved one
+        repo_info.url = self.edit_account_url.trim().to_string();
+        
+        let result = git2::Repository::open(".")
+            .map_err(anyhow::Error::from)
+            .and_then(|repo| detect_default_branch(&repo, &repo_info));
+        match result {
+            Ok(branch) => {
+                self.status_message = format!("The default branch of {} is {}", repo_info.name, branch);
+                self.edit_account_default_branch = branch;
+            }
+            Err(e) => {
+                self.status_message = format!("Failed to detect the default branch: {}", e);
+            }
+        }
+    }
+    
+    // Delete the account at `index`, once confirmed
+    fn delete_account(&mut self, index: usize) {
         let mut config = self.config.lock().unwrap();
-        if self.selected_account_index < config.repositories.len() {
-            let repo_name = config.repositories[self.selected_account_index].name.clone();
-            config.repositories.remove(self.selected_account_index);
+        if index < config.repositories.len() {
+            let repo_name = config.repositories[index].name.clone();
+            self.undo_stack.push(config.clone());
+            config.repositories.remove(index);
+            // The indices after the deleted account have shifted
+            self.selected_repositories.clear();
             
             // Adjust selected index if needed
             if !config.repositories.is_empty() && self.selected_account_index >= config.repositories.len() {
@@ -827,8 +1907,17 @@ impl MultiRepoPusherApp {
             self.edit_account_url.clear();
             self.edit_account_token.clear();
             self.edit_account_ssh_key.clear();
+            self.edit_account_ssh_passphrase.clear();
+            self.edit_account_author_name.clear();
+            self.edit_account_author_email.clear();
+            self.edit_account_local_path.clear();
+            self.edit_account_default_branch.clear();
             
             self.status_message = format!("Account '{}' deleted successfully", repo_name);
+            drop(config);
+            if let Err(e) = self.save_current_configuration() {
+                self.status_message = e;
+            }
         } else {
             self.status_message = "No account selected for deletion".to_string();
         }
@@ -837,12 +1926,37 @@ impl MultiRepoPusherApp {
 
 impl eframe::App for MultiRepoPusherApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Apply theme
-        self.apply_theme(ctx);
+        // Remember the window size for the next start
+        if let Some(rect) = ctx.input(|i| i.viewport().inner_rect) {
+            self.window_size = Some([rect.width(), rect.height()]);
+        }
         
         // Update animation timer
         self.animation_timer += ctx.input(|i| i.stable_dt);
         
+        // Keep repainting until background statistics collection is done
+        self.poll_statistics();
+        if self.stats_receiver.is_some() {
+            ctx.request_repaint();
+        }
+        
+        self.poll_github_import();
+        if self.github_import_receiver.is_some() {
+            ctx.request_repaint();
+        }
+        
+        // Stream results of push, pull and the other batch operations
+        self.poll_operation();
+        if self.operation_runner.is_running() {
+            ctx.request_repaint();
+        }
+        
+        // Ctrl+Z undoes configuration edits, unless a text field is taking it
+        let text_focused = ctx.memory(|memory| memory.focused().is_some());
+        if !text_focused && ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z)) {
+            self.undo_config_edit();
+        }
+        
         // Show first-time setup modal if needed
         if self.show_first_time_setup {
             self.render_first_time_setup(ctx);
@@ -889,6 +2003,11 @@ impl eframe::App for MultiRepoPusherApp {
                             self.edit_account_auth_type = repo.auth_type.clone();
                             self.edit_account_token = repo.auth_token.clone();
                             self.edit_account_ssh_key = repo.ssh_key_path.clone();
+                            self.edit_account_ssh_passphrase = repo.ssh_passphrase.clone().unwrap_or_default();
+                            self.edit_account_author_name = repo.author_name.clone().unwrap_or_default();
+                            self.edit_account_author_email = repo.author_email.clone().unwrap_or_default();
+                            self.edit_account_local_path = repo.local_path.clone();
+                            self.edit_account_default_branch = repo.default_branch.clone().unwrap_or_default();
                         }
                         
                         ui.add_space(5.0);
@@ -927,6 +2046,11 @@ impl eframe::App for MultiRepoPusherApp {
                         self.edit_account_auth_type = selected_repo.auth_type.clone();
                         self.edit_account_token = selected_repo.auth_token.clone();
                         self.edit_account_ssh_key = selected_repo.ssh_key_path.clone();
+                        self.edit_account_ssh_passphrase = selected_repo.ssh_passphrase.clone().unwrap_or_default();
+                        self.edit_account_author_name = selected_repo.author_name.clone().unwrap_or_default();
+                        self.edit_account_author_email = selected_repo.author_email.clone().unwrap_or_default();
+                        self.edit_account_local_path = selected_repo.local_path.clone();
+                        self.edit_account_default_branch = selected_repo.default_branch.clone().unwrap_or_default();
                     }
                     
                     ui.label(egui::RichText::new("Name:").strong());
@@ -943,6 +2067,8 @@ impl eframe::App for MultiRepoPusherApp {
                         .show_ui(ui, |ui| {
                             ui.selectable_value(&mut self.edit_account_auth_type, AuthType::Default, "Default");
                             ui.selectable_value(&mut self.edit_account_auth_type, AuthType::SSH, "SSH Key");
+                            ui.selectable_value(&mut self.edit_account_auth_type, AuthType::SshAgent, "SSH Agent");
+                            ui.selectable_value(&mut self.edit_account_auth_type, AuthType::CredentialHelper, "Git Credential Helper");
                             ui.selectable_value(&mut self.edit_account_auth_type, AuthType::Token, "Personal Access Token");
                         });
                     ui.add_space(5.0);
@@ -958,10 +2084,36 @@ impl eframe::App for MultiRepoPusherApp {
                             ui.label(egui::RichText::new("SSH Key Path:").strong());
                             ui.add(egui::TextEdit::singleline(&mut self.edit_account_ssh_key).desired_width(ui.available_width() * 0.8));
                             ui.add_space(5.0);
+                            
+                            ui.label(egui::RichText::new("SSH Key Passphrase:").strong());
+                            ui.add(egui::TextEdit::singleline(&mut self.edit_account_ssh_passphrase).password(true).hint_text("Leave empty for unencrypted keys").desired_width(ui.available_width() * 0.8))
+                                .on_hover_text("Kept until the app closes, passphrases aren't saved to repos.json");
+                            ui.add_space(5.0);
                         },
                         _ => {}
                     }
                     
+                    ui.label(egui::RichText::new("Author Name:").strong());
+                    ui.add(egui::TextEdit::singleline(&mut self.edit_account_author_name).hint_text("git config user.name").desired_width(ui.available_width() * 0.8));
+                    ui.add_space(5.0);
+                    
+                    ui.label(egui::RichText::new("Author Email:").strong());
+                    ui.add(egui::TextEdit::singleline(&mut self.edit_account_author_email).hint_text("git config user.email").desired_width(ui.available_width() * 0.8));
+                    ui.add_space(5.0);
+                    
+                    ui.label(egui::RichText::new("Local Path:").strong());
+                    ui.add(egui::TextEdit::singleline(&mut self.edit_account_local_path).hint_text("Not cloned locally").desired_width(ui.available_width() * 0.8));
+                    ui.add_space(5.0);
+                    
+                    ui.label(egui::RichText::new("Default Branch:").strong());
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut self.edit_account_default_branch).hint_text(FALLBACK_BRANCH).desired_width(ui.available_width() * 0.6));
+                        if ui.button("🔍 Detect").on_hover_text("Ask the remote which branch its HEAD points at").clicked() {
+                            self.detect_default_branch();
+                        }
+                    });
+                    ui.add_space(5.0);
+                    
                     ui.add_space(10.0);
                     
                     // Save and Delete buttons
@@ -989,7 +2141,11 @@ impl eframe::App for MultiRepoPusherApp {
                         .min_size(egui::Vec2::new(80.0, 30.0));
                         
                         if ui.add(delete_button).clicked() {
-                            self.delete_selected_account();
+                            let name = self.config.lock().unwrap().repositories.get(self.selected_account_index).map(|repo| repo.name.clone());
+                            match name {
+                                Some(name) => self.pending_confirmation = Some(PendingAction::DeleteAccount { index: self.selected_account_index, name }),
+                                None => self.status_message = "No account selected for deletion".to_string(),
+                            }
                         }
                     });
                 } else {
@@ -1013,7 +2169,7 @@ impl eframe::App for MultiRepoPusherApp {
                     // Theme switcher
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         let theme_button = egui::Button::new(
-                            egui::RichText::new(if self.dark_mode { "☀️ Light" } else { "🌙 Dark" })
+                            egui::RichText::new(if self.theme == Theme::Dark { "☀️ Light" } else { "🌙 Dark" })
                                 .size(14.0)
                         )
                         .fill(egui::Color32::from_rgb(90, 90, 90))
@@ -1022,8 +2178,17 @@ impl eframe::App for MultiRepoPusherApp {
                         .min_size(egui::Vec2::new(80.0, 25.0));
                         
                         if ui.add(theme_button).clicked() {
-                            self.dark_mode = !self.dark_mode;
-                            self.apply_theme(ctx);
+                            self.toggle_theme(ctx);
+                        }
+                        
+                        let undo_button = egui::Button::new(egui::RichText::new("↶ Undo").size(14.0))
+                            .rounding(egui::Rounding::same(6.0))
+                            .min_size(egui::Vec2::new(70.0, 25.0));
+                        if ui.add_enabled(!self.undo_stack.is_empty(), undo_button)
+                            .on_hover_text(format!("Undo the last configuration change (Ctrl+Z), {} available", self.undo_stack.len()))
+                            .clicked()
+                        {
+                            self.undo_config_edit();
                         }
                     });
                 });
@@ -1039,11 +2204,24 @@ impl eframe::App for MultiRepoPusherApp {
                 ui.visuals_mut().widgets.active.bg_fill = egui::Color32::from_rgb(90, 90, 150);
                 ui.visuals_mut().widgets.active.fg_stroke.color = egui::Color32::WHITE;
                 
+                let previous_tab = self.active_tab;
                 ui.selectable_value(&mut self.active_tab, Tab::Commit, "📝 Commit");
                 ui.selectable_value(&mut self.active_tab, Tab::Repositories, "📂 Repositories");
                 ui.selectable_value(&mut self.active_tab, Tab::CommitHistory, "📜 Commit History");
+                ui.selectable_value(&mut self.active_tab, Tab::Comparison, "🔍 Compare");
                 ui.selectable_value(&mut self.active_tab, Tab::Advanced, "⚙️ Advanced");
                 ui.selectable_value(&mut self.active_tab, Tab::Statistics, "📊 Statistics");
+                ui.selectable_value(&mut self.active_tab, Tab::History, "🕘 History");
+                
+                // Pick up edits made outside the app whenever the Commit tab is opened
+                if self.active_tab == Tab::Commit && previous_tab != Tab::Commit {
+                    self.refresh_working_changes();
+                    self.refresh_current_branch();
+                }
+                // Show what the CLI or another window recorded in the meantime
+                if self.active_tab == Tab::History && previous_tab != Tab::History {
+                    self.history_viewer.refresh();
+                }
             });
             
             ui.separator();
@@ -1052,41 +2230,72 @@ impl eframe::App for MultiRepoPusherApp {
                 Tab::Commit => self.render_commit_tab(ui),
                 Tab::Repositories => self.render_repositories_tab(ui),
                 Tab::CommitHistory => self.commit_history_viewer.render(ui),
+                Tab::Comparison => self.comparison_viewer.render(ui),
                 Tab::Advanced => self.render_advanced_tab(ui),
                 Tab::Statistics => self.render_statistics_tab(ui),
+                Tab::History => self.history_viewer.render(ui),
             }
             
             // Show account form as a modal if needed
             self.render_account_modal(ctx);
             
-            // Show diff viewer modal if needed
-            self.render_diff_viewer(ctx);
+            // Ask before running a destructive action
+            self.render_confirmation(ctx);
             
             // Results section with improved styling
-            if !self.operation_results.is_empty() {
+            if !self.operation_results.is_empty() || !self.operation_runner.transfers().is_empty() {
                 ui.add_space(10.0);
                 
                 ui.group(|ui| {
                     ui.heading("📋 Results");
                     
+                    let summary = summarize_results(&self.operation_results);
+                    if summary.total() > 0 {
+                        ui.label(egui::RichText::new(summary.to_string()).size(14.0));
+                        Self::render_summary_bar(ui, &summary);
+                        ui.add_space(5.0);
+                    }
+                    
                     egui::ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
-                        for (repo_name, status) in &self.operation_results {
+                        // Repositories still transferring, until their result comes in
+                        for (repo_name, transfer) in self.operation_runner.transfers() {
+                            if self.operation_results.iter().any(|(name, _, _)| name == repo_name) {
+                                continue;
+                            }
+                            ui.group(|ui| {
+                                ui.label(egui::RichText::new(repo_name).size(14.0).strong());
+                                ui.add(egui::ProgressBar::new(transfer.fraction()).text(transfer.to_string()));
+                            });
+                        }
+                        
+                        for (repo_name, status, message) in &self.operation_results {
                             ui.group(|ui| {
                                 ui.horizontal(|ui| {
                                     ui.label(egui::RichText::new(repo_name).size(14.0).strong());
                                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                        if status == "Success" || status == "No conflicts" {
-                                            ui.label(egui::RichText::new("✓ Success").color(egui::Color32::GREEN));
-                                        } else if status == "Conflicts detected" {
-                                            ui.label(egui::RichText::new("⚠ Conflicts").color(egui::Color32::YELLOW));
-                                        } else {
-                                            ui.label(egui::RichText::new("✗ Failed").color(egui::Color32::RED));
+                                        match status {
+                                            ResultStatus::Success if message.contains("Git LFS") => {
+                                                ui.label(egui::RichText::new("⚠ Git LFS").color(egui::Color32::YELLOW));
+                                            }
+                                            ResultStatus::Success => {
+                                                ui.label(egui::RichText::new("✓ Success").color(egui::Color32::GREEN));
+                                            }
+                                            ResultStatus::Conflict => {
+                                                ui.label(egui::RichText::new("⚠ Conflicts").color(egui::Color32::YELLOW));
+                                            }
+                                            ResultStatus::Skipped => {
+                                                ui.label(egui::RichText::new("⏭ Skipped").color(egui::Color32::GRAY));
+                                            }
+                                            ResultStatus::Failed => {
+                                                ui.label(egui::RichText::new("✗ Failed").color(egui::Color32::RED));
+                                            }
                                         }
                                     });
                                 });
                                 
-                                if status != "Success" && status != "No conflicts" {
-                                    ui.label(egui::RichText::new(status).weak().small());
+                                // Successes only need their message when it carries a note, like the LFS warning or retries
+                                if *status != ResultStatus::Success || message.starts_with("Success (") {
+                                    ui.label(egui::RichText::new(message).weak().small());
                                 }
                             });
                         }
@@ -1094,6 +2303,16 @@ impl eframe::App for MultiRepoPusherApp {
                 });
             }
             
+            // Configuration problems that would make operations fail
+            if !self.config_problems.is_empty() {
+                ui.add_space(10.0);
+                ui.group(|ui| {
+                    for problem in &self.config_problems {
+                        ui.label(egui::RichText::new(format!("⚠ {}", problem)).color(egui::Color32::YELLOW));
+                    }
+                });
+            }
+            
             // Status message with loading indicator for long operations
             if !self.status_message.is_empty() && self.status_message != "Ready" {
                 ui.add_space(10.0);
@@ -1111,10 +2330,51 @@ impl eframe::App for MultiRepoPusherApp {
             }
         });
     }
+    
+    // Called on close and now and then while running, the settings file is what main reads on start
+    fn save(&mut self, _storage: &mut dyn eframe::Storage) {
+        if let Err(e) = self.current_settings().save(SETTINGS_FILE) {
+            log::warn!("Failed to save settings: {}", e);
+        }
+    }
 }
 
 impl MultiRepoPusherApp {
     fn render_commit_tab(&mut self, ui: &mut egui::Ui) {
+        // Working directory status, so it's clear what "Push to All" will commit
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.heading("📋 Working Directory Changes");
+                if ui.button("🔄 Refresh").clicked() {
+                    self.refresh_working_changes();
+                    self.refresh_current_branch();
+                }
+            });
+            ui.add_space(5.0);
+            
+            if self.working_changes.is_empty() {
+                ui.label(egui::RichText::new("Working tree clean").weak().size(12.0));
+            } else {
+                egui::ScrollArea::vertical().id_source("working_changes").max_height(150.0).show(ui, |ui| {
+                    for change in &self.working_changes {
+                        ui.horizontal(|ui| {
+                            // Same colors as the file changes in the commit history viewer
+                            let (status_char, color) = match change.status {
+                                WorkingChangeStatus::Staged => ("S", egui::Color32::GREEN),
+                                WorkingChangeStatus::Modified => ("M", egui::Color32::BLUE),
+                                WorkingChangeStatus::Deleted => ("D", egui::Color32::RED),
+                                WorkingChangeStatus::Untracked => ("?", egui::Color32::YELLOW),
+                            };
+                            ui.label(egui::RichText::new(status_char).color(color).strong());
+                            ui.label(egui::RichText::new(&change.path).monospace());
+                        });
+                    }
+                });
+            }
+        });
+        
+        ui.add_space(10.0);
+        
         // Commit section with premium styling
         ui.group(|ui| {
             ui.heading("📝 Commit Settings");
@@ -1127,12 +2387,70 @@ impl MultiRepoPusherApp {
                     ui.add_sized([ui.available_width() * 0.7, 25.0], egui::TextEdit::singleline(&mut self.commit_message).hint_text("Enter commit message"));
                 });
                 
+                let mut enforce_conventional = self.config.lock().unwrap().enforce_conventional_commits;
+                if ui.checkbox(&mut enforce_conventional, "Enforce conventional commits")
+                    .on_hover_text("Require messages like \"feat(scope): description\"")
+                    .changed()
+                {
+                    self.config.lock().unwrap().enforce_conventional_commits = enforce_conventional;
+                    if let Err(e) = self.save_current_configuration() {
+                        self.status_message = e;
+                    }
+                }
+                
+                if enforce_conventional && !is_conventional_commit(&self.commit_message) {
+                    ui.label(
+                        egui::RichText::new("⚠ Use <type>(<scope>): <description> with type feat, fix, docs, chore or refactor")
+                            .color(self.theme.accent(egui::Color32::from_rgb(255, 180, 80)))
+                            .size(12.0)
+                    );
+                }
+                
                 ui.add_space(10.0);
                 
                 ui.horizontal(|ui| {
                     ui.label(egui::RichText::new("Branch name:").strong().size(14.0));
                     ui.add_sized([ui.available_width() * 0.7, 25.0], egui::TextEdit::singleline(&mut self.branch_name).hint_text("main"));
                 });
+                
+                if self.detached_head {
+                    ui.label(
+                        egui::RichText::new("⚠ (detached HEAD) Check out a branch to commit and push.")
+                            .color(egui::Color32::YELLOW)
+                            .size(12.0)
+                    );
+                }
+                
+                ui.add_space(10.0);
+                
+                if ui.checkbox(&mut self.stage_all_changes, "Stage all changes").changed() && !self.stage_all_changes {
+                    self.refresh_working_changes();
+                }
+                
+                ui.checkbox(&mut self.amend_last_commit, "Amend last commit")
+                    .on_hover_text("Replace the last commit instead of creating a new one. Remotes that already have it will reject the push.");
+                ui.checkbox(&mut self.allow_empty_commit, "Allow empty commit")
+                    .on_hover_text("Commit even when nothing changed. Otherwise only the existing commits are pushed.");
+                
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.push_include_tags, "Include tags")
+                        .on_hover_text("Also push every local tag");
+                    ui.checkbox(&mut self.push_all_branches, "Push all branches")
+                        .on_hover_text("Push every local branch, not only the one above");
+                });
+                
+                // File picker for staging a subset of the changes
+                if !self.stage_all_changes {
+                    if self.working_change_selection.is_empty() {
+                        ui.label(egui::RichText::new("No changed files").weak().size(12.0));
+                    } else {
+                        egui::ScrollArea::vertical().id_source("staging_list").max_height(150.0).show(ui, |ui| {
+                            for (path, selected) in &mut self.working_change_selection {
+                                ui.checkbox(selected, egui::RichText::new(path.as_str()).monospace());
+                            }
+                        });
+                    }
+                }
             });
             
             ui.add_space(15.0);
@@ -1153,8 +2471,30 @@ impl MultiRepoPusherApp {
                     .rounding(egui::Rounding::same(8.0))
                     .min_size(egui::Vec2::new(250.0, 40.0));
                     
-                    if ui.add(button).clicked() {
-                        self.push_to_all_repositories();
+                    let button = ui.add_enabled(!self.detached_head, button)
+                        .on_disabled_hover_text("HEAD is detached, check out a branch first");
+                    if button.clicked() {
+                        self.confirm_push_to_all();
+                    }
+                    
+                    // One-off subset ticked in the Repositories tab
+                    if !self.selected_repositories.is_empty() {
+                        ui.add_space(8.0);
+                        let selected_button = egui::Button::new(
+                            egui::RichText::new(format!("🎯 Push to Selected ({})", self.selected_repositories.len()))
+                                .size(14.0)
+                                .color(egui::Color32::WHITE)
+                        )
+                        .fill(egui::Color32::from_rgb(60, 110, 150))
+                        .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(100, 180, 255)))
+                        .rounding(egui::Rounding::same(8.0))
+                        .min_size(egui::Vec2::new(250.0, 32.0));
+                        
+                        let selected_button = ui.add_enabled(!self.detached_head, selected_button)
+                            .on_disabled_hover_text("HEAD is detached, check out a branch first");
+                        if selected_button.clicked() {
+                            self.push_to_selected_repositories();
+                        }
                     }
                 }
             });
@@ -1203,6 +2543,30 @@ impl MultiRepoPusherApp {
                     if ui.add(load_button).clicked() {
                         self.status_message = "Configuration loaded".to_string();
                     }
+                    
+                    let restore_button = egui::Button::new(
+                        egui::RichText::new("📤 Restore")
+                            .size(14.0)
+                    )
+                    .fill(egui::Color32::from_rgb(80, 80, 120))
+                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(150, 150, 200)))
+                    .rounding(egui::Rounding::same(6.0));
+                    
+                    if ui.add_enabled(!self.is_operation_running, restore_button).on_hover_text("Replace the configuration with a backup").clicked() {
+                        self.restore_configuration();
+                    }
+                    
+                    let backup_button = egui::Button::new(
+                        egui::RichText::new("📥 Backup")
+                            .size(14.0)
+                    )
+                    .fill(egui::Color32::from_rgb(80, 80, 120))
+                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(150, 150, 200)))
+                    .rounding(egui::Rounding::same(6.0));
+                    
+                    if ui.add(backup_button).on_hover_text("Save a timestamped copy of the configuration").clicked() {
+                        self.backup_configuration();
+                    }
                 });
             });
             
@@ -1216,6 +2580,44 @@ impl MultiRepoPusherApp {
         
         ui.add_space(15.0);
         
+        // GitHub import section
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.heading("🐙 Import from GitHub");
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if self.github_import_receiver.is_some() {
+                        ui.add(egui::Spinner::new().size(16.0));
+                    }
+                    
+                    let import_button = egui::Button::new(
+                        egui::RichText::new("📥 Import from GitHub")
+                            .size(14.0)
+                    )
+                    .fill(egui::Color32::from_rgb(60, 120, 160))
+                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(100, 180, 220)))
+                    .rounding(egui::Rounding::same(6.0));
+                    
+                    if ui.add_enabled(self.github_import_receiver.is_none(), import_button).clicked() {
+                        self.start_github_import();
+                    }
+                });
+            });
+            
+            ui.add_space(10.0);
+            
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("User or Organization:").strong().size(14.0));
+                ui.add_sized([ui.available_width() * 0.7, 25.0], egui::TextEdit::singleline(&mut self.github_import_owner).hint_text("e.g., octocat"));
+            });
+            
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Token:").strong().size(14.0));
+                ui.add_sized([ui.available_width() * 0.7, 25.0], egui::TextEdit::singleline(&mut self.github_import_token).password(true).hint_text("Used to list and push the repositories"));
+            });
+        });
+        
+        ui.add_space(15.0);
+        
         // Repository cloning section
         ui.group(|ui| {
             ui.horizontal(|ui| {
@@ -1236,6 +2638,18 @@ impl MultiRepoPusherApp {
                     if ui.add(clone_button).clicked() && !self.is_operation_running {
                         self.clone_all_repositories();
                     }
+                    
+                    let health_button = egui::Button::new(
+                        egui::RichText::new("🩺 Health Check")
+                            .size(14.0)
+                    )
+                    .fill(egui::Color32::from_rgb(60, 140, 100))
+                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(100, 200, 140)))
+                    .rounding(egui::Rounding::same(6.0));
+                    
+                    if ui.add(health_button).on_hover_text("Check that every remote answers, without transferring anything").clicked() && !self.is_operation_running {
+                        self.run_health_check();
+                    }
                 });
             });
             
@@ -1247,6 +2661,7 @@ impl MultiRepoPusherApp {
             });
             
             ui.label(egui::RichText::new("Specify the directory where repositories will be cloned.").weak().size(12.0));
+            ui.checkbox(&mut self.clone_skip_existing, "Skip repositories that are already cloned");
         });
         
         ui.add_space(15.0);
@@ -1299,6 +2714,18 @@ impl MultiRepoPusherApp {
                         ui.label(egui::RichText::new(&group.description).weak().size(13.0));
                         ui.label(egui::RichText::new(format!("{} repositories in this group", group.repository_names.len())).weak().size(12.0));
                         
+                        ui.add_space(5.0);
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new("Rename to:").strong().size(14.0));
+                            ui.add_sized([200.0, 25.0], egui::TextEdit::singleline(&mut self.rename_group_name).hint_text(group.name.as_str()));
+                            if ui.button("✏ Rename").clicked() {
+                                self.rename_selected_group();
+                            }
+                            if ui.button("🗑 Delete Group").clicked() {
+                                self.pending_confirmation = Some(PendingAction::RemoveGroup { name: group.name.clone() });
+                            }
+                        });
+                        
                         // Add batch operation buttons for the selected group
                         ui.add_space(10.0);
                         ui.horizontal(|ui| {
@@ -1407,6 +2834,8 @@ impl MultiRepoPusherApp {
                 egui::ComboBox::from_id_source("filter_auth")
                     .selected_text(match &self.filter_by_auth_type {
                         Some(AuthType::SSH) => "SSH",
+                        Some(AuthType::SshAgent) => "SSH Agent",
+                        Some(AuthType::CredentialHelper) => "Credential Helper",
                         Some(AuthType::Token) => "Token",
                         Some(AuthType::Default) => "Default",
                         None => "All Auth Types",
@@ -1414,10 +2843,28 @@ impl MultiRepoPusherApp {
                     .show_ui(ui, |ui| {
                         ui.selectable_value(&mut self.filter_by_auth_type, None, "All Auth Types");
                         ui.selectable_value(&mut self.filter_by_auth_type, Some(AuthType::SSH), "SSH");
+                        ui.selectable_value(&mut self.filter_by_auth_type, Some(AuthType::SshAgent), "SSH Agent");
+                        ui.selectable_value(&mut self.filter_by_auth_type, Some(AuthType::CredentialHelper), "Credential Helper");
                         ui.selectable_value(&mut self.filter_by_auth_type, Some(AuthType::Token), "Token");
                         ui.selectable_value(&mut self.filter_by_auth_type, Some(AuthType::Default), "Default");
                     });
                 
+                ui.add_space(10.0);
+                
+                // Sort
+                ui.label(egui::RichText::new("Sort:").strong().size(14.0));
+                egui::ComboBox::from_id_source("repository_sort")
+                    .selected_text(match self.repository_sort {
+                        RepositorySort::Configured => "As Added",
+                        RepositorySort::Name => "By Name",
+                        RepositorySort::Group => "By Group",
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.repository_sort, RepositorySort::Configured, "As Added");
+                        ui.selectable_value(&mut self.repository_sort, RepositorySort::Name, "By Name");
+                        ui.selectable_value(&mut self.repository_sort, RepositorySort::Group, "By Group");
+                    });
+                
                 // Clear filters button
                 let clear_button = egui::Button::new(
                     egui::RichText::new("❌ Clear")
@@ -1435,62 +2882,126 @@ impl MultiRepoPusherApp {
                 }
             });
             
+            // Bulk toggle for the repositories ticked below
+            if !self.selected_repositories.is_empty() {
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    let indices: Vec<usize> = self.selected_repositories.iter().copied().collect();
+                    ui.label(format!("{} selected:", indices.len()));
+                    if ui.button("✅ Enable").clicked() {
+                        self.set_repositories_enabled(&indices, true);
+                    }
+                    if ui.button("⏸ Disable").on_hover_text("Skip in batch operations").clicked() {
+                        self.set_repositories_enabled(&indices, false);
+                    }
+                });
+            }
+            
             ui.add_space(10.0);
             
             // Repository list with premium styling and increased height
             let config = self.config.clone();
-            let mut repos = config.lock().unwrap().repositories.clone();
-            
-            // Apply search filter
-            if !self.search_text.is_empty() {
-                let search_term = self.search_text.to_lowercase();
-                repos.retain(|repo| {
-                    repo.name.to_lowercase().contains(&search_term) || 
-                    repo.url.to_lowercase().contains(&search_term)
-                });
-            }
+            let repos = config.lock().unwrap().repositories.clone();
             
-            // Apply group filter
-            if !self.filter_by_group.is_empty() {
-                repos.retain(|repo| repo.group == self.filter_by_group);
-            }
+            // Filter and sort a view of the list, keeping each repository's index in the config
+            let search_term = self.search_text.to_lowercase();
+            let mut visible: Vec<(usize, &RepositoryInfo)> = repos
+                .iter()
+                .enumerate()
+                .filter(|(_, repo)| {
+                    search_term.is_empty()
+                        || repo.name.to_lowercase().contains(&search_term)
+                        || repo.url.to_lowercase().contains(&search_term)
+                })
+                .filter(|(_, repo)| self.filter_by_group.is_empty() || repo.group == self.filter_by_group)
+                .filter(|(_, repo)| self.filter_by_auth_type.as_ref().is_none_or(|auth_type| &repo.auth_type == auth_type))
+                .collect();
             
-            // Apply auth type filter
-            if let Some(auth_type) = &self.filter_by_auth_type {
-                repos.retain(|repo| &repo.auth_type == auth_type);
+            match self.repository_sort {
+                RepositorySort::Configured => {},
+                RepositorySort::Name => visible.sort_by_key(|(_, repo)| repo.name.to_lowercase()),
+                // Ungrouped repositories go last
+                RepositorySort::Group => visible.sort_by_key(|(_, repo)| {
+                    (repo.group.is_empty(), repo.group.to_lowercase(), repo.name.to_lowercase())
+                }),
             }
             
-            if repos.is_empty() {
+            if visible.is_empty() {
+                let message = if repos.is_empty() {
+                    "No repositories configured"
+                } else {
+                    "No repositories match the filters"
+                };
                 ui.vertical_centered(|ui| {
                     ui.add_space(30.0);
-                    ui.label(egui::RichText::new("No repositories configured").weak().size(14.0));
+                    ui.label(egui::RichText::new(message).weak().size(14.0));
                     ui.add_space(30.0);
                 });
             } else {
                 // Increased height for better browsing experience
                 egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
-                    for (i, repo) in repos.iter().enumerate() {
+                    for (i, repo) in visible {
                         ui.group(|ui| {
                             ui.horizontal(|ui| {
+                                // Reordering only makes sense in the configured order
+                                if self.repository_sort == RepositorySort::Configured {
+                                    ui.vertical(|ui| {
+                                        if ui.add_enabled(i > 0, egui::Button::new("⬆").small()).on_hover_text("Move up").clicked() {
+                                            self.move_repository(i, i - 1);
+                                        }
+                                        if ui.add_enabled(i + 1 < repos.len(), egui::Button::new("⬇").small()).on_hover_text("Move down").clicked() {
+                                            self.move_repository(i, i + 1);
+                                        }
+                                    });
+                                }
+                                
+                                let mut selected = self.selected_repositories.contains(&i);
+                                if ui.checkbox(&mut selected, "").on_hover_text("Include in \"Push to Selected\"").changed() {
+                                    if selected {
+                                        self.selected_repositories.insert(i);
+                                    } else {
+                                        self.selected_repositories.remove(&i);
+                                    }
+                                }
+                                
+                                let mut enabled = repo.enabled;
+                                if ui.checkbox(&mut enabled, "").on_hover_text("Enabled for batch operations").changed() {
+                                    self.set_repositories_enabled(&[i], enabled);
+                                }
+                                
                                 ui.vertical(|ui| {
-                                    ui.label(egui::RichText::new(&repo.name).size(16.0).strong().color(egui::Color32::from_rgb(180, 200, 255)));
+                                    let name_color = if repo.enabled {
+                                        self.theme.accent(egui::Color32::from_rgb(180, 200, 255))
+                                    } else {
+                                        egui::Color32::GRAY
+                                    };
+                                    ui.label(egui::RichText::new(&repo.name).size(16.0).strong().color(name_color));
+                                    if !repo.enabled {
+                                        ui.label(egui::RichText::new("⏸ Disabled").weak().size(11.0));
+                                    }
                                     // Show full URL as per user preference
                                     ui.label(egui::RichText::new(&repo.url).weak().size(12.0));
                                     match &repo.auth_type {
                                         AuthType::SSH => {
-                                            ui.label(egui::RichText::new("🔐 Auth: SSH").weak().size(11.0).color(egui::Color32::from_rgb(150, 200, 150)));
+                                            ui.label(egui::RichText::new("🔐 Auth: SSH").weak().size(11.0).color(self.theme.accent(egui::Color32::from_rgb(150, 200, 150))));
+                                        },
+                                        AuthType::SshAgent => {
+                                            ui.label(egui::RichText::new("🔐 Auth: SSH Agent").weak().size(11.0).color(self.theme.accent(egui::Color32::from_rgb(150, 200, 150))));
+                                        },
+                                        AuthType::CredentialHelper => {
+                                            ui.label(egui::RichText::new("🔐 Auth: Credential Helper").weak().size(11.0).color(self.theme.accent(egui::Color32::from_rgb(150, 200, 150))));
                                         },
                                         AuthType::Token => {
-                                            ui.label(egui::RichText::new("🔐 Auth: Token").weak().size(11.0).color(egui::Color32::from_rgb(150, 200, 150)));
+                                            ui.label(egui::RichText::new("🔐 Auth: Token").weak().size(11.0).color(self.theme.accent(egui::Color32::from_rgb(150, 200, 150))));
                                         },
                                         AuthType::Default => {
-                                            ui.label(egui::RichText::new("🔐 Auth: Default").weak().size(11.0).color(egui::Color32::from_rgb(150, 200, 150)));
+                                            ui.label(egui::RichText::new("🔐 Auth: Default").weak().size(11.0).color(self.theme.accent(egui::Color32::from_rgb(150, 200, 150))));
                                         }
                                     }
                                     
                                     // Show group if repository belongs to one
                                     if !repo.group.is_empty() {
-                                        ui.label(egui::RichText::new(format!("📁 Group: {}", repo.group)).weak().size(11.0).color(egui::Color32::from_rgb(200, 150, 200)));
+                                        ui.label(egui::RichText::new(format!("📁 Group: {}", repo.group)).weak().size(11.0).color(self.theme.accent(egui::Color32::from_rgb(200, 150, 200))));
                                     }
                                 });
                                 
@@ -1527,6 +3038,24 @@ impl MultiRepoPusherApp {
                                         }
                                     }
                                     
+                                    let convert_label = if repo.url.starts_with("git@") {
+                                        "🔄 Convert to HTTPS"
+                                    } else {
+                                        "🔄 Convert to SSH"
+                                    };
+                                    let convert_button = egui::Button::new(
+                                        egui::RichText::new(convert_label)
+                                            .size(11.0)
+                                    )
+                                    .fill(egui::Color32::from_rgb(80, 100, 140))
+                                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(150, 170, 220)))
+                                    .rounding(egui::Rounding::same(4.0))
+                                    .min_size(egui::Vec2::new(110.0, 25.0));
+                                    
+                                    if ui.add(convert_button).clicked() {
+                                        self.convert_repository_url(&repo.name, &repo.url);
+                                    }
+                                    
                                     let remove_button = egui::Button::new(
                                         egui::RichText::new("🗑 Remove")
                                             .size(12.0)
@@ -1537,8 +3066,7 @@ impl MultiRepoPusherApp {
                                     .min_size(egui::Vec2::new(70.0, 25.0));
                                     
                                     if ui.add(remove_button).clicked() {
-                                        let mut config = self.config.lock().unwrap();
-                                        config.remove_repository(i);
+                                        self.pending_confirmation = Some(PendingAction::RemoveRepository { index: i, name: repo.name.clone() });
                                     }
                                     
                                     let validate_button = egui::Button::new(
@@ -1551,24 +3079,25 @@ impl MultiRepoPusherApp {
                                     .min_size(egui::Vec2::new(70.0, 25.0));
                                     
                                     if ui.add(validate_button).clicked() {
-                                        match verify_authentication(repo) {
-                                            Ok(true) => {
-                                                self.status_message = format!("Repository {} authentication verified", repo.name);
-                                            },
-                                            Ok(false) => {
-                                                self.status_message = format!("Repository {} authentication failed. Please check your credentials.", repo.name);
-                                            },
-                                            Err(e) => {
-                                                let error_msg = e.to_string();
-                                                if error_msg.contains("authentication") || error_msg.contains("Authentication") {
-                                                    self.status_message = format!("Authentication failed for repository {}. Please check your credentials.", repo.name);
-                                                } else if error_msg.contains("network") || error_msg.contains("Network") {
-                                                    self.status_message = format!("Network error for repository {}. Please check your connection.", repo.name);
-                                                } else {
-                                                    self.status_message = format!("Validation error for {}: {}", repo.name, error_msg);
-                                                }
-                                            }
-                                        }
+                                        self.validate_repository(repo);
+                                    }
+                                    
+                                    let optimize_button = egui::Button::new(
+                                        egui::RichText::new("🧽 Optimize repo")
+                                            .size(12.0)
+                                    )
+                                    .fill(egui::Color32::from_rgb(90, 90, 120))
+                                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(160, 160, 210)))
+                                    .rounding(egui::Rounding::same(4.0))
+                                    .min_size(egui::Vec2::new(100.0, 25.0));
+                                    
+                                    let can_optimize = !repo.local_path.is_empty() && !self.is_operation_running;
+                                    if ui.add_enabled(can_optimize, optimize_button)
+                                        .on_hover_text("Pack loose objects with git gc")
+                                        .on_disabled_hover_text("Only repositories cloned locally can be optimized")
+                                        .clicked()
+                                    {
+                                        self.optimize_repository(repo);
                                     }
                                 });
                             });
@@ -1580,96 +3109,6 @@ impl MultiRepoPusherApp {
             
             ui.add_space(15.0);
             
-            // Repository diff comparison section
-            ui.separator();
-            ui.heading("🔍 Repository Diff Comparison");
-            
-            ui.add_space(10.0);
-            
-            let config = self.config.clone();
-            let repos = config.lock().unwrap().repositories.clone();
-            
-            if !repos.is_empty() {
-                ui.vertical(|ui| {
-                    ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new("Repository:").strong().size(14.0));
-                        egui::ComboBox::from_id_source("diff_repo_selection")
-                            .selected_text(if self.selected_repo_for_diff < repos.len() { 
-                                &repos[self.selected_repo_for_diff].name 
-                            } else { 
-                                "Select a repository" 
-                            })
-                            .show_ui(ui, |ui| {
-                                for (i, repo) in repos.iter().enumerate() {
-                                    ui.selectable_value(&mut self.selected_repo_for_diff, i, &repo.name);
-                                }
-                            });
-                    });
-                    
-                    ui.add_space(8.0);
-                    
-                    ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new("Branch 1:").strong().size(14.0));
-                        ui.add_sized([ui.available_width() * 0.4, 25.0], egui::TextEdit::singleline(&mut self.diff_branch1).hint_text("main"));
-                        
-                        ui.label(egui::RichText::new("Branch 2:").strong().size(14.0));
-                        ui.add_sized([ui.available_width() * 0.4, 25.0], egui::TextEdit::singleline(&mut self.diff_branch2).hint_text("develop"));
-                    });
-                    
-                    ui.add_space(10.0);
-                    
-                    ui.horizontal(|ui| {
-                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            let diff_button = egui::Button::new(
-                                egui::RichText::new("🔍 Generate Diff")
-                                    .size(14.0)
-                            )
-                            .fill(egui::Color32::from_rgb(100, 150, 100))
-                            .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(180, 230, 180)))
-                            .rounding(egui::Rounding::same(6.0))
-                            .min_size(egui::Vec2::new(120.0, 30.0));
-                            
-                            if ui.add(diff_button).clicked() {
-                                match self.generate_repository_diff() {
-                                    Ok(()) => {
-                                        self.status_message = "Diff generated successfully!".to_string();
-                                    },
-                                    Err(e) => {
-                                        self.status_message = format!("Failed to generate diff: {}", e);
-                                    }
-                                }
-                            }
-                            
-                            ui.add_space(10.0);
-                            
-                            let wd_diff_button = egui::Button::new(
-                                egui::RichText::new("🔍 Working Dir Diff")
-                                    .size(14.0)
-                            )
-                            .fill(egui::Color32::from_rgb(100, 150, 100))
-                            .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(180, 230, 180)))
-                            .rounding(egui::Rounding::same(6.0))
-                            .min_size(egui::Vec2::new(120.0, 30.0));
-                            
-                            if ui.add(wd_diff_button).clicked() {
-                                match self.generate_working_directory_diff() {
-                                    Ok(()) => {
-                                        self.status_message = "Working directory diff generated successfully!".to_string();
-                                    },
-                                    Err(e) => {
-                                        self.status_message = format!("Failed to generate working directory diff: {}", e);
-                                    }
-                                }
-                            }
-                        });
-                    });
-                });
-            } else {
-                ui.label(egui::RichText::new("No repositories available for diff comparison.").weak().size(13.0));
-            }
-            
-            ui.add_space(15.0);
-            
             // Add new repository form with premium styling
             ui.separator();
             ui.heading("➕ Add New Repository");
@@ -1699,6 +3138,8 @@ impl MultiRepoPusherApp {
                         .show_ui(ui, |ui| {
                             ui.selectable_value(&mut self.new_repo_auth_type, AuthType::Default, "Default");
                             ui.selectable_value(&mut self.new_repo_auth_type, AuthType::SSH, "SSH Key");
+                            ui.selectable_value(&mut self.new_repo_auth_type, AuthType::SshAgent, "SSH Agent");
+                            ui.selectable_value(&mut self.new_repo_auth_type, AuthType::CredentialHelper, "Git Credential Helper");
                             ui.selectable_value(&mut self.new_repo_auth_type, AuthType::Token, "Token");
                         });
                     
@@ -1731,6 +3172,12 @@ impl MultiRepoPusherApp {
                                 ui.label(egui::RichText::new("SSH Key Path:").strong().size(14.0));
                                 ui.add_sized([ui.available_width() * 0.7, 25.0], egui::TextEdit::singleline(&mut self.new_repo_ssh_key).hint_text("~/.ssh/id_rsa"));
                             });
+                            ui.add_space(8.0);
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new("Passphrase:").strong().size(14.0));
+                                ui.add_sized([ui.available_width() * 0.7, 25.0], egui::TextEdit::singleline(&mut self.new_repo_ssh_passphrase).password(true).hint_text("Leave empty for unencrypted keys"))
+                                    .on_hover_text("Kept until the app closes, passphrases aren't saved to repos.json");
+                            });
                         },
                         _ => {}
                     }
@@ -1836,6 +3283,8 @@ impl MultiRepoPusherApp {
                         .show_ui(ui, |ui| {
                             ui.selectable_value(&mut self.account_auth_type, AuthType::Default, "Default");
                             ui.selectable_value(&mut self.account_auth_type, AuthType::SSH, "SSH Key");
+                            ui.selectable_value(&mut self.account_auth_type, AuthType::SshAgent, "SSH Agent");
+                            ui.selectable_value(&mut self.account_auth_type, AuthType::CredentialHelper, "Git Credential Helper");
                             ui.selectable_value(&mut self.account_auth_type, AuthType::Token, "Personal Access Token");
                         });
                 });
@@ -1854,9 +3303,10 @@ impl MultiRepoPusherApp {
                         ui.add_space(10.0);
                         ui.separator();
                         ui.label(egui::RichText::new("Or use OAuth:").strong().size(12.0));
+                        Self::render_oauth_provider_picker(ui, "account_oauth_provider", &mut self.oauth_provider);
                         ui.horizontal(|ui| {
                             ui.label(egui::RichText::new("OAuth Code:").strong().size(14.0));
-                            ui.add_sized([ui.available_width() * 0.7, 28.0], egui::TextEdit::singleline(&mut self.oauth_code).hint_text("Enter code from GitHub"));
+                            ui.add_sized([ui.available_width() * 0.7, 28.0], egui::TextEdit::singleline(&mut self.oauth_code).hint_text(format!("Enter code from {}", self.oauth_provider.name())));
                         });
                                                 
                         let exchange_button = egui::Button::new(
@@ -1871,13 +3321,18 @@ impl MultiRepoPusherApp {
                             self.exchange_oauth_code();
                         }
                                                 
-                        ui.label(egui::RichText::new("After entering the code from GitHub, click the button above to get your access token.").weak().size(11.0));
+                        ui.label(egui::RichText::new(format!("After entering the code from {}, click the button above to get your access token.", self.oauth_provider.name())).weak().size(11.0));
                     },
                     AuthType::SSH => {
                         ui.horizontal(|ui| {
                             ui.label(egui::RichText::new("SSH Key Path:").strong().size(14.0));
                             ui.add_sized([ui.available_width() * 0.7, 28.0], egui::TextEdit::singleline(&mut self.account_ssh_key_path).hint_text("~/.ssh/id_rsa"));
                         });
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new("Key Passphrase:").strong().size(14.0));
+                            ui.add_sized([ui.available_width() * 0.7, 28.0], egui::TextEdit::singleline(&mut self.account_ssh_passphrase).password(true).hint_text("Leave empty for unencrypted keys"))
+                                .on_hover_text("Kept until the app closes, passphrases aren't saved to repos.json");
+                        });
                         ui.label(egui::RichText::new("Ensure your SSH key is added to ssh-agent").weak().size(11.0));
                     },
                     _ => {
@@ -1885,6 +3340,14 @@ impl MultiRepoPusherApp {
                     }
                 }
                 
+                ui.add_space(15.0);
+                
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("GPG Signing Key:").strong().size(14.0));
+                    ui.add_sized([ui.available_width() * 0.7, 28.0], egui::TextEdit::singleline(&mut self.account_signing_key).hint_text("Key id, leave empty for unsigned commits"));
+                });
+                ui.label(egui::RichText::new("Commits will be signed with `gpg` when a key id is set").weak().size(11.0));
+                
                 ui.add_space(20.0);
                 
                 ui.horizontal(|ui| {
@@ -1905,6 +3368,8 @@ impl MultiRepoPusherApp {
                             self.account_email.clear();
                             self.account_token.clear();
                             self.account_ssh_key_path.clear();
+                            self.account_ssh_passphrase.clear();
+                            self.account_signing_key.clear();
                         }
                         
                         let add_button = egui::Button::new(
@@ -1912,65 +3377,69 @@ impl MultiRepoPusherApp {
                                 .size(14.0)
                                 .color(egui::Color32::WHITE)
                         )
-                        .fill(egui::Color32::from_rgb(70, 150, 70))
-                        .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(120, 220, 120)))
-                        .rounding(egui::Rounding::same(6.0))
-                        .min_size(egui::Vec2::new(120.0, 30.0));
-                        
-                        if ui.add(add_button).clicked() {
-                            self.add_new_account();
-                        }
-                    });
-                });
-            });
-        });
-    }
-    
-    // Render diff viewer modal
-    fn render_diff_viewer(&mut self, ctx: &egui::Context) {
-        if self.show_diff_viewer {
-            let mut open = self.show_diff_viewer;
-            egui::Window::new("🔍 Repository Diff Viewer")
-                .open(&mut open)
-                .resizable(true)
-                .default_width(600.0)
-                .default_height(400.0)
-                .show(ctx, |ui| {
-                    ui.vertical(|ui| {
-                        ui.heading("Repository Diff");
-                        ui.separator();
-                        
-                        // Display diff content in a scrollable area
-                        egui::ScrollArea::vertical()
-                            .auto_shrink([false; 2])
-                            .max_height(300.0)
-                            .show(ui, |ui| {
-                                ui.monospace(&self.diff_content);
-                            });
-                        
-                        ui.add_space(10.0);
+                        .fill(egui::Color32::from_rgb(70, 150, 70))
+                        .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(120, 220, 120)))
+                        .rounding(egui::Rounding::same(6.0))
+                        .min_size(egui::Vec2::new(120.0, 30.0));
                         
-                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            let close_button = egui::Button::new(
-                                egui::RichText::new("Close")
-                                    .size(14.0)
-                            )
-                            .fill(egui::Color32::from_rgb(120, 120, 120))
-                            .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(200, 200, 200)))
-                            .rounding(egui::Rounding::same(6.0))
-                            .min_size(egui::Vec2::new(80.0, 30.0));
-                            
-                            if ui.add(close_button).clicked() {
-                                self.show_diff_viewer = false;
-                                self.diff_content.clear();
-                            }
-                        });
+                        if ui.add(add_button).clicked() {
+                            self.add_new_account();
+                        }
                     });
                 });
-            self.show_diff_viewer = open;
+            });
+        });
+    }
+    
+    // Throw away the conflicted merge in the local repository
+    fn abort_merge(&mut self) {
+        let result = git2::Repository::open(".")
+            .map_err(anyhow::Error::from)
+            .and_then(|repo| abort_merge(&repo));
+        
+        match result {
+            Ok(()) => {
+                self.status_message = "Merge aborted, the working tree is back at HEAD".to_string();
+                self.has_merge_conflicts = false;
+                self.conflicted_files.clear();
+                self.show_conflicts_modal = false;
+                self.refresh_working_changes();
+            }
+            Err(e) => {
+                self.status_message = format!("Failed to abort merge: {}", e);
+            }
         }
     }
     
+    // List the files a pull left conflicted
+    fn render_conflicts_modal(&mut self, ctx: &egui::Context) {
+        if !self.show_conflicts_modal {
+            return;
+        }
+        
+        let mut open = self.show_conflicts_modal;
+        egui::Window::new("⚠ Merge Conflicts")
+            .open(&mut open)
+            .resizable(true)
+            .default_width(400.0)
+            .show(ctx, |ui| {
+                ui.label(egui::RichText::new("Resolve these files, then commit the merge:").strong().size(14.0));
+                ui.add_space(8.0);
+                
+                egui::ScrollArea::vertical().max_height(250.0).show(ui, |ui| {
+                    for path in &self.conflicted_files {
+                        ui.label(egui::RichText::new(format!("📄 {}", path)).monospace().color(self.theme.accent(egui::Color32::from_rgb(255, 180, 120))));
+                    }
+                });
+                
+                ui.add_space(8.0);
+                if ui.button("Close").clicked() {
+                    self.show_conflicts_modal = false;
+                }
+            });
+        self.show_conflicts_modal &= open;
+    }
+    
     // New function to handle the group modal rendering
     fn render_group_modal(&mut self, ctx: &egui::Context) {
         if self.show_group_form {
@@ -2055,6 +3524,9 @@ impl MultiRepoPusherApp {
                     ui.add_sized([ui.available_width() * 0.7, 25.0], egui::TextEdit::singleline(&mut self.branch_name).hint_text("main"));
                 });
                 
+                ui.checkbox(&mut self.auto_stash_on_pull, "Stash changes before pull")
+                    .on_hover_text("Put local changes aside while pulling and restore them afterwards");
+                
                 ui.add_space(10.0);
                 
                 ui.horizontal(|ui| {
@@ -2105,6 +3577,18 @@ impl MultiRepoPusherApp {
                     ui.add_sized([ui.available_width() * 0.7, 25.0], egui::TextEdit::singleline(&mut self.tag_name).hint_text("v1.0.0"));
                 });
                 
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Bump:").weak());
+                    for (label, bump) in [("major", Bump::Major), ("minor", Bump::Minor), ("patch", Bump::Patch)] {
+                        if ui.small_button(format!("Bump {}", label))
+                            .on_hover_text("Fill in the version after the latest vX.Y.Z tag")
+                            .clicked()
+                        {
+                            self.bump_tag(bump);
+                        }
+                    }
+                });
+                
                 ui.add_space(8.0);
                 
                 ui.horizontal(|ui| {
@@ -2138,6 +3622,50 @@ impl MultiRepoPusherApp {
             
             ui.separator();
             
+            // Changelog between two tags
+            ui.vertical(|ui| {
+                ui.heading("📝 Changelog");
+                
+                ui.add_space(10.0);
+                
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("From tag:").strong().size(14.0));
+                    ui.add_sized([140.0, 25.0], egui::TextEdit::singleline(&mut self.changelog_from).hint_text("v1.0.0"));
+                    if ui.small_button("Latest").on_hover_text("Use the latest vX.Y.Z tag").clicked() && let Some(latest) = git2::Repository::open(".").ok().and_then(|repo| latest_semver_tag(&repo)) {
+                        self.changelog_from = latest;
+                    }
+                    ui.label(egui::RichText::new("To:").strong().size(14.0));
+                    ui.add_sized([140.0, 25.0], egui::TextEdit::singleline(&mut self.changelog_to).hint_text("HEAD"));
+                });
+                
+                ui.add_space(8.0);
+                
+                ui.horizontal(|ui| {
+                    if ui.button("📝 Generate Changelog").clicked() {
+                        self.generate_changelog();
+                    }
+                    
+                    if let Some(changelog) = &self.changelog && ui.button("💾 Save to CHANGELOG.md").on_hover_text("Add it to the top of CHANGELOG.md").clicked() {
+                        self.status_message = match save_changelog(std::path::Path::new("CHANGELOG.md"), changelog) {
+                            Ok(()) => "Changelog saved to CHANGELOG.md".to_string(),
+                            Err(e) => format!("Failed to save changelog: {}", e),
+                        };
+                    }
+                });
+                
+                if let Some(changelog) = &self.changelog {
+                    ui.add_space(8.0);
+                    egui::ScrollArea::vertical()
+                        .id_source("changelog_preview")
+                        .max_height(200.0)
+                        .show(ui, |ui| {
+                            ui.label(egui::RichText::new(changelog).monospace());
+                        });
+                }
+            });
+            
+            ui.separator();
+            
             // Merge conflict detection
             ui.vertical(|ui| {
                 ui.heading("🔍 Conflict Detection");
@@ -2162,8 +3690,263 @@ impl MultiRepoPusherApp {
                         if ui.add(conflict_button).clicked() && !self.is_operation_running {
                             self.check_merge_conflicts();
                         }
+                        
+                        if self.has_merge_conflicts {
+                            let abort_button = egui::Button::new(
+                                egui::RichText::new("⛔ Abort Merge")
+                                    .size(14.0)
+                            )
+                            .fill(egui::Color32::from_rgb(160, 70, 70))
+                            .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(230, 150, 150)))
+                            .rounding(egui::Rounding::same(6.0))
+                            .min_size(egui::Vec2::new(150.0, 35.0));
+                            
+                            if ui.add(abort_button).on_hover_text("Discard the merge and reset the working tree to HEAD").clicked() && !self.is_operation_running {
+                                self.pending_confirmation = Some(PendingAction::AbortMerge);
+                            }
+                        }
+                    });
+                });
+            });
+            
+            ui.separator();
+            
+            // Network settings
+            ui.vertical(|ui| {
+                ui.heading("🌐 Network");
+                
+                ui.add_space(10.0);
+                
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Timeout:").strong().size(14.0));
+                    let mut timeout_secs = self.config.lock().unwrap().timeout_secs;
+                    let response = ui.add(egui::DragValue::new(&mut timeout_secs).clamp_range(1..=3600).suffix(" s"))
+                        .on_hover_text("Abort push, pull and fetch transfers that make no progress for this long");
+                    if response.changed() {
+                        self.config.lock().unwrap().timeout_secs = timeout_secs;
+                    }
+                    // Save once the value is settled instead of on every drag step
+                    if (response.drag_stopped() || response.lost_focus()) && let Err(e) = self.save_current_configuration() {
+                        self.status_message = e;
+                    }
+                });
+                
+                ui.add_space(8.0);
+                
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Parallel operations:").strong().size(14.0));
+                    let mut max_parallel = self.config.lock().unwrap().max_parallel_operations;
+                    let response = ui.add(egui::DragValue::new(&mut max_parallel).clamp_range(1..=32))
+                        .on_hover_text("How many repositories are cloned at the same time");
+                    if response.changed() {
+                        self.config.lock().unwrap().max_parallel_operations = max_parallel;
+                    }
+                    if (response.drag_stopped() || response.lost_focus()) && let Err(e) = self.save_current_configuration() {
+                        self.status_message = e;
+                    }
+                });
+                
+                ui.add_space(8.0);
+                
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Proxy URL:").strong().size(14.0));
+                    let response = ui.add_sized(
+                        [ui.available_width() * 0.7, 25.0],
+                        egui::TextEdit::singleline(&mut self.proxy_url_input).hint_text("http://proxy.example.com:8080")
+                    )
+                    .on_hover_text("Leave empty to use HTTPS_PROXY or the git configuration");
+                    if response.lost_focus() {
+                        let proxy_url = self.proxy_url_input.trim();
+                        self.config.lock().unwrap().proxy_url = (!proxy_url.is_empty()).then(|| proxy_url.to_string());
+                        if let Err(e) = self.save_current_configuration() {
+                            self.status_message = e;
+                        }
+                    }
+                });
+            });
+            
+            ui.separator();
+            
+            // Webhooks told about finished pushes
+            ui.vertical(|ui| {
+                ui.heading("🔔 Notifications");
+                
+                ui.add_space(10.0);
+                
+                let mut changed = false;
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Slack webhook:").strong().size(14.0));
+                    changed |= ui.add_sized(
+                        [ui.available_width() * 0.7, 25.0],
+                        egui::TextEdit::singleline(&mut self.slack_webhook_input).hint_text("https://hooks.slack.com/services/...")
+                    ).lost_focus();
+                });
+                
+                ui.add_space(8.0);
+                
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Discord webhook:").strong().size(14.0));
+                    changed |= ui.add_sized(
+                        [ui.available_width() * 0.7, 25.0],
+                        egui::TextEdit::singleline(&mut self.discord_webhook_input).hint_text("https://discord.com/api/webhooks/...")
+                    ).lost_focus();
+                });
+                
+                if changed {
+                    let non_blank = |url: &str| (!url.trim().is_empty()).then(|| url.trim().to_string());
+                    {
+                        let mut config = self.config.lock().unwrap();
+                        config.notifications.slack_webhook_url = non_blank(&self.slack_webhook_input);
+                        config.notifications.discord_webhook_url = non_blank(&self.discord_webhook_input);
+                    }
+                    if let Err(e) = self.save_current_configuration() {
+                        self.status_message = e;
+                    }
+                }
+            });
+            
+            ui.separator();
+            
+            // Submodules that need `git submodule update --init` before operating on the repository
+            ui.vertical(|ui| {
+                ui.horizontal(|ui| {
+                    ui.heading("📦 Submodules");
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button("🔍 Check Submodules").clicked() {
+                            self.check_submodules();
+                        }
+                    });
+                });
+                
+                if let Some(submodules) = &self.submodules {
+                    ui.add_space(8.0);
+                    if submodules.is_empty() {
+                        ui.label(egui::RichText::new("No submodules").weak());
+                    }
+                    for submodule in submodules {
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new(&submodule.path).monospace());
+                            if !submodule.initialized {
+                                ui.label(egui::RichText::new("⚠ not initialized").color(self.theme.accent(egui::Color32::from_rgb(255, 180, 80))))
+                                    .on_hover_text("Run git submodule update --init");
+                            } else if !submodule.up_to_date {
+                                ui.label(egui::RichText::new("⚠ not at the recorded commit").color(self.theme.accent(egui::Color32::from_rgb(255, 180, 80))));
+                            } else {
+                                ui.label(egui::RichText::new("✓ up to date").color(self.theme.accent(egui::Color32::from_rgb(100, 200, 100))));
+                            }
+                        });
+                    }
+                }
+            });
+            
+            ui.separator();
+            
+            // Remotes of the local repository next to the configured URLs
+            ui.vertical(|ui| {
+                ui.horizontal(|ui| {
+                    ui.heading("🔗 Remotes");
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button("🔄 Sync config → repo")
+                            .on_hover_text("Add or update a remote for every configured repository")
+                            .clicked()
+                        {
+                            self.sync_remotes_from_config();
+                        }
+                        if ui.button("🔍 Load Remotes").clicked() {
+                            self.load_repo_remotes();
+                        }
+                    });
+                });
+                
+                let mut apply = None;
+                if let Some(remotes) = &mut self.repo_remotes {
+                    ui.add_space(8.0);
+                    if remotes.is_empty() {
+                        ui.label(egui::RichText::new("No remotes").weak());
+                    }
+                    let configured: HashMap<String, String> = self.config.lock().unwrap().repositories
+                        .iter()
+                        .map(|repo| (repo.name.clone(), repo.url.clone()))
+                        .collect();
+                    egui::Grid::new("repo_remotes").num_columns(3).striped(true).show(ui, |ui| {
+                        ui.label(egui::RichText::new("Remote").strong());
+                        ui.label(egui::RichText::new("URL in repository").strong());
+                        ui.label(egui::RichText::new("URL in config").strong());
+                        ui.end_row();
+                        
+                        for (name, url, edited_url) in remotes.iter_mut() {
+                            ui.label(egui::RichText::new(name.as_str()).monospace());
+                            ui.horizontal(|ui| {
+                                ui.add(egui::TextEdit::singleline(edited_url).desired_width(280.0));
+                                if edited_url.trim() != url.as_str() && ui.small_button("Apply").clicked() {
+                                    apply = Some((name.clone(), edited_url.clone()));
+                                }
+                            });
+                            match configured.get(name.as_str()) {
+                                Some(configured_url) if configured_url == url => {
+                                    ui.label(egui::RichText::new("✓ same").color(self.theme.accent(egui::Color32::from_rgb(100, 200, 100))));
+                                }
+                                Some(configured_url) => {
+                                    ui.label(egui::RichText::new(format!("⚠ {}", configured_url)).color(self.theme.accent(egui::Color32::from_rgb(255, 180, 80))));
+                                }
+                                None => {
+                                    ui.label(egui::RichText::new("not configured").weak());
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+                }
+                if let Some((name, url)) = apply {
+                    self.apply_remote_url(&name, &url);
+                }
+            });
+            
+            ui.separator();
+            
+            // Stale branch detection
+            ui.vertical(|ui| {
+                ui.heading("🧹 Stale Branches");
+                
+                ui.add_space(10.0);
+                
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("No commits in the last").strong().size(14.0));
+                    ui.add(egui::DragValue::new(&mut self.stale_branch_days).clamp_range(1..=3650));
+                    ui.label(egui::RichText::new("days").strong().size(14.0));
+                    
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let stale_button = egui::Button::new(
+                            egui::RichText::new("🧹 Find Stale Branches")
+                                .size(14.0)
+                        )
+                        .fill(egui::Color32::from_rgb(120, 110, 90))
+                        .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(210, 200, 170)))
+                        .rounding(egui::Rounding::same(6.0))
+                        .min_size(egui::Vec2::new(180.0, 35.0));
+                        
+                        if ui.add(stale_button).clicked() {
+                            self.find_stale_branches();
+                        }
                     });
                 });
+                
+                if let Some(branches) = &self.stale_branch_results {
+                    ui.add_space(8.0);
+                    if branches.is_empty() {
+                        ui.label(egui::RichText::new("No stale branches").color(self.theme.accent(egui::Color32::from_rgb(100, 200, 100))));
+                    } else {
+                        for (branch, last_commit_date) in branches {
+                            let date = chrono::DateTime::from_timestamp(*last_commit_date, 0)
+                                .map(|datetime| datetime.format("%Y-%m-%d").to_string())
+                                .unwrap_or_default();
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new(branch).monospace());
+                                ui.label(egui::RichText::new(format!("last commit {}", date)).weak().size(12.0));
+                            });
+                        }
+                    }
+                }
             });
             
             ui.separator();
@@ -2214,9 +3997,7 @@ impl MultiRepoPusherApp {
                         .min_size(egui::Vec2::new(150.0, 35.0));
                         
                         if ui.add(restore_button).clicked() && !self.is_operation_running {
-                            // For now, we'll just show a message
-                            // In a real implementation, you would use a file dialog to select the backup file
-                            self.status_message = "Restore functionality would open a file dialog to select a backup file.".to_string();
+                            self.restore_configuration();
                         }
                         
                         let backup_button = egui::Button::new(
@@ -2229,14 +4010,14 @@ impl MultiRepoPusherApp {
                         .min_size(egui::Vec2::new(150.0, 35.0));
                         
                         if ui.add(backup_button).clicked() && !self.is_operation_running {
-                            // For now, we'll just show a message
-                            // In a real implementation, you would use a file dialog to select the backup location
-                            self.status_message = "Backup functionality would open a file dialog to select a backup location.".to_string();
+                            self.backup_configuration();
                         }
                     });
                 });
             });
         });
+        
+        self.render_conflicts_modal(ui.ctx());
     }
     
     // New function to handle the first-time setup modal
@@ -2334,6 +4115,8 @@ impl MultiRepoPusherApp {
                             .show_ui(ui, |ui| {
                                 ui.selectable_value(&mut self.new_repo_auth_type, AuthType::Default, "Default");
                                 ui.selectable_value(&mut self.new_repo_auth_type, AuthType::SSH, "SSH Key");
+                                ui.selectable_value(&mut self.new_repo_auth_type, AuthType::SshAgent, "SSH Agent");
+                                ui.selectable_value(&mut self.new_repo_auth_type, AuthType::CredentialHelper, "Git Credential Helper");
                                 ui.selectable_value(&mut self.new_repo_auth_type, AuthType::Token, "Personal Access Token");
                             });
                     });
@@ -2348,11 +4131,12 @@ impl MultiRepoPusherApp {
                             });
                             ui.add_space(5.0);
                             
-                            // Add OAuth button for GitHub token generation
+                            // Add OAuth button for token generation with the chosen provider
+                            Self::render_oauth_provider_picker(ui, "setup_oauth_provider", &mut self.oauth_provider);
                             ui.horizontal(|ui| {
                                 ui.label(egui::RichText::new("").weak().size(12.0));
                                 let oauth_button = egui::Button::new(
-                                    egui::RichText::new("🔑 Generate Token via GitHub OAuth")
+                                    egui::RichText::new(format!("🔑 Generate Token via {} OAuth", self.oauth_provider.name()))
                                         .size(12.0)
                                 )
                                 .fill(egui::Color32::from_rgb(60, 100, 160))
@@ -2360,11 +4144,11 @@ impl MultiRepoPusherApp {
                                 .rounding(egui::Rounding::same(4.0));
                                 
                                 if ui.add(oauth_button).clicked() {
-                                    self.open_github_oauth();
+                                    self.open_oauth();
                                 }
                             });
                             
-                            ui.label(egui::RichText::new("Click above to generate a personal access token via GitHub OAuth").weak().size(11.0));
+                            ui.label(egui::RichText::new(format!("Click above to generate a personal access token via {} OAuth", self.oauth_provider.name())).weak().size(11.0));
                             
                             // OAuth code input and exchange button
                             ui.add_space(10.0);
@@ -2372,7 +4156,7 @@ impl MultiRepoPusherApp {
                             ui.label(egui::RichText::new("Or enter OAuth Code:").strong().size(12.0));
                             ui.horizontal(|ui| {
                                 ui.label(egui::RichText::new("OAuth Code:").strong().size(14.0));
-                                ui.add_sized([ui.available_width() * 0.7, 28.0], egui::TextEdit::singleline(&mut self.oauth_code).hint_text("Enter code from GitHub"));
+                                ui.add_sized([ui.available_width() * 0.7, 28.0], egui::TextEdit::singleline(&mut self.oauth_code).hint_text(format!("Enter code from {}", self.oauth_provider.name())));
                             });
                             
                             let exchange_button = egui::Button::new(
@@ -2387,13 +4171,18 @@ impl MultiRepoPusherApp {
                                 self.exchange_oauth_code();
                             }
                             
-                            ui.label(egui::RichText::new("After entering the code from GitHub, click the button above to get your access token.").weak().size(11.0));
+                            ui.label(egui::RichText::new(format!("After entering the code from {}, click the button above to get your access token.", self.oauth_provider.name())).weak().size(11.0));
                         },
                         AuthType::SSH => {
                             ui.horizontal(|ui| {
                                 ui.label(egui::RichText::new("SSH Key Path:").strong().size(14.0));
                                 ui.add_sized([ui.available_width() * 0.7, 28.0], egui::TextEdit::singleline(&mut self.account_ssh_key_path).hint_text("~/.ssh/id_rsa"));
                             });
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new("Key Passphrase:").strong().size(14.0));
+                                ui.add_sized([ui.available_width() * 0.7, 28.0], egui::TextEdit::singleline(&mut self.account_ssh_passphrase).password(true).hint_text("Leave empty for unencrypted keys"))
+                                    .on_hover_text("Kept until the app closes, passphrases aren't saved to repos.json");
+                            });
                             ui.label(egui::RichText::new("Ensure your SSH key is added to ssh-agent").weak().size(11.0));
                         },
                         _ => {
@@ -2448,8 +4237,8 @@ impl MultiRepoPusherApp {
         }
         
         // Validate repository URL
-        if !validate_repository_url(&self.new_repo_url) {
-            self.status_message = "Invalid repository URL format".to_string();
+        if let Err(e) = parse_repository_url(&self.new_repo_url) {
+            self.status_message = format!("Invalid repository URL: {}", e);
             return;
         }
         
@@ -2475,10 +4264,16 @@ impl MultiRepoPusherApp {
             },
             AuthType::SSH => {
                 repo_info.ssh_key_path = self.account_ssh_key_path.clone();
+                repo_info.ssh_passphrase = optional_passphrase(&self.account_ssh_passphrase);
             },
             _ => {}
         }
         
+        // Commit as the account entered in the setup form
+        if !self.account_username.is_empty() && !self.account_email.is_empty() {
+            repo_info = repo_info.with_author(self.account_username.clone(), self.account_email.clone());
+        }
+        
         // Validate the repository configuration before adding it
         match verify_authentication(&repo_info) {
             Ok(true) => {
@@ -2507,6 +4302,38 @@ impl MultiRepoPusherApp {
         }
     }
     
+    // Let the user pick the service an OAuth token is requested from
+    fn render_oauth_provider_picker(ui: &mut egui::Ui, id: &str, provider: &mut Provider) {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Provider:").strong().size(14.0));
+            egui::ComboBox::from_id_source(id)
+                .selected_text(provider.name())
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(provider, Provider::GitHub, "GitHub");
+                    ui.selectable_value(provider, Provider::GitLab, "GitLab");
+                });
+        });
+    }
+    
+    // Open the OAuth authorization page of the selected provider
+    fn open_oauth(&mut self) {
+        if self.oauth_provider == Provider::GitHub {
+            self.open_github_oauth();
+            return;
+        }
+        
+        let auth_url = self.oauth_provider.auth_url();
+        let provider = self.oauth_provider.name();
+        match webbrowser::open(&auth_url) {
+            Ok(_) => {
+                self.status_message = format!("{} OAuth page opened in your browser. Please authorize the application and enter the authorization code below.", provider);
+            },
+            Err(e) => {
+                self.status_message = format!("Failed to open browser: {}. Please manually go to {}", e, auth_url);
+            }
+        }
+    }
+    
     // New function to open GitHub OAuth flow
     fn open_github_oauth(&mut self) {
         self.status_message = "Opening GitHub for OAuth authentication...".to_string();
@@ -2548,99 +4375,65 @@ impl MultiRepoPusherApp {
         self.oauth_code.clear();
     }
     
-    // Backup current configuration to a file
-    fn backup_configuration(&mut self, backup_path: &str) -> Result<(), String> {
-        let config = self.config.lock().unwrap();
-        let config_str = serde_json::to_string_pretty(&*config)
-            .map_err(|e| format!("Failed to serialize configuration: {}", e))?;
-        std::fs::write(backup_path, config_str)
-            .map_err(|e| format!("Failed to write backup file: {}", e))
-    }
-    
-    // Restore configuration from a backup file
-    fn restore_configuration(&mut self, backup_path: &str) -> Result<(), String> {
-        let config_str = std::fs::read_to_string(backup_path)
-            .map_err(|e| format!("Failed to read backup file: {}", e))?;
-        let restored_config: RepoConfig = serde_json::from_str(&config_str)
-            .map_err(|e| format!("Failed to parse backup file: {}", e))?;
+    // Write a timestamped snapshot of the configuration to a file picked by the user
+    fn backup_configuration(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(backup_file_name(chrono::Local::now()))
+            .add_filter("JSON", &["json"])
+            .save_file()
+        else {
+            return;
+        };
         
-        let mut config = self.config.lock().unwrap();
-        *config = restored_config;
-        Ok(())
-    }
-    
-    // Save current configuration using the provided save function
-    fn save_current_configuration(&mut self) -> Result<(), String> {
-        let config = self.config.lock().unwrap();
-        (self.save_config_fn)(&config)
-            .map_err(|e| format!("Failed to save configuration: {}", e))
+        let path = path.to_string_lossy().to_string();
+        let result = backup_config(&self.config.lock().unwrap(), &path);
+        self.status_message = match result {
+            Ok(()) => format!("Configuration backed up to {}", path),
+            Err(e) => format!("Failed to back up configuration: {}", e),
+        };
     }
     
-    // Generate diff for selected repository
-    fn generate_repository_diff(&mut self) -> Result<(), String> {
-        let config = self.config.lock().unwrap();
-        
-        if self.selected_repo_for_diff >= config.repositories.len() {
-            return Err("Invalid repository selection".to_string());
-        }
-        
-        let repo = &config.repositories[self.selected_repo_for_diff];
+    // Replace the configuration with a backup picked by the user, unless the backup has problems
+    fn restore_configuration(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .pick_file()
+        else {
+            return;
+        };
         
-        // Try to generate a real diff
-        match generate_repository_diff(repo, ".", &self.diff_branch1, &self.diff_branch2) {
-            Ok(diff) => {
-                self.diff_content = format!(
-                    "Repository: {}\nBranch 1: {}\nBranch 2: {}\n\nFiles changed: {}\nInsertions: {}\nDeletions: {}\n\n{}",
-                    repo.name, self.diff_branch1, self.diff_branch2,
-                    diff.stats.files_changed, diff.stats.insertions, diff.stats.deletions,
-                    diff.diff_content
-                );
-                self.show_diff_viewer = true;
-                Ok(())
-            },
+        let path = path.to_string_lossy().to_string();
+        let restored_config = match restore_config(&path) {
+            Ok(config) => config,
             Err(e) => {
-                // Fallback to placeholder if diff generation fails
-                self.diff_content = format!(
-                    "Diff for repository: {}\n\nBranch 1: {}\nBranch 2: {}\n\nError generating diff: {}\n\nThis might be because the repository is not cloned locally or the branches don't exist.",
-                    repo.name, self.diff_branch1, self.diff_branch2, e
-                );
-                self.show_diff_viewer = true;
-                Ok(())
+                self.status_message = format!("Failed to restore configuration: {}", e);
+                return;
             }
+        };
+        
+        let problems = restored_config.validate();
+        if !problems.is_empty() {
+            let problems: Vec<String> = problems.iter().map(|problem| problem.to_string()).collect();
+            self.status_message = format!("Backup not restored, it has problems: {}", problems.join("; "));
+            return;
         }
+        
+        self.record_undo();
+        *self.config.lock().unwrap() = restored_config;
+        // Indices into the old repository list
+        self.selected_repositories.clear();
+        self.selected_account_index = 0;
+        self.status_message = match self.save_current_configuration() {
+            Ok(()) => format!("Configuration restored from {}", path),
+            Err(e) => e,
+        };
     }
     
-    // Generate working directory diff for selected repository
-    fn generate_working_directory_diff(&mut self) -> Result<(), String> {
+    // Save current configuration using the provided save function
+    fn save_current_configuration(&mut self) -> Result<(), String> {
         let config = self.config.lock().unwrap();
-        
-        if self.selected_repo_for_diff >= config.repositories.len() {
-            return Err("Invalid repository selection".to_string());
-        }
-        
-        let repo = &config.repositories[self.selected_repo_for_diff];
-        
-        // Try to generate a real diff between working directory and HEAD
-        match crate::core::repository_comparison::generate_working_directory_diff(repo, ".") {
-            Ok(diff) => {
-                self.diff_content = format!(
-                    "Repository: {}\n\nWorking Directory vs HEAD\n\nFiles changed: {}\nInsertions: {}\nDeletions: {}\n\n{}",
-                    repo.name,
-                    diff.stats.files_changed, diff.stats.insertions, diff.stats.deletions,
-                    diff.diff_content
-                );
-                self.show_diff_viewer = true;
-                Ok(())
-            },
-            Err(e) => {
-                // Fallback to placeholder if diff generation fails
-                self.diff_content = format!(
-                    "Working directory diff for repository: {}\n\nError generating diff: {}\n\nThis might be because the repository is not cloned locally.",
-                    repo.name, e
-                );
-                self.show_diff_viewer = true;
-                Ok(())
-            }
-        }
+        self.config_problems = config.validate();
+        (self.save_config_fn)(&config)
+            .map_err(|e| format!("Failed to save configuration: {}", e))
     }
 }
@@ -1,18 +1,42 @@
 use eframe::egui;
 use std::sync::{Arc, Mutex};
-use crate::core::commit_history::{CommitInfo, CommitDiff, get_repository_commits, get_commit_diff};
+use crate::core::commit_history::{
+    BlameLine, CommitInfo, CommitDiff, CommitFilter,
+    blame_file, filter_commits, get_repository_commits, get_commit_diff, get_file_history
+};
+use crate::core::git_operations::{revert_commit, CommitOptions};
 use crate::core::repository::RepoConfig;
+use crate::gui::diff_view::DiffView;
 
 pub struct CommitHistoryViewer {
     config: Arc<Mutex<RepoConfig>>,
     selected_repo_index: Option<usize>,
     commits: Vec<CommitInfo>,
     selected_commit: Option<CommitDiff>,
+    diff_view: Option<DiffView>,
     loading: bool,
     error_message: Option<String>,
     show_commit_details: bool,
+    file_history: Option<(String, Vec<CommitInfo>)>, // (path, commits that touched it)
+    blame: Option<(String, Vec<BlameLine>)>,         // (path, blamed lines at HEAD)
+    commit_limit: usize,
+    all_commits: bool,
+    // Set when the history was cut off at MAX_LOADED_COMMITS
+    history_truncated: bool,
+    // Filters are applied to the loaded commits and survive refreshes
+    search_text: String,
+    author_filter: String,
+    date_from: String, // YYYY-MM-DD
+    date_to: String,   // YYYY-MM-DD
 }
 
+// How many more commits "Load more" fetches
+const COMMIT_PAGE_SIZE: usize = 50;
+
+// Every loaded commit is rendered as its own row, so even "All commits" stops here
+// to keep the UI responsive on very large repositories
+const MAX_LOADED_COMMITS: usize = 10_000;
+
 impl CommitHistoryViewer {
     pub fn new(config: Arc<Mutex<RepoConfig>>) -> Self {
         Self {
@@ -20,9 +44,34 @@ impl CommitHistoryViewer {
             selected_repo_index: None,
             commits: Vec::new(),
             selected_commit: None,
+            diff_view: None,
             loading: false,
             error_message: None,
             show_commit_details: false,
+            file_history: None,
+            blame: None,
+            commit_limit: COMMIT_PAGE_SIZE,
+            all_commits: false,
+            history_truncated: false,
+            search_text: String::new(),
+            author_filter: String::new(),
+            date_from: String::new(),
+            date_to: String::new(),
+        }
+    }
+
+    // The history is read from the repository in the current directory, whichever repository is selected
+    fn repo_path(&self) -> &'static str {
+        "."
+    }
+
+    // Sign and attribute commits made from the history the way the selected repository's pushes are
+    fn commit_options(&self) -> CommitOptions {
+        let config = self.config.lock().unwrap();
+        let options = CommitOptions::from_config(&config);
+        match self.selected_repo_index.and_then(|index| config.repositories.get(index)) {
+            Some(repo_info) => options.with_identity_of(repo_info),
+            None => options,
         }
     }
 
@@ -31,16 +80,14 @@ impl CommitHistoryViewer {
             self.loading = true;
             self.error_message = None;
             
-            // Get repository path - in a real implementation, you would get the actual path
-            // For now, we'll use the current directory as a placeholder
-            let repo_path = ".";
-            
-            let result = get_repository_commits(repo_path, 50);
+            let requested = if self.all_commits { usize::MAX } else { self.commit_limit };
+            let result = get_repository_commits(self.repo_path(), requested.min(MAX_LOADED_COMMITS));
             
             self.loading = false;
             
             match result {
                 Ok(commits) => {
+                    self.history_truncated = requested > MAX_LOADED_COMMITS && commits.len() == MAX_LOADED_COMMITS;
                     self.commits = commits;
                 }
                 Err(e) => {
@@ -50,20 +97,34 @@ impl CommitHistoryViewer {
         }
     }
 
+    // Build the filter from the search fields. Dates that don't parse are ignored.
+    fn current_filter(&self) -> CommitFilter {
+        let parse_date = |text: &str| chrono::NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").ok();
+        
+        CommitFilter {
+            text: self.search_text.clone(),
+            author: self.author_filter.clone(),
+            since: parse_date(&self.date_from)
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|datetime| datetime.and_utc().timestamp()),
+            // The end date includes the whole day
+            until: parse_date(&self.date_to)
+                .and_then(|date| date.and_hms_opt(23, 59, 59))
+                .map(|datetime| datetime.and_utc().timestamp()),
+        }
+    }
+
     pub fn show_commit_details(&mut self, commit_id: &str) {
         self.loading = true;
         self.error_message = None;
         
-        // Get repository path - in a real implementation, you would get the actual path
-        // For now, we'll use the current directory as a placeholder
-        let repo_path = ".";
-        
-        let result = get_commit_diff(repo_path, commit_id);
+        let result = get_commit_diff(self.repo_path(), commit_id);
         
         self.loading = false;
         
         match result {
             Ok(commit_diff) => {
+                self.diff_view = Some(DiffView::new(commit_diff.diff_content.clone()));
                 self.selected_commit = Some(commit_diff);
                 self.show_commit_details = true;
             }
@@ -73,6 +134,52 @@ impl CommitHistoryViewer {
         }
     }
 
+    pub fn show_file_history(&mut self, file_path: &str) {
+        self.error_message = None;
+        
+        match get_file_history(self.repo_path(), file_path, self.commit_limit) {
+            Ok(commits) => {
+                self.file_history = Some((file_path.to_string(), commits));
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to load file history: {}", e));
+            }
+        }
+    }
+
+    pub fn revert_commit(&mut self, commit_id: &str) {
+        self.error_message = None;
+        
+        let options = self.commit_options();
+        let result = git2::Repository::open(self.repo_path())
+            .map_err(anyhow::Error::from)
+            .and_then(|repo| revert_commit(&repo, commit_id, &options));
+        
+        match result {
+            Ok(()) => {
+                // Show the new revert commit at the top of the history
+                self.show_commit_details = false;
+                self.load_commit_history();
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to revert {}: {}", commit_id, e));
+            }
+        }
+    }
+
+    pub fn show_blame(&mut self, file_path: &str) {
+        self.error_message = None;
+        
+        match blame_file(self.repo_path(), file_path) {
+            Ok(lines) => {
+                self.blame = Some((file_path.to_string(), lines));
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to blame {}: {}", file_path, e));
+            }
+        }
+    }
+
     pub fn render(&mut self, ui: &mut egui::Ui) {
         ui.heading("📜 Commit History Viewer");
         ui.separator();
@@ -121,6 +228,12 @@ impl CommitHistoryViewer {
         
         // Commit details modal
         self.render_commit_details_modal(ui.ctx());
+        
+        // File history modal
+        self.render_file_history_modal(ui.ctx());
+        
+        // Blame modal
+        self.render_blame_modal(ui.ctx());
     }
 
     fn render_repository_selection(&mut self, ui: &mut egui::Ui) {
@@ -164,8 +277,33 @@ impl CommitHistoryViewer {
                     if ui.button("🔄 Refresh").clicked() {
                         self.load_commit_history();
                     }
+                    
+                    ui.separator();
+                    
+                    ui.label(egui::RichText::new("Limit:").strong());
+                    let limit_response = ui.add_enabled(
+                        !self.all_commits,
+                        egui::DragValue::new(&mut self.commit_limit)
+                            .clamp_range(1..=MAX_LOADED_COMMITS)
+                            .speed(10.0)
+                    );
+                    let all_response = ui.checkbox(&mut self.all_commits, "All commits");
+                    
+                    // Reload once editing is done rather than on every drag step
+                    if (limit_response.drag_stopped() || limit_response.lost_focus() || all_response.changed())
+                        && self.selected_repo_index.is_some()
+                    {
+                        self.load_commit_history();
+                    }
                 }
             });
+            
+            if self.history_truncated {
+                ui.label(egui::RichText::new(format!(
+                    "⚠ Only the {} most recent commits are loaded to keep the viewer responsive",
+                    MAX_LOADED_COMMITS
+                )).color(egui::Color32::YELLOW));
+            }
         });
         
         ui.add_space(10.0);
@@ -176,8 +314,18 @@ impl CommitHistoryViewer {
             ui.heading("Recent Commits");
             ui.add_space(10.0);
             
-            // Clone the commits to avoid borrowing issues
-            let commits = self.commits.clone();
+            self.render_filters(ui);
+            
+            ui.add_space(10.0);
+            
+            // Clone the matching commits to avoid borrowing issues
+            let filter = self.current_filter();
+            let commits: Vec<CommitInfo> = filter_commits(&self.commits, &filter)
+                .into_iter()
+                .cloned()
+                .collect();
+            
+            ui.label(egui::RichText::new(format!("Showing {} of {} loaded commits", commits.len(), self.commits.len())).weak());
             
             egui::ScrollArea::vertical()
                 .max_height(400.0)
@@ -223,13 +371,55 @@ impl CommitHistoryViewer {
                         
                         ui.add_space(5.0);
                     }
+                    
+                    // A full page means there may be more history to fetch
+                    if !self.all_commits && self.commits.len() >= self.commit_limit && self.commit_limit < MAX_LOADED_COMMITS {
+                        ui.vertical_centered(|ui| {
+                            if ui.button("⬇ Load more").clicked() {
+                                self.commit_limit = (self.commit_limit + COMMIT_PAGE_SIZE).min(MAX_LOADED_COMMITS);
+                                self.load_commit_history();
+                            }
+                        });
+                    }
                 });
         });
     }
 
+    fn render_filters(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Search:").strong());
+            ui.add_sized([220.0, 22.0], egui::TextEdit::singleline(&mut self.search_text)
+                .hint_text("message, author or commit id"));
+            
+            ui.label(egui::RichText::new("Author:").strong());
+            ui.add_sized([140.0, 22.0], egui::TextEdit::singleline(&mut self.author_filter)
+                .hint_text("name or email"));
+        });
+        
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("From:").strong());
+            ui.add_sized([100.0, 22.0], egui::TextEdit::singleline(&mut self.date_from)
+                .hint_text("YYYY-MM-DD"));
+            
+            ui.label(egui::RichText::new("To:").strong());
+            ui.add_sized([100.0, 22.0], egui::TextEdit::singleline(&mut self.date_to)
+                .hint_text("YYYY-MM-DD"));
+            
+            if ui.button("✖ Clear").clicked() {
+                self.search_text.clear();
+                self.author_filter.clear();
+                self.date_from.clear();
+                self.date_to.clear();
+            }
+        });
+    }
+
     fn render_commit_details_modal(&mut self, ctx: &egui::Context) {
         if self.show_commit_details {
             let mut open = self.show_commit_details;
+            let mut history_request = None;
+            let mut blame_request = None;
+            let mut revert_request = None;
             egui::Window::new("Commit Details")
                 .open(&mut open)
                 .resizable(true)
@@ -264,6 +454,14 @@ impl CommitHistoryViewer {
                                     ui.label(egui::RichText::new(&commit_diff.commit_info.parents[0][..7]).monospace());
                                 });
                             }
+                            
+                            ui.add_space(5.0);
+                            if ui.button("↩ Revert")
+                                .on_hover_text("Create a new commit that undoes this one")
+                                .clicked()
+                            {
+                                revert_request = Some(commit_diff.commit_info.id.clone());
+                            }
                         });
                         
                         ui.add_space(10.0);
@@ -293,7 +491,19 @@ impl CommitHistoryViewer {
                                                         crate::core::commit_history::FileChangeStatus::Renamed => egui::Color32::YELLOW,
                                                     }));
                                                 
-                                                ui.label(&file_change.path);
+                                                if ui.link(&file_change.path)
+                                                    .on_hover_text("History of this file")
+                                                    .clicked()
+                                                {
+                                                    history_request = Some(file_change.path.clone());
+                                                }
+                                                
+                                                // Deleted files have nothing left to blame
+                                                if file_change.status != crate::core::commit_history::FileChangeStatus::Deleted
+                                                    && ui.small_button("Blame").clicked()
+                                                {
+                                                    blame_request = Some(file_change.path.clone());
+                                                }
                                             });
                                         }
                                     });
@@ -305,20 +515,119 @@ impl CommitHistoryViewer {
                         // Diff content
                         ui.group(|ui| {
                             ui.heading("Diff");
-                            egui::ScrollArea::vertical()
-                                .max_height(300.0)
-                                .show(ui, |ui| {
-                                    ui.add(egui::TextEdit::multiline(&mut commit_diff.diff_content.clone())
-                                        .font(egui::TextStyle::Monospace)
-                                        .desired_width(f32::INFINITY)
-                                        .interactive(false));
-                                });
+                            if let Some(diff_view) = &mut self.diff_view {
+                                diff_view.render(ui, "commit_diff", 300.0);
+                            }
                         });
                     } else {
                         ui.label("No commit details available");
                     }
                 });
             self.show_commit_details = open;
+            
+            if let Some(file_path) = history_request {
+                self.show_file_history(&file_path);
+            }
+            
+            if let Some(file_path) = blame_request {
+                self.show_blame(&file_path);
+            }
+            
+            if let Some(commit_id) = revert_request {
+                self.revert_commit(&commit_id);
+            }
+        }
+    }
+
+    fn render_file_history_modal(&mut self, ctx: &egui::Context) {
+        let Some((file_path, commits)) = &self.file_history else {
+            return;
+        };
+        
+        let mut open = true;
+        let mut selected_commit = None;
+        
+        egui::Window::new(format!("History of {}", file_path))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                if commits.is_empty() {
+                    ui.label(egui::RichText::new("No commits touched this file").weak());
+                    return;
+                }
+                
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        for commit in commits {
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new(&commit.short_id)
+                                    .monospace()
+                                    .color(egui::Color32::from_rgb(100, 150, 200)));
+                                
+                                let datetime = chrono::DateTime::from_timestamp(commit.date, 0)
+                                    .unwrap_or_else(chrono::Utc::now);
+                                ui.label(egui::RichText::new(datetime.format("%Y-%m-%d").to_string()).weak());
+                                ui.label(egui::RichText::new(&commit.author).weak());
+                                
+                                let summary = commit.message.lines().next().unwrap_or_default();
+                                if ui.link(summary).clicked() {
+                                    selected_commit = Some(commit.id.clone());
+                                }
+                            });
+                        }
+                    });
+            });
+        
+        if !open {
+            self.file_history = None;
+        }
+        
+        if let Some(commit_id) = selected_commit {
+            self.show_commit_details(&commit_id);
         }
     }
-}
\ No newline at end of file
+
+    fn render_blame_modal(&mut self, ctx: &egui::Context) {
+        let Some((file_path, lines)) = &self.blame else {
+            return;
+        };
+        
+        let mut open = true;
+        
+        egui::Window::new(format!("Blame: {}", file_path))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(700.0)
+            .default_height(500.0)
+            .show(ctx, |ui| {
+                if lines.is_empty() {
+                    ui.label(egui::RichText::new("The file is empty").weak());
+                    return;
+                }
+                
+                let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                egui::ScrollArea::both()
+                    .auto_shrink([false, true])
+                    .show_rows(ui, row_height, lines.len(), |ui, row_range| {
+                        ui.spacing_mut().item_spacing.y = 0.0;
+                        for line in &lines[row_range] {
+                            let short_id = line.commit_id.get(..7).unwrap_or(&line.commit_id);
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new(format!("{:>5}", line.line_no)).monospace().weak());
+                                ui.label(egui::RichText::new(format!("{:<7}", short_id))
+                                    .monospace()
+                                    .color(egui::Color32::from_rgb(100, 150, 200)));
+                                ui.label(egui::RichText::new(format!("{:<16.16}", line.author)).monospace().weak());
+                                ui.add(egui::Label::new(egui::RichText::new(&line.content).monospace()).wrap(false));
+                            });
+                        }
+                    });
+            });
+        
+        if !open {
+            self.blame = None;
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use eframe::egui;
+use std::sync::{Arc, Mutex};
+use crate::core::commit_history::get_repository_commits;
+use crate::core::repository::{RepoConfig, RepositoryInfo};
+use crate::core::repository_comparison::{DiffSettings, DiffStats, RepositoryDiff, generate_repository_diff, generate_working_directory_diff, repository_diff_stats};
+use crate::gui::diff_view::DiffView;
+
+#[derive(PartialEq, Clone, Copy)]
+enum ComparisonMode {
+    Refs,
+    WorkingDirectory,
+}
+
+pub struct ComparisonViewer {
+    config: Arc<Mutex<RepoConfig>>,
+    selected_repo_index: Option<usize>,
+    mode: ComparisonMode,
+    ref1: String,
+    ref2: String,
+    available_refs: Vec<(String, String)>, // (ref, label)
+    diff: Option<RepositoryDiff>,
+    diff_view: Option<DiffView>,
+    summary: Option<DiffStats>, // Quick counts for the refs, shown before the full diff is generated
+    settings: DiffSettings,
+    error_message: Option<String>,
+}
+
+impl ComparisonViewer {
+    pub fn new(config: Arc<Mutex<RepoConfig>>) -> Self {
+        Self {
+            config,
+            selected_repo_index: None,
+            mode: ComparisonMode::Refs,
+            ref1: "main".to_string(),
+            ref2: "develop".to_string(),
+            available_refs: Vec::new(),
+            diff: None,
+            diff_view: None,
+            summary: None,
+            settings: DiffSettings::default(),
+            error_message: None,
+        }
+    }
+
+    // The selected repository and the path of its local clone.
+    // Repositories that aren't cloned are looked up in the current directory.
+    fn selected_repository(&self) -> Option<(RepositoryInfo, String)> {
+        let index = self.selected_repo_index?;
+        let config = self.config.lock().unwrap();
+        let repo_info = config.repositories.get(index)?.clone();
+        
+        let repo_path = if repo_info.local_path.is_empty() {
+            ".".to_string()
+        } else {
+            repo_info.local_path.clone()
+        };
+        
+        Some((repo_info, repo_path))
+    }
+
+    // Load the branches and recent commits that can be picked as refs
+    fn load_refs(&mut self) {
+        self.available_refs.clear();
+        
+        let Some((_, repo_path)) = self.selected_repository() else {
+            return;
+        };
+        
+        let repo = match git2::Repository::open(&repo_path) {
+            Ok(repo) => repo,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to open repository: {}", e));
+                return;
+            }
+        };
+        
+        if let Ok(branches) = repo.branches(Some(git2::BranchType::Local)) {
+            for (branch, _) in branches.flatten() {
+                if let Ok(Some(name)) = branch.name() {
+                    self.available_refs.push((name.to_string(), format!("🌿 {}", name)));
+                }
+            }
+        }
+        
+        if let Ok(commits) = get_repository_commits(&repo_path, 20) {
+            for commit in commits {
+                let summary = commit.message.lines().next().unwrap_or_default().to_string();
+                self.available_refs.push((commit.short_id.clone(), format!("{} {}", commit.short_id, summary)));
+            }
+        }
+    }
+
+    // Count the changed files and lines between the refs without building the patch
+    fn summarize_diff(&mut self) {
+        self.error_message = None;
+        self.summary = None;
+        
+        let Some((_, repo_path)) = self.selected_repository() else {
+            self.error_message = Some("Select a repository to compare".to_string());
+            return;
+        };
+        
+        match repository_diff_stats(&repo_path, &self.ref1, &self.ref2, &self.settings) {
+            Ok(stats) => self.summary = Some(stats),
+            Err(e) => self.error_message = Some(format!("Failed to compare refs: {}", e)),
+        }
+    }
+
+    pub fn generate_diff(&mut self) {
+        self.error_message = None;
+        self.diff = None;
+        
+        let Some((repo_info, repo_path)) = self.selected_repository() else {
+            self.error_message = Some("Select a repository to compare".to_string());
+            return;
+        };
+        
+        let result = match self.mode {
+            ComparisonMode::Refs => generate_repository_diff(&repo_info, &repo_path, &self.ref1, &self.ref2, &self.settings),
+            ComparisonMode::WorkingDirectory => generate_working_directory_diff(&repo_info, &repo_path, &self.settings),
+        };
+        
+        match result {
+            Ok(diff) => {
+                self.diff_view = Some(DiffView::new(diff.diff_content.clone()));
+                self.diff = Some(diff);
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to generate diff: {}", e));
+            }
+        }
+    }
+
+    pub fn render(&mut self, ui: &mut egui::Ui) {
+        ui.heading("🔍 Repository Comparison");
+        ui.separator();
+        
+        self.render_controls(ui);
+        
+        // Error message
+        if let Some(error) = &self.error_message {
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("⚠").color(egui::Color32::YELLOW));
+                    ui.label(egui::RichText::new(error).color(egui::Color32::YELLOW));
+                });
+            });
+            ui.add_space(10.0);
+        }
+        
+        if let (Some(diff), Some(diff_view)) = (&self.diff, &mut self.diff_view) {
+            Self::render_diff(ui, diff, diff_view);
+        } else {
+            ui.vertical_centered(|ui| {
+                ui.add_space(30.0);
+                ui.label(egui::RichText::new("Pick a repository and generate a diff").weak());
+                ui.add_space(30.0);
+            });
+        }
+    }
+
+    fn render_controls(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Repository:").strong());
+                
+                let repo_names: Vec<String> = {
+                    let config = self.config.lock().unwrap();
+                    config.repositories.iter().map(|repo| repo.name.clone()).collect()
+                };
+                
+                if repo_names.is_empty() {
+                    ui.label(egui::RichText::new("No repositories configured").weak());
+                    return;
+                }
+                
+                let selected_text = self.selected_repo_index
+                    .and_then(|index| repo_names.get(index))
+                    .map(|name| name.as_str())
+                    .unwrap_or("Select repository");
+                
+                egui::ComboBox::from_id_source("comparison_repo_selection")
+                    .selected_text(selected_text)
+                    .show_ui(ui, |ui| {
+                        for (i, repo_name) in repo_names.iter().enumerate() {
+                            if ui.selectable_label(self.selected_repo_index == Some(i), repo_name).clicked() {
+                                self.selected_repo_index = Some(i);
+                                self.diff = None;
+                                self.diff_view = None;
+                                self.summary = None;
+                                self.error_message = None;
+                                self.load_refs();
+                            }
+                        }
+                    });
+            });
+            
+            ui.add_space(8.0);
+            
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Compare:").strong());
+                ui.radio_value(&mut self.mode, ComparisonMode::Refs, "Two refs");
+                ui.radio_value(&mut self.mode, ComparisonMode::WorkingDirectory, "Working directory vs HEAD");
+            });
+            
+            if self.mode == ComparisonMode::Refs {
+                ui.add_space(8.0);
+                
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("From:").strong());
+                    let from_changed = Self::render_ref_picker(ui, "comparison_ref1", &mut self.ref1, &self.available_refs);
+                    
+                    ui.label(egui::RichText::new("To:").strong());
+                    let to_changed = Self::render_ref_picker(ui, "comparison_ref2", &mut self.ref2, &self.available_refs);
+                    
+                    // A summary of other refs would be misleading
+                    if from_changed || to_changed {
+                        self.summary = None;
+                    }
+                });
+                
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(self.selected_repo_index.is_some(), egui::Button::new("📊 Summary"))
+                        .on_hover_text("Count the changed files and lines without loading the whole diff")
+                        .clicked()
+                    {
+                        self.summarize_diff();
+                    }
+                    if let Some(summary) = &self.summary {
+                        ui.label(egui::RichText::new(summary.summary()).strong());
+                    }
+                });
+            }
+            
+            ui.add_space(8.0);
+            
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Options:").strong());
+                let rename_threshold = ui.add(
+                    egui::DragValue::new(&mut self.settings.rename_threshold)
+                        .clamp_range(0..=100)
+                        .suffix("%")
+                );
+                rename_threshold.on_hover_text("How similar a deleted and an added file must be to be shown as a rename");
+                ui.label("rename similarity");
+                ui.separator();
+                ui.checkbox(&mut self.settings.ignore_whitespace, "Ignore whitespace")
+                    .on_hover_text("Hide changes that only add, remove or change whitespace");
+                ui.separator();
+                ui.add(egui::DragValue::new(&mut self.settings.context_lines).clamp_range(0..=1000))
+                    .on_hover_text("Unchanged lines shown around each change, 0 shows only the changed lines");
+                ui.label("context lines");
+            });
+            
+            ui.add_space(10.0);
+            
+            ui.horizontal(|ui| {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let diff_button = egui::Button::new(
+                        egui::RichText::new("🔍 Generate Diff")
+                            .size(14.0)
+                    )
+                    .fill(egui::Color32::from_rgb(100, 150, 100))
+                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(180, 230, 180)))
+                    .rounding(egui::Rounding::same(6.0))
+                    .min_size(egui::Vec2::new(120.0, 30.0));
+                    
+                    if ui.add_enabled(self.selected_repo_index.is_some(), diff_button).clicked() {
+                        self.generate_diff();
+                    }
+                });
+            });
+        });
+        
+        ui.add_space(10.0);
+    }
+
+    // A ref can be typed in or picked from the branches and recent commits. True when it changed.
+    fn render_ref_picker(ui: &mut egui::Ui, id: &str, value: &mut String, available_refs: &[(String, String)]) -> bool {
+        let mut changed = ui.add_sized([140.0, 22.0], egui::TextEdit::singleline(value).hint_text("branch or commit")).changed();
+        
+        egui::ComboBox::from_id_source(id)
+            .selected_text("▼")
+            .width(30.0)
+            .show_ui(ui, |ui| {
+                for (reference, label) in available_refs {
+                    changed |= ui.selectable_value(value, reference.clone(), label).changed();
+                }
+            });
+        changed
+    }
+
+    fn render_diff(ui: &mut egui::Ui, diff: &RepositoryDiff, diff_view: &mut DiffView) {
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.heading("Diff");
+                ui.label(egui::RichText::new(format!("{} files changed", diff.stats.files_changed)).strong());
+                ui.label(egui::RichText::new(format!("+{}", diff.stats.insertions)).color(egui::Color32::GREEN));
+                ui.label(egui::RichText::new(format!("-{}", diff.stats.deletions)).color(egui::Color32::RED));
+            });
+            
+            ui.add_space(5.0);
+            
+            if diff.diff_content.is_empty() {
+                ui.label(egui::RichText::new("No differences").weak());
+                return;
+            }
+            
+            diff_view.render(ui, "comparison_diff", 400.0);
+        });
+    }
+}
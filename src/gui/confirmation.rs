@@ -0,0 +1,99 @@
+/// How many files the push confirmation lists before summarizing the rest
+const PREVIEW_FILE_LIMIT: usize = 15;
+
+/// A destructive or hard to undo action that only runs once the user confirms it
+#[derive(Debug, Clone, PartialEq)]
+pub enum PendingAction {
+    /// Remove the repository at this config index
+    RemoveRepository { index: usize, name: String },
+    /// Delete the account edited in the side panel, at this config index
+    DeleteAccount { index: usize, name: String },
+    RemoveGroup { name: String },
+    /// Reset the local repository to HEAD, throwing away the merge
+    AbortMerge,
+    /// Stage every change and push, these are the files that get committed
+    PushAll { files: Vec<String> },
+}
+
+impl PendingAction {
+    pub fn title(&self) -> &'static str {
+        match self {
+            PendingAction::RemoveRepository { .. } => "Remove Repository",
+            PendingAction::DeleteAccount { .. } => "Delete Account",
+            PendingAction::RemoveGroup { .. } => "Delete Group",
+            PendingAction::AbortMerge => "Abort Merge",
+            PendingAction::PushAll { .. } => "Commit and Push",
+        }
+    }
+    
+    pub fn message(&self) -> String {
+        match self {
+            PendingAction::RemoveRepository { name, .. } => {
+                format!("Remove '{}' from the configuration? Its local checkout is left alone.", name)
+            },
+            PendingAction::DeleteAccount { name, .. } => {
+                format!("Delete the account '{}' and its credentials?", name)
+            },
+            PendingAction::RemoveGroup { name } => {
+                format!("Delete the group '{}'? Its repositories stay configured.", name)
+            },
+            PendingAction::AbortMerge => {
+                "Abort the merge? Every uncommitted change in the working tree is lost.".to_string()
+            },
+            PendingAction::PushAll { files } => {
+                let mut message = format!("Commit these {} files and push them to every repository?\n", files.len());
+                for file in files.iter().take(PREVIEW_FILE_LIMIT) {
+                    message.push_str(&format!("\n  {}", file));
+                }
+                if files.len() > PREVIEW_FILE_LIMIT {
+                    message.push_str(&format!("\n  ... and {} more", files.len() - PREVIEW_FILE_LIMIT));
+                }
+                message
+            },
+        }
+    }
+}
+
+/// Ask the user to confirm in a modal window. `Some(true)` once confirmed, `Some(false)` once
+/// cancelled or closed, `None` while the question is still open.
+pub fn confirm_action(ctx: &egui::Context, title: &str, message: &str) -> Option<bool> {
+    let mut answer = None;
+    let mut open = true;
+    egui::Window::new(format!("⚠ {}", title))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(message);
+            ui.add_space(12.0);
+            ui.horizontal(|ui| {
+                let confirm_button = egui::Button::new(egui::RichText::new(title).color(egui::Color32::WHITE))
+                    .fill(egui::Color32::from_rgb(150, 80, 80));
+                if ui.add(confirm_button).clicked() {
+                    answer = Some(true);
+                }
+                if ui.button("Cancel").clicked() || ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    answer = Some(false);
+                }
+            });
+        });
+    
+    if !open {
+        return Some(false);
+    }
+    answer
+}
+
+/// Settle the pending action with the answer from `confirm_action`: the action is returned
+/// to be run when confirmed, dropped when cancelled, and kept waiting while unanswered.
+pub fn resolve(pending: &mut Option<PendingAction>, answer: Option<bool>) -> Option<PendingAction> {
+    match answer {
+        Some(true) => pending.take(),
+        Some(false) => {
+            pending.take();
+            None
+        },
+        None => None,
+    }
+}
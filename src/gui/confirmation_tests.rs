@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use crate::gui::confirmation::{resolve, PendingAction};
+
+    fn remove_repo() -> PendingAction {
+        PendingAction::RemoveRepository { index: 2, name: "repo3".to_string() }
+    }
+
+    #[test]
+    fn test_confirmed_action_is_dispatched() {
+        let mut pending = Some(remove_repo());
+        assert_eq!(resolve(&mut pending, Some(true)), Some(remove_repo()));
+        assert_eq!(pending, None);
+    }
+
+    #[test]
+    fn test_cancelled_action_is_dropped() {
+        let mut pending = Some(PendingAction::AbortMerge);
+        assert_eq!(resolve(&mut pending, Some(false)), None);
+        assert_eq!(pending, None);
+    }
+
+    #[test]
+    fn test_unanswered_action_keeps_waiting() {
+        let mut pending = Some(PendingAction::RemoveGroup { name: "backend".to_string() });
+        assert_eq!(resolve(&mut pending, None), None);
+        assert_eq!(pending, Some(PendingAction::RemoveGroup { name: "backend".to_string() }));
+        
+        // Nothing pending, nothing to run
+        let mut pending = None;
+        assert_eq!(resolve(&mut pending, Some(true)), None);
+    }
+
+    #[test]
+    fn test_messages_name_the_target() {
+        assert!(remove_repo().message().contains("repo3"));
+        let delete_account = PendingAction::DeleteAccount { index: 0, name: "work".to_string() };
+        assert!(delete_account.message().contains("work"));
+        assert_eq!(delete_account.title(), "Delete Account");
+    }
+
+    #[test]
+    fn test_push_message_lists_the_staged_files() {
+        let push = PendingAction::PushAll { files: vec!["src/main.rs".to_string(), "README.md".to_string()] };
+        assert_eq!(push.title(), "Commit and Push");
+        assert_eq!(push.message(), "Commit these 2 files and push them to every repository?\n\n  src/main.rs\n  README.md");
+        
+        // Long lists are cut short
+        let files: Vec<String> = (0..20).map(|i| format!("file{}.txt", i)).collect();
+        let message = PendingAction::PushAll { files }.message();
+        assert!(message.contains("file14.txt"));
+        assert!(!message.contains("file15.txt"));
+        assert!(message.ends_with("... and 5 more"));
+    }
+}
@@ -0,0 +1,490 @@
+use eframe::egui;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+/// A single content line of a hunk with its line numbers in the old and new file
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffLineView {
+    pub kind: DiffLineKind,
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+    pub content: String,
+    pub no_newline_at_eof: bool,
+}
+
+/// A hunk of a unified diff together with the file it belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffHunkView {
+    pub file: String,
+    pub header: String,
+    pub old_start: usize,
+    pub new_start: usize,
+    pub lines: Vec<DiffLineView>,
+}
+
+/// One row of the side-by-side view: (old side, new side)
+pub type DiffRow<'a> = (Option<&'a DiffLineView>, Option<&'a DiffLineView>);
+
+// Emit a block of removed lines next to the added lines that replaced them
+fn flush_change_block<'a>(rows: &mut Vec<DiffRow<'a>>, removed: &mut Vec<&'a DiffLineView>, added: &mut Vec<&'a DiffLineView>) {
+    for i in 0..removed.len().max(added.len()) {
+        rows.push((removed.get(i).copied(), added.get(i).copied()));
+    }
+    removed.clear();
+    added.clear();
+}
+
+impl DiffHunkView {
+    /// Pair the lines up for side-by-side display. Context lines appear on both sides,
+    /// a run of removed lines is shown next to the run of added lines that follows it.
+    pub fn side_by_side_rows(&self) -> Vec<DiffRow<'_>> {
+        let mut rows = Vec::new();
+        let mut removed = Vec::new();
+        let mut added = Vec::new();
+        
+        for line in &self.lines {
+            match line.kind {
+                DiffLineKind::Removed => {
+                    // Removals after additions start a new change block
+                    if !added.is_empty() {
+                        flush_change_block(&mut rows, &mut removed, &mut added);
+                    }
+                    removed.push(line);
+                },
+                DiffLineKind::Added => added.push(line),
+                DiffLineKind::Context => {
+                    flush_change_block(&mut rows, &mut removed, &mut added);
+                    rows.push((Some(line), Some(line)));
+                }
+            }
+        }
+        flush_change_block(&mut rows, &mut removed, &mut added);
+        
+        rows
+    }
+}
+
+// Parse "@@ -old_start[,count] +new_start[,count] @@" into (old_start, old_count, new_start, new_count)
+fn parse_hunk_header(line: &str) -> Option<(usize, usize, usize, usize)> {
+    let ranges = line.strip_prefix("@@ ")?;
+    let end = ranges.find(" @@")?;
+    let mut parts = ranges[..end].split_whitespace();
+    
+    let parse_range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    
+    let (old_start, old_count) = parse_range(parts.next()?.strip_prefix('-')?)?;
+    let (new_start, new_count) = parse_range(parts.next()?.strip_prefix('+')?)?;
+    Some((old_start, old_count, new_start, new_count))
+}
+
+// "a/src/main.rs" -> "src/main.rs", "/dev/null" stays as is
+fn strip_path_prefix(path: &str) -> &str {
+    path.strip_prefix("a/").or_else(|| path.strip_prefix("b/")).unwrap_or(path)
+}
+
+/// Parse a unified diff (as produced by `git diff`) into hunks.
+/// Lines outside hunks (file headers, index lines, binary notices) only update the current file name.
+pub fn parse_unified_diff(diff: &str) -> Vec<DiffHunkView> {
+    let mut hunks: Vec<DiffHunkView> = Vec::new();
+    let mut current_file = String::new();
+    // Lines still expected in the current hunk for the old and new side
+    let mut old_remaining: usize = 0;
+    let mut new_remaining: usize = 0;
+    let mut old_line = 0;
+    let mut new_line = 0;
+    
+    for line in diff.lines() {
+        let in_hunk = old_remaining > 0 || new_remaining > 0;
+        
+        if line.starts_with('\\') {
+            // "\ No newline at end of file" refers to the line before it
+            if let Some(last) = hunks.last_mut().and_then(|hunk| hunk.lines.last_mut()) {
+                last.no_newline_at_eof = true;
+            }
+            continue;
+        }
+        
+        if in_hunk {
+            let (kind, content) = match line.chars().next() {
+                Some('+') => (DiffLineKind::Added, &line[1..]),
+                Some('-') => (DiffLineKind::Removed, &line[1..]),
+                Some(' ') => (DiffLineKind::Context, &line[1..]),
+                // Some tools drop the space of empty context lines
+                None => (DiffLineKind::Context, ""),
+                Some(_) => {
+                    // Malformed hunk, stop treating lines as content
+                    old_remaining = 0;
+                    new_remaining = 0;
+                    continue;
+                }
+            };
+            
+            let (old_number, new_number) = match kind {
+                DiffLineKind::Context => {
+                    old_line += 1;
+                    new_line += 1;
+                    old_remaining = old_remaining.saturating_sub(1);
+                    new_remaining = new_remaining.saturating_sub(1);
+                    (Some(old_line - 1), Some(new_line - 1))
+                },
+                DiffLineKind::Removed => {
+                    old_line += 1;
+                    old_remaining = old_remaining.saturating_sub(1);
+                    (Some(old_line - 1), None)
+                },
+                DiffLineKind::Added => {
+                    new_line += 1;
+                    new_remaining = new_remaining.saturating_sub(1);
+                    (None, Some(new_line - 1))
+                }
+            };
+            
+            if let Some(hunk) = hunks.last_mut() {
+                hunk.lines.push(DiffLineView {
+                    kind,
+                    old_line: old_number,
+                    new_line: new_number,
+                    content: content.to_string(),
+                    no_newline_at_eof: false,
+                });
+            }
+            continue;
+        }
+        
+        if let Some(paths) = line.strip_prefix("diff --git ") {
+            // "diff --git a/old b/new", the new path wins
+            current_file = paths
+                .rsplit_once(" b/")
+                .map(|(_, new_path)| new_path.to_string())
+                .unwrap_or_else(|| paths.to_string());
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            if path != "/dev/null" {
+                current_file = strip_path_prefix(path).to_string();
+            }
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            if path != "/dev/null" && current_file.is_empty() {
+                current_file = strip_path_prefix(path).to_string();
+            }
+        } else if let Some((old_start, old_count, new_start, new_count)) = parse_hunk_header(line) {
+            old_remaining = old_count;
+            new_remaining = new_count;
+            old_line = old_start;
+            new_line = new_start;
+            hunks.push(DiffHunkView {
+                file: current_file.clone(),
+                header: line.to_string(),
+                old_start,
+                new_start,
+                lines: Vec::new(),
+            });
+        }
+    }
+    
+    hunks
+}
+
+/// Part of a modified line in a word-level diff
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffSegment {
+    Unchanged(String),
+    Removed(String),
+    Added(String),
+}
+
+// Longer line pairs are shown as a whole removed and added line, the LCS table grows with both lengths
+const MAX_WORD_DIFF_CELLS: usize = 250_000;
+
+// Split a line into words, runs of whitespace and single punctuation characters
+fn tokenize(line: &str) -> Vec<&str> {
+    // 0: word, 1: whitespace, 2: anything else, which is never grouped
+    let class = |c: char| if c.is_alphanumeric() || c == '_' { 0 } else if c.is_whitespace() { 1 } else { 2 };
+    
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut previous = None;
+    for (index, c) in line.char_indices() {
+        let current = class(c);
+        if index > start && (previous != Some(current) || current == 2) {
+            tokens.push(&line[start..index]);
+            start = index;
+        }
+        previous = Some(current);
+    }
+    if start < line.len() {
+        tokens.push(&line[start..]);
+    }
+    tokens
+}
+
+// Append text to the segments, growing the last segment when it is of the same kind
+fn push_segment(segments: &mut Vec<DiffSegment>, segment: DiffSegment) {
+    match (segments.last_mut(), &segment) {
+        (Some(DiffSegment::Unchanged(last)), DiffSegment::Unchanged(text))
+        | (Some(DiffSegment::Removed(last)), DiffSegment::Removed(text))
+        | (Some(DiffSegment::Added(last)), DiffSegment::Added(text)) => last.push_str(text),
+        _ => segments.push(segment),
+    }
+}
+
+/// Split a modified line into the words both versions share and the words that were removed
+/// or added, using the longest common subsequence of their tokens. Removals come before additions.
+pub fn word_diff(old_line: &str, new_line: &str) -> Vec<DiffSegment> {
+    let old_tokens = tokenize(old_line);
+    let new_tokens = tokenize(new_line);
+    let (n, m) = (old_tokens.len(), new_tokens.len());
+    
+    let mut segments = Vec::new();
+    if n * m > MAX_WORD_DIFF_CELLS {
+        push_segment(&mut segments, DiffSegment::Removed(old_line.to_string()));
+        push_segment(&mut segments, DiffSegment::Added(new_line.to_string()));
+        return segments;
+    }
+    
+    // lengths[i][j]: length of the LCS of old_tokens[i..] and new_tokens[j..]
+    let mut lengths = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if old_tokens[i] == new_tokens[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+    
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_tokens[i] == new_tokens[j] {
+            push_segment(&mut segments, DiffSegment::Unchanged(old_tokens[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lengths[i + 1][j] >= lengths[i][j + 1]) {
+            push_segment(&mut segments, DiffSegment::Removed(old_tokens[i].to_string()));
+            i += 1;
+        } else {
+            push_segment(&mut segments, DiffSegment::Added(new_tokens[j].to_string()));
+            j += 1;
+        }
+    }
+    segments
+}
+
+/// How a line of a raw patch is colored
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PatchLineClass {
+    FileHeader,
+    HunkHeader,
+    Added,
+    Removed,
+    Context,
+}
+
+/// Classify a raw patch line by its prefix. File headers are checked first, so a
+/// removed line that itself starts with "--" is shown as a header; that's fine for coloring.
+pub fn classify_patch_line(line: &str) -> PatchLineClass {
+    if line.starts_with("diff --git") || line.starts_with("+++ ") || line.starts_with("--- ") {
+        PatchLineClass::FileHeader
+    } else if line.starts_with("@@") {
+        PatchLineClass::HunkHeader
+    } else if line.starts_with('+') {
+        PatchLineClass::Added
+    } else if line.starts_with('-') {
+        PatchLineClass::Removed
+    } else {
+        PatchLineClass::Context
+    }
+}
+
+// One screen row of the side-by-side view
+enum SideBySideLine<'a> {
+    File(&'a str),
+    HunkHeader(&'a str),
+    Lines(DiffRow<'a>),
+}
+
+/// A diff that can be shown either side by side or as the raw patch
+pub struct DiffView {
+    raw_lines: Vec<(PatchLineClass, String)>,
+    hunks: Vec<DiffHunkView>,
+    side_by_side: bool,
+    highlight_words: bool, // Off by default, the visible modified lines are diffed again every frame
+}
+
+impl DiffView {
+    pub fn new(raw: String) -> Self {
+        let hunks = parse_unified_diff(&raw);
+        let raw_lines = raw
+            .lines()
+            .map(|line| (classify_patch_line(line), line.to_string()))
+            .collect();
+        Self {
+            raw_lines,
+            hunks,
+            side_by_side: true,
+            highlight_words: false,
+        }
+    }
+
+    pub fn render(&mut self, ui: &mut egui::Ui, id_source: &str, max_height: f32) {
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.side_by_side, true, "Side by side");
+            ui.selectable_value(&mut self.side_by_side, false, "Raw patch");
+            if self.side_by_side {
+                ui.separator();
+                ui.checkbox(&mut self.highlight_words, "Highlight changed words")
+                    .on_hover_text("Mark the words that changed within modified lines. Slower on large diffs.");
+            }
+        });
+        
+        ui.add_space(5.0);
+        
+        let scroll_area = egui::ScrollArea::both()
+            .id_source(id_source)
+            .max_height(max_height)
+            .auto_shrink([false, true]);
+        
+        // Only lay out the visible rows so thousand-line patches stay responsive
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        if self.side_by_side && !self.hunks.is_empty() {
+            let lines = self.side_by_side_lines();
+            scroll_area.show_rows(ui, row_height, lines.len(), |ui, row_range| {
+                ui.spacing_mut().item_spacing.y = 0.0;
+                self.render_side_by_side(ui, &lines[row_range]);
+            });
+        } else {
+            scroll_area.show_rows(ui, row_height, self.raw_lines.len(), |ui, row_range| {
+                ui.spacing_mut().item_spacing.y = 0.0;
+                for (class, line) in &self.raw_lines[row_range] {
+                    ui.add(egui::Label::new(Self::patch_line_text(*class, line)).wrap(false));
+                }
+            });
+        }
+    }
+    
+    fn patch_line_text(class: PatchLineClass, line: &str) -> egui::RichText {
+        let text = egui::RichText::new(line).monospace();
+        match class {
+            PatchLineClass::FileHeader => text.strong(),
+            PatchLineClass::HunkHeader => text.color(egui::Color32::from_rgb(100, 200, 220)),
+            PatchLineClass::Added => text.color(egui::Color32::from_rgb(100, 200, 100)),
+            PatchLineClass::Removed => text.color(egui::Color32::from_rgb(220, 100, 100)),
+            PatchLineClass::Context => text,
+        }
+    }
+
+    // Every hunk as screen rows of the same height, with the file name before the first hunk of each file
+    fn side_by_side_lines(&self) -> Vec<SideBySideLine<'_>> {
+        let mut lines = Vec::new();
+        let mut previous_file = None;
+        
+        for hunk in &self.hunks {
+            if previous_file != Some(&hunk.file) {
+                lines.push(SideBySideLine::File(&hunk.file));
+                previous_file = Some(&hunk.file);
+            }
+            lines.push(SideBySideLine::HunkHeader(&hunk.header));
+            lines.extend(hunk.side_by_side_rows().into_iter().map(SideBySideLine::Lines));
+        }
+        lines
+    }
+
+    fn render_side_by_side(&self, ui: &mut egui::Ui, lines: &[SideBySideLine<'_>]) {
+        let column_width = ((ui.available_width() - ui.spacing().item_spacing.x) / 2.0).max(200.0);
+        
+        for line in lines {
+            let (old, new) = match line {
+                SideBySideLine::File(file) => {
+                    ui.add(egui::Label::new(egui::RichText::new(*file).monospace().strong()).wrap(false));
+                    continue;
+                },
+                SideBySideLine::HunkHeader(header) => {
+                    ui.add(egui::Label::new(egui::RichText::new(*header).monospace().color(egui::Color32::from_rgb(100, 200, 220))).wrap(false));
+                    continue;
+                },
+                SideBySideLine::Lines(row) => *row,
+            };
+            
+            // Only a removed line next to the added line that replaced it has words to compare
+            let segments = match (old, new) {
+                (Some(old), Some(new)) if self.highlight_words && old.kind == DiffLineKind::Removed && new.kind == DiffLineKind::Added => {
+                    Some(word_diff(&old.content, &new.content))
+                },
+                _ => None,
+            };
+            
+            ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.y = 0.0;
+                Self::render_cell(ui, column_width, old, segments.as_deref(), |line| line.old_line);
+                Self::render_cell(ui, column_width, new, segments.as_deref(), |line| line.new_line);
+            });
+        }
+    }
+
+    // The words of one side of a modified line, with the changed ones on a brighter background
+    fn highlighted_words(ui: &egui::Ui, kind: DiffLineKind, segments: &[DiffSegment]) -> egui::text::LayoutJob {
+        let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+        let color = ui.visuals().text_color();
+        let highlight = match kind {
+            DiffLineKind::Removed => egui::Color32::from_rgb(150, 50, 50),
+            _ => egui::Color32::from_rgb(45, 120, 55),
+        };
+        
+        let mut job = egui::text::LayoutJob::default();
+        for segment in segments {
+            let (text, background) = match (segment, kind) {
+                (DiffSegment::Unchanged(text), _) => (text, egui::Color32::TRANSPARENT),
+                (DiffSegment::Removed(text), DiffLineKind::Removed) | (DiffSegment::Added(text), DiffLineKind::Added) => (text, highlight),
+                // Words of the other side
+                _ => continue,
+            };
+            job.append(text, 0.0, egui::TextFormat { font_id: font_id.clone(), color, background, ..Default::default() });
+        }
+        job
+    }
+
+    fn render_cell(
+        ui: &mut egui::Ui,
+        width: f32,
+        line: Option<&DiffLineView>,
+        segments: Option<&[DiffSegment]>,
+        line_number: impl Fn(&DiffLineView) -> Option<usize>,
+    ) {
+        let fill = match line.map(|line| line.kind) {
+            Some(DiffLineKind::Added) => egui::Color32::from_rgb(30, 70, 35),
+            Some(DiffLineKind::Removed) => egui::Color32::from_rgb(85, 35, 35),
+            Some(DiffLineKind::Context) => egui::Color32::TRANSPARENT,
+            None => egui::Color32::from_rgb(45, 45, 55),
+        };
+        
+        egui::Frame::none().fill(fill).show(ui, |ui| {
+            ui.set_width(width);
+            ui.horizontal(|ui| {
+                let number = line
+                    .and_then(line_number)
+                    .map(|number| format!("{:>5}", number))
+                    .unwrap_or_else(|| " ".repeat(5));
+                ui.label(egui::RichText::new(number).monospace().weak());
+                
+                if let Some(line) = line {
+                    match segments {
+                        Some(segments) => ui.label(Self::highlighted_words(ui, line.kind, segments)),
+                        None => ui.label(egui::RichText::new(&line.content).monospace()),
+                    };
+                    if line.no_newline_at_eof {
+                        ui.label(egui::RichText::new("(no newline at end of file)").weak().size(11.0));
+                    }
+                }
+            });
+        });
+    }
+}
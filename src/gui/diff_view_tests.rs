@@ -0,0 +1,178 @@
+#[cfg(test)]
+mod tests {
+    use crate::gui::diff_view::{classify_patch_line, parse_unified_diff, word_diff, DiffLineKind, DiffSegment, PatchLineClass};
+
+    const MODIFIED_FILE: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 83db48f..bf269f4 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,4 +1,4 @@ mod config;
+ use std::fs;
+-fn old_name() {}
++fn new_name() {}
+
+ fn unchanged() {}
+";
+
+    #[test]
+    fn test_parse_modified_file() {
+        let hunks = parse_unified_diff(MODIFIED_FILE);
+        assert_eq!(hunks.len(), 1);
+        
+        let hunk = &hunks[0];
+        assert_eq!(hunk.file, "src/lib.rs");
+        assert_eq!(hunk.header, "@@ -1,4 +1,4 @@ mod config;");
+        assert_eq!(hunk.old_start, 1);
+        assert_eq!(hunk.new_start, 1);
+        
+        let kinds: Vec<DiffLineKind> = hunk.lines.iter().map(|line| line.kind).collect();
+        assert_eq!(kinds, vec![
+            DiffLineKind::Context,
+            DiffLineKind::Removed,
+            DiffLineKind::Added,
+            DiffLineKind::Context,
+            DiffLineKind::Context,
+        ]);
+        
+        assert_eq!(hunk.lines[1].content, "fn old_name() {}");
+        assert_eq!((hunk.lines[1].old_line, hunk.lines[1].new_line), (Some(2), None));
+        assert_eq!((hunk.lines[2].old_line, hunk.lines[2].new_line), (None, Some(2)));
+        assert_eq!((hunk.lines[4].old_line, hunk.lines[4].new_line), (Some(4), Some(4)));
+        assert_eq!(hunk.lines[3].content, "");
+    }
+
+    #[test]
+    fn test_side_by_side_rows_pair_changes() {
+        let hunks = parse_unified_diff(MODIFIED_FILE);
+        let rows = hunks[0].side_by_side_rows();
+        
+        assert_eq!(rows.len(), 4);
+        let (old, new) = rows[1];
+        assert_eq!(old.unwrap().content, "fn old_name() {}");
+        assert_eq!(new.unwrap().content, "fn new_name() {}");
+        
+        // Context lines appear on both sides
+        let (old, new) = rows[0];
+        assert_eq!(old, new);
+    }
+
+    #[test]
+    fn test_parse_multiple_hunks_and_files() {
+        let diff = "\
+diff --git a/a.txt b/a.txt
+--- a/a.txt
++++ b/a.txt
+@@ -1,2 +1,3 @@
+ one
++two
+ three
+@@ -10 +11,0 @@
+-ten
+diff --git a/b.txt b/b.txt
+new file mode 100644
+--- /dev/null
++++ b/b.txt
+@@ -0,0 +1,2 @@
++--- not a header
++last
+";
+        let hunks = parse_unified_diff(diff);
+        assert_eq!(hunks.len(), 3);
+        
+        assert_eq!(hunks[0].file, "a.txt");
+        assert_eq!(hunks[0].lines.len(), 3);
+        
+        assert_eq!(hunks[1].file, "a.txt");
+        assert_eq!(hunks[1].lines.len(), 1);
+        assert_eq!(hunks[1].lines[0].kind, DiffLineKind::Removed);
+        assert_eq!(hunks[1].lines[0].old_line, Some(10));
+        
+        // Content that looks like a file header is still a content line inside a hunk
+        assert_eq!(hunks[2].file, "b.txt");
+        assert_eq!(hunks[2].lines.len(), 2);
+        assert_eq!(hunks[2].lines[0].kind, DiffLineKind::Added);
+        assert_eq!(hunks[2].lines[0].content, "--- not a header");
+        assert_eq!(hunks[2].lines[1].new_line, Some(2));
+    }
+
+    #[test]
+    fn test_parse_no_newline_at_eof_marker() {
+        let diff = "\
+diff --git a/notes.txt b/notes.txt
+--- a/notes.txt
++++ b/notes.txt
+@@ -1 +1 @@
+-old
+\\ No newline at end of file
++new
+\\ No newline at end of file
+";
+        let hunks = parse_unified_diff(diff);
+        assert_eq!(hunks.len(), 1);
+        
+        let lines = &hunks[0].lines;
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].no_newline_at_eof);
+        assert_eq!(lines[0].content, "old");
+        assert!(lines[1].no_newline_at_eof);
+        assert_eq!(lines[1].content, "new");
+    }
+
+    #[test]
+    fn test_parse_empty_and_binary_diffs() {
+        assert!(parse_unified_diff("").is_empty());
+        
+        let binary = "\
+diff --git a/logo.png b/logo.png
+index 1111111..2222222 100644
+Binary files a/logo.png and b/logo.png differ
+";
+        assert!(parse_unified_diff(binary).is_empty());
+    }
+
+    #[test]
+    fn test_classify_patch_lines() {
+        assert_eq!(classify_patch_line("diff --git a/a.txt b/a.txt"), PatchLineClass::FileHeader);
+        assert_eq!(classify_patch_line("--- a/a.txt"), PatchLineClass::FileHeader);
+        assert_eq!(classify_patch_line("+++ b/a.txt"), PatchLineClass::FileHeader);
+        assert_eq!(classify_patch_line("@@ -1,2 +1,2 @@ fn main"), PatchLineClass::HunkHeader);
+        assert_eq!(classify_patch_line("+added"), PatchLineClass::Added);
+        assert_eq!(classify_patch_line("-removed"), PatchLineClass::Removed);
+        assert_eq!(classify_patch_line(" context"), PatchLineClass::Context);
+        assert_eq!(classify_patch_line("index 83db48f..bf269f4 100644"), PatchLineClass::Context);
+        assert_eq!(classify_patch_line(""), PatchLineClass::Context);
+    }
+
+    #[test]
+    fn test_word_diff_marks_changed_words() {
+        assert_eq!(word_diff("let x = 1;", "let x = 2;"), vec![
+            DiffSegment::Unchanged("let x = ".to_string()),
+            DiffSegment::Removed("1".to_string()),
+            DiffSegment::Added("2".to_string()),
+            DiffSegment::Unchanged(";".to_string()),
+        ]);
+        
+        assert_eq!(word_diff("the quick fox", "the quick brown fox"), vec![
+            DiffSegment::Unchanged("the quick ".to_string()),
+            DiffSegment::Added("brown ".to_string()),
+            DiffSegment::Unchanged("fox".to_string()),
+        ]);
+        
+        // Words are compared whole, not character by character
+        assert_eq!(word_diff("call(old_name)", "call(new_name)"), vec![
+            DiffSegment::Unchanged("call(".to_string()),
+            DiffSegment::Removed("old_name".to_string()),
+            DiffSegment::Added("new_name".to_string()),
+            DiffSegment::Unchanged(")".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_word_diff_edge_cases() {
+        assert_eq!(word_diff("same line", "same line"), vec![DiffSegment::Unchanged("same line".to_string())]);
+        assert_eq!(word_diff("", "added"), vec![DiffSegment::Added("added".to_string())]);
+        assert_eq!(word_diff("gone now", ""), vec![DiffSegment::Removed("gone now".to_string())]);
+        assert!(word_diff("", "").is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+use eframe::egui;
+use crate::core::error_handler::ResultStatus;
+use crate::core::operation_log::{LogEntry, LogFilter, OperationLog, LOGGED_OPERATIONS};
+
+// Entries shown at most, the file itself keeps everything
+const HISTORY_LIMIT: usize = 500;
+
+pub struct HistoryViewer {
+    log: OperationLog,
+    filter: LogFilter,
+    entries: Vec<LogEntry>, // Newest first
+    error_message: Option<String>,
+}
+
+impl HistoryViewer {
+    pub fn new(log: OperationLog) -> Self {
+        Self {
+            log,
+            filter: LogFilter::default(),
+            entries: Vec::new(),
+            error_message: None,
+        }
+    }
+    
+    /// Add the results of a finished operation to the history and show them
+    pub fn record(&mut self, operation: &str, results: &[(String, ResultStatus, String)]) {
+        if let Err(e) = self.log.record(operation, results) {
+            self.error_message = Some(format!("Failed to write the history to {}: {}", self.log.path().display(), e));
+            return;
+        }
+        self.refresh();
+    }
+    
+    /// Read the entries matching the filter again
+    pub fn refresh(&mut self) {
+        match self.log.recent(&self.filter, HISTORY_LIMIT) {
+            Ok(entries) => {
+                self.entries = entries;
+                self.error_message = None;
+            },
+            Err(e) => self.error_message = Some(format!("Failed to read the history from {}: {}", self.log.path().display(), e)),
+        }
+    }
+    
+    pub fn render(&mut self, ui: &mut egui::Ui) {
+        ui.heading("🕘 Operation History");
+        ui.separator();
+        
+        self.render_filters(ui);
+        
+        if let Some(error) = &self.error_message {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("⚠").color(egui::Color32::YELLOW));
+                ui.label(egui::RichText::new(error).color(egui::Color32::YELLOW));
+            });
+            ui.add_space(10.0);
+        }
+        
+        if self.entries.is_empty() {
+            ui.vertical_centered(|ui| {
+                ui.add_space(30.0);
+                ui.label(egui::RichText::new("No pushes, pulls, fetches or clones recorded").weak());
+                ui.add_space(30.0);
+            });
+            return;
+        }
+        
+        egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+            egui::Grid::new("operation_history").striped(true).num_columns(4).show(ui, |ui| {
+                ui.label(egui::RichText::new("Time").strong());
+                ui.label(egui::RichText::new("Operation").strong());
+                ui.label(egui::RichText::new("Repository").strong());
+                ui.label(egui::RichText::new("Status").strong());
+                ui.end_row();
+                
+                for entry in &self.entries {
+                    let color = match entry.status {
+                        ResultStatus::Success => egui::Color32::from_rgb(100, 200, 100),
+                        ResultStatus::Skipped => egui::Color32::GRAY,
+                        ResultStatus::Conflict | ResultStatus::Failed => egui::Color32::from_rgb(230, 100, 100),
+                    };
+                    
+                    ui.label(entry.formatted_time());
+                    ui.label(&entry.operation);
+                    ui.label(&entry.repo);
+                    ui.label(egui::RichText::new(&entry.message).color(color));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+    
+    fn render_filters(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        
+        ui.horizontal(|ui| {
+            ui.label("Repository:");
+            changed |= ui.add(egui::TextEdit::singleline(&mut self.filter.repo).hint_text("any").desired_width(150.0)).changed();
+            
+            ui.label("Operation:");
+            let selected = if self.filter.operation.is_empty() { "all" } else { self.filter.operation.as_str() };
+            egui::ComboBox::from_id_source("history_operation")
+                .selected_text(selected.to_string())
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut self.filter.operation, String::new(), "all").changed();
+                    for operation in LOGGED_OPERATIONS {
+                        changed |= ui.selectable_value(&mut self.filter.operation, operation.to_string(), operation).changed();
+                    }
+                });
+            
+            if ui.button("🔄 Refresh").clicked() {
+                changed = true;
+            }
+        });
+        ui.add_space(5.0);
+        
+        if changed {
+            self.refresh();
+        }
+    }
+}
@@ -1,5 +1,22 @@
 pub mod app;
 pub mod commit_history_viewer;
+pub mod comparison_viewer;
+pub mod confirmation;
+pub mod diff_view;
+pub mod history_viewer;
+pub mod operation_runner;
+pub mod settings;
+pub mod undo;
 
 #[cfg(test)]
-mod app_tests;
\ No newline at end of file
+mod app_tests;
+#[cfg(test)]
+mod confirmation_tests;
+#[cfg(test)]
+mod diff_view_tests;
+#[cfg(test)]
+mod operation_runner_tests;
+#[cfg(test)]
+mod settings_tests;
+#[cfg(test)]
+mod undo_tests;
\ No newline at end of file
@@ -0,0 +1,98 @@
+use crate::core::error_handler::ResultStatus;
+use crate::core::git_operations::TransferProgress;
+use std::sync::mpsc;
+
+/// Runs a batch operation on a background thread so the window stays responsive.
+/// The work reports one `(repo_name, status, message)` result per repository, which the UI
+/// picks up with `poll` on every frame. `K` tells the UI what finished.
+pub struct OperationRunner<K> {
+    receiver: Option<mpsc::Receiver<(String, ResultStatus, String)>>,
+    progress_receiver: Option<mpsc::Receiver<(String, TransferProgress)>>,
+    transfers: Vec<(String, TransferProgress)>,
+    kind: Option<K>,
+}
+
+impl<K> Default for OperationRunner<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> OperationRunner<K> {
+    pub fn new() -> Self {
+        Self {
+            receiver: None,
+            progress_receiver: None,
+            transfers: Vec::new(),
+            kind: None,
+        }
+    }
+    
+    /// True while the work is running, i.e. until its end of the channel is dropped
+    pub fn is_running(&self) -> bool {
+        self.receiver.is_some()
+    }
+    
+    /// Latest transfer progress of each repository, in the order the transfers started
+    pub fn transfers(&self) -> &[(String, TransferProgress)] {
+        &self.transfers
+    }
+    
+    /// Start `work` on a new thread. Does nothing and returns false if an operation is already running.
+    pub fn start<F>(&mut self, kind: K, work: F) -> bool
+    where
+        F: FnOnce(&mut dyn FnMut((String, ResultStatus, String)), &(dyn Fn(&str, TransferProgress) + Sync)) + Send + 'static,
+    {
+        if self.is_running() {
+            return false;
+        }
+        
+        let (sender, receiver) = mpsc::channel();
+        let (progress_sender, progress_receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            // The UI may have gone away, there's nobody left to tell
+            work(
+                &mut |result| {
+                    let _ = sender.send(result);
+                },
+                &|repo_name, transfer| {
+                    let _ = progress_sender.send((repo_name.to_string(), transfer));
+                },
+            );
+        });
+        
+        self.receiver = Some(receiver);
+        self.progress_receiver = Some(progress_receiver);
+        self.transfers.clear();
+        self.kind = Some(kind);
+        true
+    }
+    
+    /// Move the results that arrived since the last call into `results` and update the transfer progress.
+    /// Returns the kind of the operation once, on the call that sees it finish.
+    pub fn poll(&mut self, results: &mut Vec<(String, ResultStatus, String)>) -> Option<K> {
+        if let Some(progress_receiver) = &self.progress_receiver {
+            for (repo_name, transfer) in progress_receiver.try_iter() {
+                match self.transfers.iter_mut().find(|(name, _)| *name == repo_name) {
+                    Some((_, latest)) => *latest = transfer,
+                    None => self.transfers.push((repo_name, transfer)),
+                }
+            }
+        }
+        
+        let receiver = self.receiver.as_ref()?;
+        
+        loop {
+            match receiver.try_recv() {
+                Ok(result) => results.push(result),
+                Err(mpsc::TryRecvError::Empty) => return None,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.receiver = None;
+                    self.progress_receiver = None;
+                    self.transfers.clear();
+                    return self.kind.take();
+                }
+            }
+        }
+    }
+}
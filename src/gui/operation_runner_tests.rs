@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::error_handler::ResultStatus;
+    use crate::core::git_operations::TransferProgress;
+    use crate::gui::operation_runner::OperationRunner;
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    // Poll until the operation finishes, failing the test if it hangs
+    fn poll_until_finished(runner: &mut OperationRunner<&'static str>, results: &mut Vec<(String, ResultStatus, String)>) -> &'static str {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            if let Some(kind) = runner.poll(results) {
+                return kind;
+            }
+            assert!(Instant::now() < deadline, "operation didn't finish");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_results_stream_in_before_the_operation_finishes() {
+        let mut runner = OperationRunner::new();
+        let (release, wait) = mpsc::channel::<()>();
+        
+        assert!(runner.start("push", move |report, _| {
+            report(("repo1".to_string(), ResultStatus::Success, "Success".to_string()));
+            // Hold the second repository back until the test has seen the first
+            wait.recv().unwrap();
+            report(("repo2".to_string(), ResultStatus::Failed, "Network timeout after 30 seconds".to_string()));
+        }));
+        assert!(runner.is_running());
+        
+        let mut results = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while results.is_empty() {
+            assert_eq!(runner.poll(&mut results), None);
+            assert!(Instant::now() < deadline, "first result never arrived");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(results, vec![("repo1".to_string(), ResultStatus::Success, "Success".to_string())]);
+        assert!(runner.is_running());
+        
+        release.send(()).unwrap();
+        assert_eq!(poll_until_finished(&mut runner, &mut results), "push");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].0, "repo2");
+        assert!(!runner.is_running());
+        
+        // The finish is only reported once
+        assert_eq!(runner.poll(&mut results), None);
+    }
+
+    #[test]
+    fn test_only_one_operation_runs_at_a_time() {
+        let mut runner = OperationRunner::new();
+        let (release, wait) = mpsc::channel::<()>();
+        
+        assert!(runner.start("pull", move |_, _| {
+            wait.recv().unwrap();
+        }));
+        assert!(!runner.start("fetch", |_, _| {}));
+        
+        release.send(()).unwrap();
+        let mut results = Vec::new();
+        assert_eq!(poll_until_finished(&mut runner, &mut results), "pull");
+        assert!(results.is_empty());
+    }
+    #[test]
+    fn test_transfer_progress_is_kept_per_repository() {
+        let mut runner = OperationRunner::new();
+        let (release, wait) = mpsc::channel::<()>();
+        let halfway = TransferProgress { objects_done: 12, total_objects: 27, bytes: 4096 };
+        
+        assert!(runner.start("fetch", move |_, progress| {
+            progress("repo1", TransferProgress { objects_done: 1, total_objects: 27, bytes: 512 });
+            progress("repo1", halfway);
+            progress("repo2", TransferProgress::default());
+            wait.recv().unwrap();
+        }));
+        
+        let mut results = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while runner.transfers().len() < 2 || runner.transfers()[0].1 != halfway {
+            assert_eq!(runner.poll(&mut results), None);
+            assert!(Instant::now() < deadline, "progress never arrived");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(runner.transfers()[0], ("repo1".to_string(), halfway));
+        assert_eq!(runner.transfers()[1].0, "repo2");
+        
+        // Progress is only shown while the operation runs
+        release.send(()).unwrap();
+        poll_until_finished(&mut runner, &mut results);
+        assert!(runner.transfers().is_empty());
+    }
+}
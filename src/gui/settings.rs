@@ -0,0 +1,63 @@
+use anyhow::Result;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use crate::gui::app::Tab;
+
+/// Where the GUI keeps its own preferences, next to `repos.json`
+pub const SETTINGS_FILE: &str = "settings.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl Theme {
+    pub fn toggled(self) -> Self {
+        match self {
+            Theme::Dark => Theme::Light,
+            Theme::Light => Theme::Dark,
+        }
+    }
+    
+    /// The accent colors are picked for the dark background, darken them on the light one
+    pub fn accent(self, color: egui::Color32) -> egui::Color32 {
+        match self {
+            Theme::Dark => color,
+            Theme::Light => {
+                let darken = |channel: u8| (channel as f32 * 0.55) as u8;
+                egui::Color32::from_rgb(darken(color.r()), darken(color.g()), darken(color.b()))
+            }
+        }
+    }
+}
+
+/// Preferences of the GUI that are kept between runs
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AppSettings {
+    #[serde(default)]
+    pub theme: Theme,
+    #[serde(default)]
+    pub window_size: Option<[f32; 2]>, // Inner size of the window when it was last closed
+    #[serde(default)]
+    pub active_tab: Tab,
+}
+
+impl AppSettings {
+    /// Read the settings, falling back to the defaults when the file is missing or unreadable
+    pub fn load(path: &str) -> Self {
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&contents).unwrap_or_else(|e| {
+            warn!("Ignoring unreadable settings in {}: {}", path, e);
+            Self::default()
+        })
+    }
+    
+    pub fn save(&self, path: &str) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use crate::gui::app::Tab;
+    use crate::gui::settings::{AppSettings, Theme};
+
+    #[test]
+    fn test_theme_toggles() {
+        assert_eq!(Theme::default(), Theme::Dark);
+        assert_eq!(Theme::Dark.toggled(), Theme::Light);
+        assert_eq!(Theme::Light.toggled(), Theme::Dark);
+    }
+
+    #[test]
+    fn test_accent_is_darkened_only_on_light_theme() {
+        let accent = egui::Color32::from_rgb(200, 150, 200);
+        assert_eq!(Theme::Dark.accent(accent), accent);
+        assert_eq!(Theme::Light.accent(accent), egui::Color32::from_rgb(110, 82, 110));
+    }
+
+    #[test]
+    fn test_settings_serde_round_trip() {
+        let settings = AppSettings {
+            theme: Theme::Light,
+            window_size: Some([1024.0, 768.0]),
+            active_tab: Tab::Repositories,
+        };
+        let json = serde_json::to_string(&settings).unwrap();
+        assert_eq!(serde_json::from_str::<AppSettings>(&json).unwrap(), settings);
+        
+        // Settings written before the window and tab were kept
+        let older: AppSettings = serde_json::from_str(r#"{"theme": "Light"}"#).unwrap();
+        assert_eq!(older.window_size, None);
+        assert_eq!(older.active_tab, Tab::Commit);
+    }
+
+    #[test]
+    fn test_settings_keep_theme_between_runs() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("settings.json").to_string_lossy().to_string();
+        
+        // Nothing saved yet
+        assert_eq!(AppSettings::load(&path), AppSettings::default());
+        
+        let settings = AppSettings { theme: Theme::Light, ..Default::default() };
+        settings.save(&path).unwrap();
+        assert_eq!(AppSettings::load(&path).theme, Theme::Light);
+        
+        // A broken file doesn't keep the app from starting
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(AppSettings::load(&path), AppSettings::default());
+    }
+}
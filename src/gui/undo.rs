@@ -0,0 +1,57 @@
+use crate::core::repository::RepoConfig;
+
+/// How many configuration edits can be undone
+pub const UNDO_LIMIT: usize = 20;
+
+/// Snapshots of the configuration taken before each edit, newest last.
+/// The oldest snapshot is dropped once the limit is reached.
+pub struct UndoStack {
+    snapshots: Vec<RepoConfig>,
+    limit: usize,
+}
+
+impl Default for UndoStack {
+    fn default() -> Self {
+        Self::new(UNDO_LIMIT)
+    }
+}
+
+impl UndoStack {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            snapshots: Vec::new(),
+            limit,
+        }
+    }
+    
+    /// Remember the configuration as it was before an edit
+    pub fn push(&mut self, snapshot: RepoConfig) {
+        // A limit of zero keeps nothing to undo
+        if self.limit == 0 {
+            return;
+        }
+        if self.snapshots.len() == self.limit {
+            self.snapshots.remove(0);
+        }
+        self.snapshots.push(snapshot);
+    }
+    
+    /// Put the configuration back to the last snapshot. Returns false, leaving it alone, when there is nothing to undo.
+    pub fn undo(&mut self, config: &mut RepoConfig) -> bool {
+        match self.snapshots.pop() {
+            Some(snapshot) => {
+                *config = snapshot;
+                true
+            },
+            None => false,
+        }
+    }
+    
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::repository::{RepoConfig, RepositoryInfo};
+    use crate::gui::undo::{UndoStack, UNDO_LIMIT};
+
+    fn config_with(names: &[&str]) -> RepoConfig {
+        let mut config = RepoConfig::new();
+        for name in names {
+            config.add_repository(RepositoryInfo::new(name.to_string(), format!("https://github.com/user/{}.git", name)));
+        }
+        config
+    }
+
+    fn names(config: &RepoConfig) -> Vec<String> {
+        config.repositories.iter().map(|repo| repo.name.clone()).collect()
+    }
+
+    #[test]
+    fn test_undo_restores_removed_repository() {
+        let mut config = config_with(&["repo1", "repo2", "repo3"]);
+        let mut undo_stack = UndoStack::default();
+        
+        undo_stack.push(config.clone());
+        config.remove_repository(1);
+        assert_eq!(names(&config), vec!["repo1", "repo3"]);
+        
+        assert!(undo_stack.undo(&mut config));
+        assert_eq!(names(&config), vec!["repo1", "repo2", "repo3"]);
+        assert!(undo_stack.is_empty());
+    }
+
+    #[test]
+    fn test_undo_on_empty_stack_is_a_no_op() {
+        let mut config = config_with(&["repo1"]);
+        let mut undo_stack = UndoStack::default();
+        
+        assert!(!undo_stack.undo(&mut config));
+        assert_eq!(names(&config), vec!["repo1"]);
+    }
+
+    #[test]
+    fn test_undo_stack_drops_oldest_snapshots() {
+        let mut undo_stack = UndoStack::default();
+        let mut config = config_with(&[]);
+        for i in 0..UNDO_LIMIT + 5 {
+            undo_stack.push(config.clone());
+            config.add_repository(RepositoryInfo::new(format!("repo{}", i), format!("https://github.com/user/repo{}.git", i)));
+        }
+        assert_eq!(undo_stack.len(), UNDO_LIMIT);
+        
+        // Undoing everything stops at the oldest snapshot kept
+        while undo_stack.undo(&mut config) {}
+        assert_eq!(config.repositories.len(), 5);
+    }
+
+    #[test]
+    fn test_undo_stack_without_room_keeps_nothing() {
+        let mut undo_stack = UndoStack::new(0);
+        let mut config = config_with(&["repo1"]);
+        undo_stack.push(config.clone());
+        
+        assert!(undo_stack.is_empty());
+        assert!(!undo_stack.undo(&mut config));
+    }
+}
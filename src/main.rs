@@ -3,71 +3,93 @@ mod cli;
 mod gui;
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 use std::sync::{Arc, Mutex};
 use std::fs;
+use std::time::Duration;
 
 use crate::core::repository::RepoConfig;
+use crate::core::config_migration::migrate;
+use crate::cli::args::{Args, Command};
 use crate::cli::runner::run_cli;
-
-#[derive(Parser, Debug)]
-#[clap(author, version, about, long_about = None)]
-struct Args {
-    /// Commit message
-    #[clap(short, long, default_value = "Auto commit")]
-    message: String,
-
-    /// Branch name
-    #[clap(short, long, default_value = "main")]
-    branch: String,
-    
-    /// Run in GUI mode
-    #[clap(long, action)]
-    gui: bool,
-}
+use crate::cli::daemon::{daemon_command, run_daemon};
+use crate::cli::watch::run_watch;
+use crate::gui::settings::{AppSettings, SETTINGS_FILE};
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // Logging goes to stderr and is configured with RUST_LOG, e.g. RUST_LOG=info
+    env_logger::init();
+    
     let args = Args::parse();
+    let verbosity = args.verbosity();
     
     // Load repository configuration
     let config = load_repo_config()?;
+    
+    // Point out problems now rather than when a push fails on them, validate prints them itself
+    if !args.gui && !matches!(args.command, Some(Command::Validate { .. })) {
+        for problem in config.validate() {
+            eprintln!("Warning: {}", problem);
+        }
+    }
     let config_arc = Arc::new(Mutex::new(config));
     
     if args.gui {
         // Run GUI application
         run_gui(config_arc)?;
-    } else {
+    } else if args.daemon {
+        // Commit and push on a timer until Ctrl+C
+        let command = daemon_command(args.command)?;
+        run_daemon(config_arc, command, Duration::from_secs(args.interval), verbosity).await?;
+    } else if args.watch {
+        // Commit and push whenever files stop changing, until Ctrl+C
+        let command = daemon_command(args.command)?;
+        run_watch(config_arc, command, Duration::from_secs(args.quiet_period), verbosity).await?;
+    } else if let Some(command) = args.command {
         // Run CLI application
-        run_cli(config_arc, &args.message, &args.branch)?;
+        run_cli(config_arc, command, args.json, verbosity, save_repo_config)?;
+    } else {
+        Args::command().print_help()?;
     }
     
     Ok(())
 }
 
 fn run_gui(config: Arc<Mutex<RepoConfig>>) -> Result<()> {
+    // Window size, tab and theme from the last run
+    let settings = AppSettings::load(SETTINGS_FILE);
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([600.0, 400.0]) // Set to 600x400 as per user preference
+            .with_inner_size(settings.window_size.unwrap_or([600.0, 400.0])) // 600x400 on the first run as per user preference
             .with_min_inner_size([300.0, 220.0])
             .with_icon(
                 eframe::icon_data::from_png_bytes(&include_bytes!("../assets/icon.png")[..])
                     .unwrap_or_default(),
             ),
+        // The window size is restored from our own settings above
+        persist_window: false,
         ..Default::default()
     };
     
     eframe::run_native(
         "संधि",
         native_options,
-        Box::new(move |cc| Box::new(gui::app::MultiRepoPusherApp::new(cc, config, save_repo_config))),
+        Box::new(move |cc| Box::new(gui::app::MultiRepoPusherApp::new(cc, config, save_repo_config, settings))),
     )
     .map_err(|e| anyhow::anyhow!("Failed to start GUI: {}", e))
 }
 
 fn load_repo_config() -> Result<RepoConfig> {
     let config_str = fs::read_to_string("repos.json")?;
-    let config: RepoConfig = serde_json::from_str(&config_str)?;
+    // Upgrade files written by older versions before reading them into the current shape
+    let mut config = migrate(serde_json::from_str(&config_str)?)?;
+    
+    // Group members left behind when repositories were removed by hand
+    let pruned = config.prune_dangling_group_members();
+    if pruned > 0 {
+        log::warn!("Removed {} group members that are not configured repositories", pruned);
+    }
     Ok(config)
 }
 